@@ -0,0 +1,121 @@
+/*
+ * Copyright © 2019-2020 Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use grex::{generate, Feature, RegExpBuilder, RegExpConfig};
+
+// All inputs are generated deterministically, so the numbers are comparable across runs.
+
+fn char_class_test_cases(size: u32) -> Vec<String> {
+    // Every third code point is skipped to create many separate ranges.
+    (0x4e00..0x4e00 + size)
+        .filter(|it| it % 3 != 0)
+        .filter_map(std::char::from_u32)
+        .map(|it| it.to_string())
+        .collect()
+}
+
+fn contiguous_test_cases(size: u32) -> Vec<String> {
+    // The range crosses the surrogate code points, so it is only contiguous
+    // if U+D7FF and U+E000 are considered adjacent.
+    (0xd800 - size / 2..0xe000 + size / 2)
+        .filter_map(std::char::from_u32)
+        .map(|it| it.to_string())
+        .collect()
+}
+
+fn word_test_cases(size: usize) -> Vec<String> {
+    (0..size)
+        .map(|i| format!("{}{}", ["get", "set", "has", "is"][i % 4], i * 31 % 1000))
+        .collect()
+}
+
+fn bench_char_class(c: &mut Criterion) {
+    let mut group = c.benchmark_group("character class");
+    group.sample_size(10);
+    for size in [100, 500, 1_000].iter() {
+        let test_cases = char_class_test_cases(*size);
+        group.bench_with_input(BenchmarkId::new("ranges", size), &test_cases, |b, it| {
+            b.iter(|| RegExpBuilder::from(it).build())
+        });
+        group.bench_with_input(BenchmarkId::new("no ranges", size), &test_cases, |b, it| {
+            b.iter(|| {
+                RegExpBuilder::from(it)
+                    .with_range_compression_disabled()
+                    .build()
+            })
+        });
+    }
+    group.finish();
+}
+
+fn bench_range_compression(c: &mut Criterion) {
+    let mut group = c.benchmark_group("range compression");
+    group.sample_size(10);
+    for size in [100, 1_000, 10_000].iter() {
+        let test_cases = contiguous_test_cases(*size);
+        group.bench_with_input(BenchmarkId::from_parameter(size), &test_cases, |b, it| {
+            b.iter(|| RegExpBuilder::from(it).build())
+        });
+    }
+    group.finish();
+}
+
+fn bench_generate(c: &mut Criterion) {
+    let config = RegExpConfig::new();
+    let mut group = c.benchmark_group("generate");
+    for size in [10, 100, 1_000].iter() {
+        let test_cases = word_test_cases(*size);
+        group.bench_with_input(BenchmarkId::from_parameter(size), &test_cases, |b, it| {
+            b.iter(|| generate(it, &config))
+        });
+    }
+    group.finish();
+}
+
+// Run `cargo bench` and `cargo bench --features parallel` one after another
+// to compare the serial with the parallel creation of grapheme clusters.
+fn test_cases() -> Vec<String> {
+    (0..10_000)
+        .map(|i| format!("user-{}@host{}.example.com", i, i % 97))
+        .collect()
+}
+
+fn bench_build(c: &mut Criterion) {
+    let test_cases = test_cases();
+    let mut group = c.benchmark_group("10k test cases");
+    group.sample_size(10);
+    group.bench_function("default settings", |b| {
+        b.iter(|| RegExpBuilder::from(&test_cases).build())
+    });
+    group.bench_function("char classes and repetitions", |b| {
+        b.iter(|| {
+            RegExpBuilder::from(&test_cases)
+                .with_conversion_of(&[Feature::Digit, Feature::Word, Feature::Repetition])
+                .build()
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_char_class,
+    bench_range_compression,
+    bench_generate,
+    bench_build
+);
+criterion_main!(benches);
@@ -0,0 +1,88 @@
+/*
+ * Copyright © 2019-2020 Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+#![no_main]
+
+use grex::{generate, Feature, RegExpBuilder, RegExpConfig};
+use libfuzzer_sys::fuzz_target;
+use regex::Regex;
+
+fuzz_target!(|data: &[u8]| {
+    let input = match std::str::from_utf8(data) {
+        Ok(input) => input,
+        Err(_) => return,
+    };
+    let test_cases = input.split('\n').collect::<Vec<_>>();
+
+    for config in configs(&test_cases) {
+        let regexp = strip_ansi_escape_codes(&generate(&test_cases, &config));
+        let compiled_regexp = Regex::new(&regexp)
+            .unwrap_or_else(|error| panic!("invalid regex {:?}: {}", regexp, error));
+
+        for test_case in test_cases.iter() {
+            assert!(
+                compiled_regexp.is_match(test_case),
+                "regex {:?} does not match test case {:?}",
+                regexp,
+                test_case
+            );
+        }
+    }
+});
+
+fn configs(test_cases: &[&str]) -> Vec<RegExpConfig> {
+    vec![
+        RegExpBuilder::from(test_cases).config().clone(),
+        RegExpBuilder::from(test_cases)
+            .with_escaping_of_non_ascii_chars(false)
+            .config()
+            .clone(),
+        RegExpBuilder::from(test_cases)
+            .with_escaping_of_slashes()
+            .with_strict_escaping()
+            .config()
+            .clone(),
+        RegExpBuilder::from(test_cases)
+            .with_conversion_of(&[Feature::Repetition, Feature::CaseInsensitivity])
+            .with_escaping_of_non_ascii_chars(false)
+            .config()
+            .clone(),
+        RegExpBuilder::from(test_cases)
+            .with_syntax_highlighting()
+            .config()
+            .clone(),
+    ]
+}
+
+/// Removes the ANSI escape sequences added by syntax highlighting
+/// so that the regex crate sees the plain expression.
+fn strip_ansi_escape_codes(regexp: &str) -> String {
+    let mut result = String::with_capacity(regexp.len());
+    let mut chars = regexp.chars();
+
+    while let Some(c) = chars.next() {
+        if c == '\u{1b}' {
+            for c in chars.by_ref() {
+                if c.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+        } else {
+            result.push(c);
+        }
+    }
+    result
+}
@@ -0,0 +1,64 @@
+/*
+ * Copyright © 2019-2020 Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+#ifndef GREX_H
+#define GREX_H
+
+#include <stdbool.h>
+#include <stddef.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+#define GREX_OK 0
+#define GREX_NULL_POINTER 1
+#define GREX_INVALID_UTF8 2
+#define GREX_NO_INPUTS 3
+#define GREX_GENERATION_FAILED 4
+
+typedef struct GrexConfig {
+    bool is_digit_converted;
+    bool is_non_digit_converted;
+    bool is_space_converted;
+    bool is_non_space_converted;
+    bool is_word_converted;
+    bool is_non_word_converted;
+    bool is_repetition_converted;
+    bool is_case_insensitive_matching;
+    bool is_capturing_group_enabled;
+    bool is_non_ascii_char_escaped;
+    bool is_astral_code_point_converted_to_surrogate;
+} GrexConfig;
+
+/*
+ * Generates a regular expression from n null-terminated UTF-8 strings.
+ * If cfg is NULL, the default settings are used.
+ *
+ * On success, GREX_OK is returned and *out is set to a string allocated by grex.
+ * The caller owns this string and must release it with grex_free(), not with free().
+ * On failure, an error code is returned and *out is set to NULL.
+ */
+int grex_generate(const char *const *inputs, size_t n, const GrexConfig *cfg, char **out);
+
+/* Releases a string returned by grex_generate(). Passing NULL does nothing. */
+void grex_free(char *s);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* GREX_H */
@@ -0,0 +1,152 @@
+/*
+ * Copyright © 2019-2020 Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::ast::Expression;
+use crate::char::{char_range_len, Grapheme, GraphemeCluster, UNBOUNDED_REPETITIONS};
+use itertools::Itertools;
+use std::collections::BTreeSet;
+use std::ops::RangeInclusive;
+
+const MAX_LISTED_CHAR_CLASS_MEMBERS: usize = 10;
+
+impl Expression {
+    /// Describes this expression in plain English, e.g. `'ab' followed by one of [c, d]`.
+    pub(crate) fn explain(&self) -> String {
+        match self {
+            Expression::Alternation(options, _) => format!(
+                "one of ({})",
+                options.iter().map(|it| it.explain()).join(", ")
+            ),
+            Expression::CharacterClass(char_set, _) => explain_character_class(char_set),
+            Expression::CharacterRange(range, _) => explain_character_range(range),
+            Expression::Concatenation(expr1, expr2, _) => {
+                format!("{} followed by {}", expr1.explain(), expr2.explain())
+            }
+            Expression::Literal(cluster, _) => explain_literal(cluster),
+            Expression::Repetition(expr, quantifier, _) => {
+                let (min, max) = quantifier.bounds();
+                let unit = match **expr {
+                    Expression::Concatenation(_, _, _) => format!("({})", expr.explain()),
+                    _ => expr.explain(),
+                };
+                explain_repetition(&unit, min, max)
+            }
+        }
+    }
+}
+
+fn explain_character_class(char_set: &BTreeSet<char>) -> String {
+    if char_set.len() > MAX_LISTED_CHAR_CLASS_MEMBERS {
+        format!(
+            "one of {} characters between {} and {}",
+            char_set.len(),
+            quote(&char_set.iter().next().unwrap().to_string()),
+            quote(&char_set.iter().next_back().unwrap().to_string())
+        )
+    } else {
+        format!(
+            "one of [{}]",
+            char_set
+                .iter()
+                .map(|it| it.escape_debug().to_string())
+                .join(", ")
+        )
+    }
+}
+
+fn explain_character_range(range: &RangeInclusive<char>) -> String {
+    let char_count = char_range_len(range);
+    if char_count > MAX_LISTED_CHAR_CLASS_MEMBERS {
+        format!(
+            "one of {} characters between {} and {}",
+            char_count,
+            quote(&range.start().to_string()),
+            quote(&range.end().to_string())
+        )
+    } else {
+        explain_character_class(&range.clone().collect())
+    }
+}
+
+fn explain_literal(cluster: &GraphemeCluster) -> String {
+    if cluster.graphemes().is_empty() {
+        return "nothing".to_string();
+    }
+
+    let mut fragments = vec![];
+    let mut plain_text = String::new();
+
+    for grapheme in cluster.graphemes() {
+        let is_plain = !grapheme.has_repetitions()
+            && grapheme.minimum() == 1
+            && grapheme.maximum() == 1
+            && explain_shorthand(&grapheme.value()).is_none();
+
+        if is_plain {
+            plain_text.push_str(&grapheme.value());
+        } else {
+            if !plain_text.is_empty() {
+                fragments.push(quote(&plain_text));
+                plain_text.clear();
+            }
+            fragments.push(explain_grapheme(grapheme));
+        }
+    }
+    if !plain_text.is_empty() {
+        fragments.push(quote(&plain_text));
+    }
+
+    fragments.join(" followed by ")
+}
+
+fn explain_grapheme(grapheme: &Grapheme) -> String {
+    let unit = if grapheme.has_repetitions() {
+        let value = grapheme.repetitions.iter().map(|it| it.value()).join("");
+        explain_shorthand(&value).unwrap_or_else(|| quote(&value))
+    } else {
+        let value = grapheme.value();
+        explain_shorthand(&value).unwrap_or_else(|| quote(&value))
+    };
+    explain_repetition(&unit, grapheme.minimum(), grapheme.maximum())
+}
+
+fn explain_repetition(unit: &str, min: u32, max: u32) -> String {
+    match (min, max) {
+        (1, 1) => unit.to_string(),
+        (0, 1) => format!("optionally {}", unit),
+        (0, UNBOUNDED_REPETITIONS) => format!("{} repeated zero or more times", unit),
+        (_, UNBOUNDED_REPETITIONS) => format!("{} repeated at least {} times", unit, min),
+        _ if min == max => format!("{} repeated {} times", unit, min),
+        _ => format!("{} repeated {} to {} times", unit, min, max),
+    }
+}
+
+fn explain_shorthand(value: &str) -> Option<String> {
+    let description = match value {
+        "\\d" => "a digit",
+        "\\D" => "a non-digit",
+        "\\s" => "a whitespace character",
+        "\\S" => "a non-whitespace character",
+        "\\w" => "a word character",
+        "\\W" => "a non-word character",
+        _ => return None,
+    };
+    Some(description.to_string())
+}
+
+fn quote(value: &str) -> String {
+    format!("'{}'", value.escape_debug())
+}
@@ -14,27 +14,45 @@
  * limitations under the License.
  */
 
+use crate::ast::ordering::order_options_prefixes_last;
 use crate::ast::{Quantifier, Substring};
-use crate::char::{Grapheme, GraphemeCluster};
+use crate::char::{
+    char_range_len, without_colorization, Grapheme, GraphemeCluster, UNBOUNDED_REPETITIONS,
+    UNICODE_SCALAR_VALUE_COUNT,
+};
 use crate::fsm::DFA;
-use crate::regexp::RegExpConfig;
+use crate::regexp::{AlternationOrder, RegExpConfig};
 use itertools::EitherOrBoth::Both;
 use itertools::Itertools;
 use ndarray::{Array1, Array2};
 use petgraph::prelude::EdgeRef;
+use std::cmp::Reverse;
 use std::collections::BTreeSet;
+use std::ops::RangeInclusive;
+use std::sync::Arc;
 
+/// A node of the syntax tree of a regular expression.
+///
+/// The variants carry the settings which control how the node is rendered.
+/// Literals hold graphemes which cannot be created outside of this crate,
+/// so use the constructors [`literal`](#method.literal), [`character_class`](#method.character_class),
+/// [`character_class_from_range`](#method.character_class_from_range),
+/// [`alternation`](#method.alternation), [`concatenation`](#method.concatenation) and
+/// [`repetition`](#method.repetition) to build new nodes.
+/// New variants may be added in the future, so matches on it need a wildcard arm.
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
 pub enum Expression {
-    Alternation(Vec<Expression>, RegExpConfig),
-    CharacterClass(BTreeSet<char>, RegExpConfig),
-    Concatenation(Box<Expression>, Box<Expression>, RegExpConfig),
-    Literal(GraphemeCluster, RegExpConfig),
-    Repetition(Box<Expression>, Quantifier, RegExpConfig),
+    Alternation(Vec<Expression>, Arc<RegExpConfig>),
+    CharacterClass(BTreeSet<char>, Arc<RegExpConfig>),
+    CharacterRange(RangeInclusive<char>, Arc<RegExpConfig>),
+    Concatenation(Box<Expression>, Box<Expression>, Arc<RegExpConfig>),
+    Literal(GraphemeCluster, Arc<RegExpConfig>),
+    Repetition(Box<Expression>, Quantifier, Arc<RegExpConfig>),
 }
 
 impl Expression {
-    pub(crate) fn from(dfa: DFA, config: &RegExpConfig) -> Self {
+    pub(crate) fn from(dfa: DFA, config: &Arc<RegExpConfig>) -> Self {
         let states = dfa.states_in_depth_first_order();
         let state_count = dfa.state_count();
 
@@ -102,31 +120,642 @@ impl Expression {
         }
     }
 
-    fn new_alternation(expr1: Expression, expr2: Expression, config: &RegExpConfig) -> Self {
+    /// Converts alternations whose options are all single characters,
+    /// such as `a|é|ü`, to character classes, such as `[aéü]`. Alternations whose options
+    /// are all optional single characters, such as the noise characters in ` ?|-?`,
+    /// are converted to an optional character class, such as `[ -]?`.
+    pub(crate) fn convert_single_char_alternations(self) -> Self {
+        match self {
+            Expression::Alternation(options, config) => {
+                let options = options
+                    .into_iter()
+                    .map(|it| it.convert_single_char_alternations())
+                    .collect_vec();
+                if options.len() < config.alternation_to_class_threshold {
+                    return Expression::Alternation(options, config);
+                }
+                let char_set = options
+                    .iter()
+                    .map(|it| it.single_char())
+                    .collect::<Option<BTreeSet<char>>>();
+                let optional_char_set = options
+                    .iter()
+                    .map(|it| it.optional_single_char())
+                    .collect::<Option<BTreeSet<char>>>();
+                match (char_set, optional_char_set) {
+                    (Some(char_set), _) => Expression::CharacterClass(char_set, config),
+                    (None, Some(char_set)) => Expression::new_repetition(
+                        Expression::CharacterClass(char_set, config.clone()),
+                        Quantifier::QuestionMark,
+                        &config,
+                    ),
+                    _ => Expression::Alternation(options, config),
+                }
+            }
+            Expression::Concatenation(expr1, expr2, config) => Expression::Concatenation(
+                Box::new(expr1.convert_single_char_alternations()),
+                Box::new(expr2.convert_single_char_alternations()),
+                config,
+            ),
+            Expression::Repetition(expr, quantifier, config) => Expression::Repetition(
+                Box::new(expr.convert_single_char_alternations()),
+                quantifier,
+                config,
+            ),
+            _ => self,
+        }
+    }
+
+    /// Merges adjacent repetitions of the same unit in concatenations
+    /// by adding up their quantifiers, so that `a{2}a{3}` becomes `a{5}`.
+    pub(crate) fn merge_adjacent_repetitions(self) -> Self {
+        match self {
+            Expression::Alternation(options, config) => Expression::Alternation(
+                options
+                    .into_iter()
+                    .map(|it| it.merge_adjacent_repetitions())
+                    .collect_vec(),
+                config,
+            ),
+            Expression::Concatenation(expr1, expr2, config) => Self::concatenate_repetitions(
+                expr1.merge_adjacent_repetitions(),
+                expr2.merge_adjacent_repetitions(),
+                &config,
+            ),
+            Expression::Repetition(expr, quantifier, config) => {
+                Expression::new_repetition(expr.merge_adjacent_repetitions(), quantifier, &config)
+            }
+            _ => self,
+        }
+    }
+
+    fn concatenate_repetitions(
+        expr1: Expression,
+        expr2: Expression,
+        config: &Arc<RegExpConfig>,
+    ) -> Self {
+        let is_mergeable = match (expr1.last_factor(), expr2.first_factor()) {
+            (Expression::Repetition(unit1, _, _), Expression::Repetition(unit2, _, _)) => {
+                unit1 == unit2
+            }
+            _ => false,
+        };
+        if !is_mergeable {
+            return Expression::new_concatenation(expr1, expr2, config);
+        }
+        match (expr1, expr2) {
+            (Expression::Concatenation(first, last, _), expr2) => Expression::new_concatenation(
+                *first,
+                Self::concatenate_repetitions(*last, expr2, config),
+                config,
+            ),
+            (expr1, Expression::Concatenation(first, last, _)) => Expression::new_concatenation(
+                Self::concatenate_repetitions(expr1, *first, config),
+                *last,
+                config,
+            ),
+            (
+                Expression::Repetition(unit, quantifier1, _),
+                Expression::Repetition(_, quantifier2, _),
+            ) => Expression::new_repetition(*unit, quantifier1.add(&quantifier2), config),
+            _ => unreachable!(),
+        }
+    }
+
+    fn first_factor(&self) -> &Expression {
+        match self {
+            Expression::Concatenation(expr1, _, _) => expr1.first_factor(),
+            _ => self,
+        }
+    }
+
+    fn last_factor(&self) -> &Expression {
+        match self {
+            Expression::Concatenation(_, expr2, _) => expr2.last_factor(),
+            _ => self,
+        }
+    }
+
+    fn single_char(&self) -> Option<char> {
+        self.single_char_with_bounds(1, 1)
+    }
+
+    fn optional_single_char(&self) -> Option<char> {
+        self.single_char_with_bounds(0, 1)
+    }
+
+    fn single_char_with_bounds(&self, minimum: u32, maximum: u32) -> Option<char> {
+        if let Expression::Literal(cluster, config) = self {
+            if let [grapheme] = cluster.graphemes().as_slice() {
+                let mut chars = grapheme.value().chars().collect_vec();
+                let is_surrogate_pair = config.is_non_ascii_char_escaped
+                    && config.is_astral_code_point_converted_to_surrogate
+                    && chars.iter().any(|&c| c > '\u{ffff}');
+                if chars.len() == 1
+                    && !is_surrogate_pair
+                    && !grapheme.has_repetitions()
+                    && grapheme.minimum() == minimum
+                    && grapheme.maximum() == maximum
+                {
+                    return Some(chars.remove(0));
+                }
+            }
+        }
+        None
+    }
+
+    pub(crate) fn factor_common_affixes(self) -> Self {
+        match self {
+            Expression::Alternation(options, config) => {
+                let mut unique_options: Vec<Expression> = vec![];
+                for option in options.into_iter().map(|it| it.factor_common_affixes()) {
+                    if !unique_options.contains(&option) {
+                        unique_options.push(option);
+                    }
+                }
+                if unique_options.len() == 1 {
+                    return unique_options.remove(0);
+                }
+                Self::factor_alternation(unique_options, &config)
+            }
+            Expression::Concatenation(expr1, expr2, config) => Expression::new_concatenation(
+                expr1.factor_common_affixes(),
+                expr2.factor_common_affixes(),
+                &config,
+            ),
+            Expression::Repetition(expr, quantifier, config) => {
+                Expression::new_repetition(expr.factor_common_affixes(), quantifier, &config)
+            }
+            _ => self,
+        }
+    }
+
+    pub(crate) fn make_inserted_graphemes_optional(self) -> Self {
+        match self {
+            Expression::Alternation(options, config) => {
+                let mut flattened_options = vec![];
+                Self::flatten_alternations(
+                    &mut flattened_options,
+                    options
+                        .into_iter()
+                        .map(|it| it.make_inserted_graphemes_optional())
+                        .collect_vec(),
+                );
+                Self::merge_single_insertions(flattened_options, &config)
+            }
+            Expression::Concatenation(expr1, expr2, config) => Self::concatenate(
+                &Some(expr1.make_inserted_graphemes_optional()),
+                &Some(expr2.make_inserted_graphemes_optional()),
+                &config,
+            )
+            .unwrap(),
+            Expression::Repetition(expr, quantifier, config) => Expression::new_repetition(
+                expr.make_inserted_graphemes_optional(),
+                quantifier,
+                &config,
+            ),
+            _ => self,
+        }
+    }
+
+    fn merge_single_insertions(mut options: Vec<Expression>, config: &Arc<RegExpConfig>) -> Self {
+        let mut i = 0;
+
+        while i < options.len() {
+            let merged_option = (i + 1..options.len()).find_map(|j| {
+                Self::merge_single_insertion(&options[i], &options[j], config).map(|it| (j, it))
+            });
+            match merged_option {
+                Some((j, option)) => {
+                    options.remove(j);
+                    options[i] = option;
+                }
+                None => i += 1,
+            }
+        }
+
+        options
+            .into_iter()
+            .fold1(|a, b| Expression::new_alternation(a, b, config))
+            .unwrap()
+    }
+
+    fn merge_single_insertion(
+        expr1: &Expression,
+        expr2: &Expression,
+        config: &Arc<RegExpConfig>,
+    ) -> Option<Expression> {
+        let (shorter, longer) = match (expr1, expr2) {
+            (Expression::Literal(cluster1, _), Expression::Literal(cluster2, _)) => {
+                if cluster1.size() + 1 == cluster2.size() {
+                    (cluster1.graphemes(), cluster2.graphemes())
+                } else if cluster2.size() + 1 == cluster1.size() {
+                    (cluster2.graphemes(), cluster1.graphemes())
+                } else {
+                    return None;
+                }
+            }
+            _ => return None,
+        };
+
+        let position = shorter
+            .iter()
+            .zip(longer.iter())
+            .take_while(|(a, b)| a == b)
+            .count();
+        let inserted_grapheme = &longer[position];
+
+        // The two literals must only differ in a single inserted grapheme
+        // which occurs exactly once, so that the optional grapheme
+        // matches the shorter literal if it is absent and the longer one if it is present.
+        if inserted_grapheme.minimum() != 1
+            || inserted_grapheme.maximum() != 1
+            || shorter[position..] != longer[position + 1..]
+        {
+            return None;
+        }
+
+        let mut graphemes = longer.clone();
+        graphemes[position].make_optional();
+
+        Some(Expression::new_literal(
+            GraphemeCluster::from_graphemes(graphemes, config),
+            config,
+        ))
+    }
+
+    fn factor_alternation(options: Vec<Expression>, config: &Arc<RegExpConfig>) -> Self {
+        let mut prefix = None;
+        let mut suffix = None;
+        let mut residual = Expression::Alternation(options, config.clone());
+
+        for (substring, affix, is_enabled) in [
+            (
+                Substring::Prefix,
+                &mut prefix,
+                config.is_prefix_factoring_enabled,
+            ),
+            (
+                Substring::Suffix,
+                &mut suffix,
+                config.is_suffix_factoring_enabled,
+            ),
+        ] {
+            if !is_enabled {
+                continue;
+            }
+            if let Expression::Alternation(options, _) = &residual {
+                if let Some((common_affix, rest)) =
+                    Self::extract_common_affix(options, &substring, config)
+                {
+                    *affix = Some(common_affix);
+                    residual = rest;
+                }
+            }
+        }
+
+        let expr = match prefix {
+            Some(prefix) => Self::concatenate(&Some(prefix), &Some(residual), config).unwrap(),
+            None => residual,
+        };
+        match suffix {
+            Some(suffix) => Self::concatenate(&Some(expr), &Some(suffix), config).unwrap(),
+            None => expr,
+        }
+    }
+
+    fn extract_common_affix(
+        options: &[Expression],
+        substring: &Substring,
+        config: &Arc<RegExpConfig>,
+    ) -> Option<(Expression, Expression)> {
+        let first_graphemes = options.first().unwrap().affix_graphemes(substring);
+        let affix_length = options
+            .iter()
+            .skip(1)
+            .map(|option| {
+                let graphemes = option.affix_graphemes(substring);
+                match substring {
+                    Substring::Prefix => first_graphemes
+                        .iter()
+                        .zip(graphemes)
+                        .take_while(|(first, second)| first == second)
+                        .count(),
+                    Substring::Suffix => first_graphemes
+                        .iter()
+                        .rev()
+                        .zip(graphemes.iter().rev())
+                        .take_while(|(first, second)| first == second)
+                        .count(),
+                }
+            })
+            .min()
+            .unwrap_or(0);
+
+        if affix_length == 0 {
+            return None;
+        }
+
+        let affix_graphemes = match substring {
+            Substring::Prefix => &first_graphemes[..affix_length],
+            Substring::Suffix => &first_graphemes[first_graphemes.len() - affix_length..],
+        };
+        let affix = Expression::new_literal(
+            GraphemeCluster::from_graphemes(affix_graphemes.to_vec(), config),
+            config,
+        );
+        let residual = options
+            .iter()
+            .map(|it| Some(it.clone().remove_affix_graphemes(affix_length, substring)))
+            .reduce(|a, b| Self::union(&a, &b, config))
+            .flatten()?;
+
+        Some((affix, residual))
+    }
+
+    fn affix_graphemes(&self, substring: &Substring) -> &[Grapheme] {
+        match (self, substring) {
+            (Expression::Literal(cluster, _), _) => cluster.graphemes(),
+            (Expression::Concatenation(expr1, _, _), Substring::Prefix) => {
+                expr1.affix_graphemes(substring)
+            }
+            (Expression::Concatenation(_, expr2, _), Substring::Suffix) => {
+                expr2.affix_graphemes(substring)
+            }
+            _ => &[],
+        }
+    }
+
+    fn remove_affix_graphemes(self, count: usize, substring: &Substring) -> Self {
+        match self {
+            Expression::Literal(cluster, config) => {
+                let graphemes = cluster.graphemes();
+                let remaining_graphemes = match substring {
+                    Substring::Prefix => &graphemes[count..],
+                    Substring::Suffix => &graphemes[..graphemes.len() - count],
+                };
+                Expression::new_literal(
+                    GraphemeCluster::from_graphemes(remaining_graphemes.to_vec(), &config),
+                    &config,
+                )
+            }
+            Expression::Concatenation(expr1, expr2, config) => match substring {
+                Substring::Prefix => {
+                    let expr1 = expr1.remove_affix_graphemes(count, substring);
+                    if expr1.is_empty() {
+                        *expr2
+                    } else {
+                        Expression::new_concatenation(expr1, *expr2, &config)
+                    }
+                }
+                Substring::Suffix => {
+                    let expr2 = expr2.remove_affix_graphemes(count, substring);
+                    if expr2.is_empty() {
+                        *expr1
+                    } else {
+                        Expression::new_concatenation(*expr1, expr2, &config)
+                    }
+                }
+            },
+            _ => self,
+        }
+    }
+
+    pub(crate) fn prepend_optional_sign(expr: Expression, config: &Arc<RegExpConfig>) -> Self {
+        let signs = ['+', '-'].iter().cloned().collect();
+        Self::concatenate(
+            &Some(Self::new_optional_char_set(signs, config)),
+            &Some(expr),
+            config,
+        )
+        .unwrap()
+    }
+
+    pub(crate) fn surround_with_optional_brackets(
+        expr: Expression,
+        config: &Arc<RegExpConfig>,
+    ) -> Self {
+        let (opening_brackets, closing_brackets): (BTreeSet<char>, BTreeSet<char>) =
+            config.optional_bracket_pairs.iter().cloned().unzip();
+
+        if config.is_bracket_balance_ensured {
+            let bracketed_exprs = config
+                .optional_bracket_pairs
+                .iter()
+                .map(|&(opening_bracket, closing_bracket)| {
+                    let expr = Self::concatenate(
+                        &Some(Self::new_bracket_literal(opening_bracket, config)),
+                        &Some(expr.clone()),
+                        config,
+                    );
+                    Self::concatenate(
+                        &expr,
+                        &Some(Self::new_bracket_literal(closing_bracket, config)),
+                        config,
+                    )
+                    .unwrap()
+                })
+                .collect_vec();
+
+            bracketed_exprs
+                .into_iter()
+                .fold(expr, |alternation, bracketed_expr| {
+                    Expression::new_alternation(bracketed_expr, alternation, config)
+                })
+        } else {
+            let expr = Self::concatenate(
+                &Some(Self::new_optional_char_set(opening_brackets, config)),
+                &Some(expr),
+                config,
+            );
+            Self::concatenate(
+                &expr,
+                &Some(Self::new_optional_char_set(closing_brackets, config)),
+                config,
+            )
+            .unwrap()
+        }
+    }
+
+    /// Creates a literal from the given string.
+    /// Characters with special meaning in regular expressions are escaped when rendered.
+    pub fn literal(value: &str, config: &Arc<RegExpConfig>) -> Self {
+        Expression::new_literal(GraphemeCluster::from(value, config), config)
+    }
+
+    /// Creates a character class from the given characters, such as `[a-cx]`.
+    ///
+    /// ⚠ Panics if `chars` is empty.
+    pub fn character_class<I: IntoIterator<Item = char>>(
+        chars: I,
+        config: &Arc<RegExpConfig>,
+    ) -> Self {
+        let char_set: BTreeSet<char> = chars.into_iter().collect();
+        if char_set.is_empty() {
+            panic!("No characters have been provided for the character class");
+        }
+        Expression::CharacterClass(char_set, config.clone())
+    }
+
+    /// Creates a character class from all characters of the given range, which is useful
+    /// for known alphabets such as the CJK Unified Ideographs `U+4E00` to `U+9FFF`.
+    /// The range is kept as it is, so even large ranges are cheap to create and to render.
+    /// Surrogate code points are not characters, so they are never included.
+    ///
+    /// ⚠ Panics if `range` is empty.
+    pub fn character_class_from_range(
+        range: RangeInclusive<char>,
+        config: &Arc<RegExpConfig>,
+    ) -> Self {
+        if range.is_empty() {
+            panic!("No characters have been provided for the character class");
+        }
+        Expression::CharacterRange(range, config.clone())
+    }
+
+    /// Creates an alternation of the given options, such as `a|bc`.
+    /// The options are rendered in the given order unless
+    /// [`RegExpBuilder::with_longest_branches_first`](../struct.RegExpBuilder.html#method.with_longest_branches_first)
+    /// has been called for the configuration. In that case, they are reordered once here
+    /// so that no option matches a prefix of a string matched by an earlier one.
+    ///
+    /// ⚠ Panics if `options` is empty.
+    pub fn alternation(options: Vec<Expression>, config: &Arc<RegExpConfig>) -> Self {
+        if options.is_empty() {
+            panic!("No options have been provided for the alternation");
+        }
+        if config.is_longest_first {
+            Expression::Alternation(order_options_prefixes_last(options), config.clone())
+        } else {
+            Expression::Alternation(options, config.clone())
+        }
+    }
+
+    /// Creates a concatenation of two expressions, such as `ab(?:c|d)`.
+    pub fn concatenation(
+        first: Expression,
+        second: Expression,
+        config: &Arc<RegExpConfig>,
+    ) -> Self {
+        Expression::new_concatenation(first, second, config)
+    }
+
+    /// Creates a repetition of the given expression, such as `(?:ab){2,3}`.
+    pub fn repetition(
+        expr: Expression,
+        quantifier: Quantifier,
+        config: &Arc<RegExpConfig>,
+    ) -> Self {
+        Expression::new_repetition(expr, quantifier, config)
+    }
+
+    pub(crate) fn new_length_quantified_char_class(
+        char_set: BTreeSet<char>,
+        min_length: u32,
+        max_length: u32,
+        config: &Arc<RegExpConfig>,
+    ) -> Self {
+        Expression::new_repetition(
+            Expression::CharacterClass(char_set, config.clone()),
+            Quantifier::Range(min_length, max_length),
+            config,
+        )
+    }
+
+    /// Creates an expression which matches one or more occurrences of `field`
+    /// separated by `delimiter`, such as `(?:[a-z],)*[a-z]`.
+    pub(crate) fn new_delimited(
+        field: Expression,
+        delimiter: char,
+        config: &Arc<RegExpConfig>,
+    ) -> Self {
+        let delimiter = Expression::new_literal(
+            GraphemeCluster::from(&delimiter.to_string(), config),
+            config,
+        );
+        Expression::new_concatenation(
+            Expression::new_repetition(
+                Expression::new_concatenation(field.clone(), delimiter, config),
+                Quantifier::KleeneStar,
+                config,
+            ),
+            field,
+            config,
+        )
+    }
+
+    /// Returns the single member of a character class as a literal
+    /// or `None` if the character class has more than one member.
+    pub(crate) fn new_single_char_literal(
+        char_set: &BTreeSet<char>,
+        config: &Arc<RegExpConfig>,
+    ) -> Option<Self> {
+        if char_set.len() != 1 {
+            return None;
+        }
+        let c = char_set.iter().next().unwrap().to_string();
+        Some(Expression::new_literal(
+            GraphemeCluster::from(&c, config),
+            config,
+        ))
+    }
+
+    fn new_bracket_literal(bracket: char, config: &Arc<RegExpConfig>) -> Self {
+        Expression::new_literal(GraphemeCluster::from(&bracket.to_string(), config), config)
+    }
+
+    fn new_optional_char_set(chars: BTreeSet<char>, config: &Arc<RegExpConfig>) -> Self {
+        let expr = if chars.len() == 1 {
+            Self::new_bracket_literal(*chars.iter().next().unwrap(), config)
+        } else {
+            Expression::CharacterClass(chars, config.clone())
+        };
+        Expression::new_repetition(expr, Quantifier::QuestionMark, config)
+    }
+
+    fn new_alternation(expr1: Expression, expr2: Expression, config: &Arc<RegExpConfig>) -> Self {
         let mut options: Vec<Expression> = vec![];
         Self::flatten_alternations(&mut options, vec![expr1, expr2]);
-        options.sort_by(|a, b| b.len().cmp(&a.len()));
+        match config.alternation_order {
+            AlternationOrder::ByLength => options.sort_by_key(|option| Reverse(option.len())),
+            AlternationOrder::Input => {}
+            AlternationOrder::Lexicographic => {
+                options.sort_by_cached_key(|option| without_colorization(|| option.to_string()))
+            }
+        }
         Expression::Alternation(options, config.clone())
     }
 
     fn new_character_class(
         first_char_set: BTreeSet<char>,
         second_char_set: BTreeSet<char>,
-        config: &RegExpConfig,
+        config: &Arc<RegExpConfig>,
     ) -> Self {
         let union_set = first_char_set.union(&second_char_set).copied().collect();
         Expression::CharacterClass(union_set, config.clone())
     }
 
-    fn new_concatenation(expr1: Expression, expr2: Expression, config: &RegExpConfig) -> Self {
+    /// Concatenates the given expressions. An empty literal is left out, so that
+    /// it does not cause an alternation to be grouped more than once.
+    fn new_concatenation(expr1: Expression, expr2: Expression, config: &Arc<RegExpConfig>) -> Self {
+        if expr1.is_empty() {
+            return expr2;
+        }
+        if expr2.is_empty() {
+            return expr1;
+        }
         Expression::Concatenation(Box::from(expr1), Box::from(expr2), config.clone())
     }
 
-    fn new_literal(cluster: GraphemeCluster, config: &RegExpConfig) -> Self {
+    fn new_literal(cluster: GraphemeCluster, config: &Arc<RegExpConfig>) -> Self {
         Expression::Literal(cluster, config.clone())
     }
 
-    fn new_repetition(expr: Expression, quantifier: Quantifier, config: &RegExpConfig) -> Self {
+    fn new_repetition(
+        expr: Expression,
+        quantifier: Quantifier,
+        config: &Arc<RegExpConfig>,
+    ) -> Self {
         Expression::Repetition(Box::from(expr), quantifier, config.clone())
     }
 
@@ -139,19 +768,133 @@ impl Expression {
 
     pub(crate) fn is_single_codepoint(&self) -> bool {
         match self {
-            Expression::CharacterClass(_, _) => true,
+            Expression::CharacterClass(char_set, config) => {
+                match Self::new_single_char_literal(char_set, config) {
+                    Some(literal) => literal.is_single_codepoint(),
+                    None => true,
+                }
+            }
+            Expression::CharacterRange(range, config) => {
+                range.start() != range.end()
+                    || Self::new_single_char_literal(&btreeset![*range.start()], config)
+                        .unwrap()
+                        .is_single_codepoint()
+            }
             Expression::Literal(cluster, config) => {
+                let grapheme = cluster.graphemes().first().unwrap();
                 cluster.char_count(config.is_non_ascii_char_escaped) == 1
-                    && cluster.graphemes().first().unwrap().maximum() == 1
+                    && grapheme.minimum() == 1
+                    && grapheme.maximum() == 1
             }
             _ => false,
         }
     }
 
+    /// Returns `true` if any character class of this expression
+    /// is rendered as a wildcard because of the wildcard threshold.
+    pub(crate) fn contains_wildcard(&self) -> bool {
+        match self {
+            Expression::Alternation(options, _) => options.iter().any(|it| it.contains_wildcard()),
+            Expression::CharacterClass(char_set, config) => {
+                char_set.len() > config.wildcard_threshold
+            }
+            Expression::CharacterRange(range, config) => {
+                char_range_len(range) > config.wildcard_threshold
+            }
+            Expression::Concatenation(expr1, expr2, _) => {
+                expr1.contains_wildcard() || expr2.contains_wildcard()
+            }
+            Expression::Literal(_, _) => false,
+            Expression::Repetition(expr, _, _) => expr.contains_wildcard(),
+        }
+    }
+
+    pub(crate) fn match_count(&self) -> Option<u128> {
+        match self {
+            Expression::Alternation(options, _) => options
+                .iter()
+                .try_fold(0u128, |count, it| count.checked_add(it.match_count()?)),
+            Expression::CharacterClass(char_set, config) => {
+                if char_set.len() <= config.wildcard_threshold {
+                    Some(char_set.len() as u128)
+                } else if char_set.contains(&'\n') {
+                    Some(UNICODE_SCALAR_VALUE_COUNT)
+                } else {
+                    Some(UNICODE_SCALAR_VALUE_COUNT - 1)
+                }
+            }
+            Expression::CharacterRange(range, config) => {
+                let char_count = char_range_len(range);
+                if char_count <= config.wildcard_threshold {
+                    Some(char_count as u128)
+                } else if range.contains(&'\n') {
+                    Some(UNICODE_SCALAR_VALUE_COUNT)
+                } else {
+                    Some(UNICODE_SCALAR_VALUE_COUNT - 1)
+                }
+            }
+            Expression::Concatenation(expr1, expr2, _) => {
+                expr1.match_count()?.checked_mul(expr2.match_count()?)
+            }
+            Expression::Literal(cluster, _) => cluster
+                .graphemes()
+                .iter()
+                .try_fold(1u128, |count, it| count.checked_mul(it.match_count()?)),
+            Expression::Repetition(expr, quantifier, _) => match quantifier {
+                Quantifier::KleeneStar | Quantifier::Range(_, UNBOUNDED_REPETITIONS) => None,
+                Quantifier::QuestionMark => expr.match_count()?.checked_add(1),
+                Quantifier::Range(min, max) => {
+                    let unit_count = expr.match_count()?;
+                    (*min..=*max).try_fold(0u128, |count, exponent| {
+                        count.checked_add(unit_count.checked_pow(exponent)?)
+                    })
+                }
+            },
+        }
+    }
+
+    /// Returns the members of all character classes in this expression in order of appearance,
+    /// each together with the path of its class from the root of the syntax tree.
+    pub(crate) fn character_classes(&self) -> Vec<(Vec<usize>, &BTreeSet<char>)> {
+        let mut char_classes = vec![];
+        self.collect_character_classes(&mut vec![], &mut char_classes);
+        char_classes
+    }
+
+    fn collect_character_classes<'a>(
+        &'a self,
+        path: &mut Vec<usize>,
+        char_classes: &mut Vec<(Vec<usize>, &'a BTreeSet<char>)>,
+    ) {
+        match self {
+            Expression::Alternation(options, _) => {
+                for (i, option) in options.iter().enumerate() {
+                    path.push(i);
+                    option.collect_character_classes(path, char_classes);
+                    path.pop();
+                }
+            }
+            Expression::CharacterClass(char_set, _) => char_classes.push((path.clone(), char_set)),
+            Expression::Concatenation(expr1, expr2, _) => {
+                for (i, expr) in [expr1, expr2].iter().enumerate() {
+                    path.push(i);
+                    expr.collect_character_classes(path, char_classes);
+                    path.pop();
+                }
+            }
+            Expression::CharacterRange(_, _) | Expression::Literal(_, _) => {}
+            Expression::Repetition(expr, _, _) => {
+                path.push(0);
+                expr.collect_character_classes(path, char_classes);
+                path.pop();
+            }
+        }
+    }
+
     fn len(&self) -> usize {
         match self {
             Expression::Alternation(options, _) => options.first().unwrap().len(),
-            Expression::CharacterClass(_, _) => 1,
+            Expression::CharacterClass(_, _) | Expression::CharacterRange(_, _) => 1,
             Expression::Concatenation(expr1, expr2, _) => expr1.len() + expr2.len(),
             Expression::Literal(cluster, _) => cluster.size(),
             Expression::Repetition(expr, _, _) => expr.len(),
@@ -160,7 +903,9 @@ impl Expression {
 
     pub(crate) fn precedence(&self) -> u8 {
         match self {
-            Expression::Alternation(_, _) | Expression::CharacterClass(_, _) => 1,
+            Expression::Alternation(_, _)
+            | Expression::CharacterClass(_, _)
+            | Expression::CharacterRange(_, _) => 1,
             Expression::Concatenation(_, _, _) | Expression::Literal(_, _) => 2,
             Expression::Repetition(_, _, _) => 3,
         }
@@ -209,23 +954,16 @@ impl Expression {
 
     fn repeat_zero_or_more_times(
         expr: &Option<Expression>,
-        config: &RegExpConfig,
+        config: &Arc<RegExpConfig>,
     ) -> Option<Expression> {
-        if let Some(value) = expr {
-            Some(Expression::new_repetition(
-                value.clone(),
-                Quantifier::KleeneStar,
-                config,
-            ))
-        } else {
-            None
-        }
+        expr.as_ref()
+            .map(|value| Expression::new_repetition(value.clone(), Quantifier::KleeneStar, config))
     }
 
     fn concatenate(
         a: &Option<Expression>,
         b: &Option<Expression>,
-        config: &RegExpConfig,
+        config: &Arc<RegExpConfig>,
     ) -> Option<Expression> {
         if a.is_none() || b.is_none() {
             return None;
@@ -292,7 +1030,7 @@ impl Expression {
     fn union(
         a: &Option<Expression>,
         b: &Option<Expression>,
-        config: &RegExpConfig,
+        config: &Arc<RegExpConfig>,
     ) -> Option<Expression> {
         if let (Some(mut expr1), Some(mut expr2)) = (a.clone(), b.clone()) {
             if expr1 != expr2 {
@@ -447,8 +1185,8 @@ impl Expression {
         b: &Expression,
         substring: &Substring,
     ) -> Option<Vec<Grapheme>> {
-        let mut graphemes_a = a.value(Some(substring)).unwrap_or_else(|| vec![]);
-        let mut graphemes_b = b.value(Some(substring)).unwrap_or_else(|| vec![]);
+        let mut graphemes_a = a.value(Some(substring)).unwrap_or_default();
+        let mut graphemes_b = b.value(Some(substring)).unwrap_or_default();
         let mut common_graphemes = vec![];
 
         if let Substring::Suffix = substring {
@@ -487,7 +1225,7 @@ mod tests {
 
     #[test]
     fn ensure_correct_string_representation_of_alternation_1() {
-        let config = RegExpConfig::new();
+        let config = Arc::new(RegExpConfig::new());
         let literal1 = Expression::new_literal(GraphemeCluster::from("abc", &config), &config);
         let literal2 = Expression::new_literal(GraphemeCluster::from("def", &config), &config);
         let alternation = Expression::new_alternation(literal1, literal2, &config);
@@ -496,7 +1234,7 @@ mod tests {
 
     #[test]
     fn ensure_correct_string_representation_of_alternation_2() {
-        let config = RegExpConfig::new();
+        let config = Arc::new(RegExpConfig::new());
         let literal1 = Expression::new_literal(GraphemeCluster::from("a", &config), &config);
         let literal2 = Expression::new_literal(GraphemeCluster::from("ab", &config), &config);
         let literal3 = Expression::new_literal(GraphemeCluster::from("abc", &config), &config);
@@ -505,16 +1243,227 @@ mod tests {
         assert_eq!(alternation2.to_string(), "abc|ab|a");
     }
 
+    #[test]
+    fn ensure_correct_factoring_of_common_prefixes() {
+        let mut config = RegExpConfig::new();
+        config.is_prefix_factoring_enabled = true;
+        let config = Arc::new(config);
+        let alternation = new_alternation_of_literals(&["foobar", "foobaz", "fooqux"], &config);
+        assert_eq!(alternation.to_string(), "foobar|foobaz|fooqux");
+        assert_eq!(
+            alternation.factor_common_affixes().to_string(),
+            "foo(?:ba[rz]|qux)"
+        );
+    }
+
+    #[test]
+    fn ensure_no_factoring_without_common_prefix() {
+        let mut config = RegExpConfig::new();
+        config.is_prefix_factoring_enabled = true;
+        let config = Arc::new(config);
+        let alternation = new_alternation_of_literals(&["abc", "def"], &config);
+        assert_eq!(alternation.factor_common_affixes().to_string(), "abc|def");
+    }
+
+    #[test]
+    fn ensure_correct_factoring_of_common_prefix_with_empty_remainder() {
+        let mut config = RegExpConfig::new();
+        config.is_prefix_factoring_enabled = true;
+        let config = Arc::new(config);
+        let alternation = new_alternation_of_literals(&["ab", "abcd"], &config);
+        assert_eq!(alternation.factor_common_affixes().to_string(), "ab(?:cd)?");
+    }
+
+    #[test]
+    fn ensure_correct_factoring_of_common_suffixes() {
+        let mut config = RegExpConfig::new();
+        config.is_suffix_factoring_enabled = true;
+        let config = Arc::new(config);
+        let alternation = new_alternation_of_literals(&["running", "jumping"], &config);
+        assert_eq!(
+            alternation.factor_common_affixes().to_string(),
+            "(?:runn|jump)ing"
+        );
+    }
+
+    #[test]
+    fn ensure_correct_factoring_of_common_suffix_with_empty_remainder() {
+        let mut config = RegExpConfig::new();
+        config.is_suffix_factoring_enabled = true;
+        let config = Arc::new(config);
+        let alternation = new_alternation_of_literals(&["ing", "sing", "bring"], &config);
+        assert_eq!(
+            alternation.factor_common_affixes().to_string(),
+            "(?:br|s)?ing"
+        );
+    }
+
+    #[test]
+    fn ensure_correct_factoring_of_common_prefixes_and_suffixes() {
+        let mut config = RegExpConfig::new();
+        config.is_prefix_factoring_enabled = true;
+        config.is_suffix_factoring_enabled = true;
+        let config = Arc::new(config);
+        let alternation = new_alternation_of_literals(&["abxyz", "abz", "abxxz"], &config);
+        assert_eq!(
+            alternation.factor_common_affixes().to_string(),
+            "ab(?:x[xy])?z"
+        );
+
+        let alternation = new_alternation_of_literals(&["ab", "abab"], &config);
+        assert_eq!(alternation.factor_common_affixes().to_string(), "ab(?:ab)?");
+    }
+
+    #[test]
+    fn ensure_removal_of_duplicate_options_after_factoring() {
+        let mut config = RegExpConfig::new();
+        config.is_prefix_factoring_enabled = true;
+        let config = Arc::new(config);
+        let first = new_alternation_of_literals(&["xa", "xb"], &config);
+        let second = new_alternation_of_literals(&["ya", "yb"], &config);
+        let literal = Expression::new_literal(GraphemeCluster::from("q", &config), &config);
+
+        let alternation = Expression::Alternation(
+            vec![
+                first.clone(),
+                literal,
+                first.clone().factor_common_affixes(),
+            ],
+            config.clone(),
+        );
+        assert_eq!(alternation.factor_common_affixes().to_string(), "x[ab]|q");
+
+        let alternation =
+            Expression::Alternation(vec![first.clone(), second, first.clone()], config.clone());
+        assert_eq!(
+            alternation.factor_common_affixes().to_string(),
+            "x[ab]|y[ab]"
+        );
+
+        let alternation = Expression::Alternation(
+            vec![first.clone(), first.factor_common_affixes()],
+            config.clone(),
+        );
+        assert_eq!(alternation.factor_common_affixes().to_string(), "x[ab]");
+    }
+
+    #[test]
+    fn ensure_conversion_of_single_char_alternations_to_char_classes() {
+        let config = Arc::new(RegExpConfig::new());
+        let alternation = new_alternation_of_literals(&["a", "b", "c"], &config);
+        assert_eq!(alternation.to_string(), "a|b|c");
+        assert_eq!(
+            alternation.convert_single_char_alternations().to_string(),
+            "[a-c]"
+        );
+
+        let alternation = new_alternation_of_literals(&["a", "b", "cd"], &config);
+        assert_eq!(
+            alternation.convert_single_char_alternations().to_string(),
+            "cd|a|b"
+        );
+
+        let mut config = RegExpConfig::new();
+        config.alternation_to_class_threshold = 4;
+        let config = Arc::new(config);
+        let alternation = new_alternation_of_literals(&["a", "b", "c"], &config);
+        assert_eq!(
+            alternation.convert_single_char_alternations().to_string(),
+            "a|b|c"
+        );
+    }
+
+    fn new_alternation_of_literals(values: &[&str], config: &Arc<RegExpConfig>) -> Expression {
+        values
+            .iter()
+            .map(|it| Expression::new_literal(GraphemeCluster::from(it, config), config))
+            .fold1(|a, b| Expression::new_alternation(a, b, config))
+            .unwrap()
+    }
+
+    #[test]
+    fn ensure_correct_merging_of_single_insertions() {
+        let config = Arc::new(RegExpConfig::new());
+        let alternation = new_alternation_of_literals(&["abc", "abxc", "xyz"], &config);
+        assert_eq!(
+            alternation.make_inserted_graphemes_optional().to_string(),
+            "abx?c|xyz"
+        );
+    }
+
+    #[test]
+    fn ensure_no_merging_of_multiple_insertions() {
+        let config = Arc::new(RegExpConfig::new());
+        let alternation = new_alternation_of_literals(&["abc", "axbxc"], &config);
+        assert_eq!(
+            alternation.make_inserted_graphemes_optional().to_string(),
+            "axbxc|abc"
+        );
+    }
+
+    #[test]
+    fn ensure_correct_alternation_order() {
+        for (order, expected_output, reversed_expected_output) in [
+            (AlternationOrder::ByLength, "abc|ab", "abc|ab"),
+            (AlternationOrder::Input, "ab|abc", "abc|ab"),
+            (AlternationOrder::Lexicographic, "ab|abc", "ab|abc"),
+        ] {
+            let mut config = RegExpConfig::new();
+            config.alternation_order = order;
+            let config = Arc::new(config);
+            let alternation = new_alternation_of_literals(&["ab", "abc"], &config);
+            assert_eq!(alternation.to_string(), expected_output);
+            let alternation = new_alternation_of_literals(&["abc", "ab"], &config);
+            assert_eq!(alternation.to_string(), reversed_expected_output);
+        }
+    }
+
     #[test]
     fn ensure_correct_string_representation_of_character_class_1() {
-        let config = RegExpConfig::new();
+        let config = Arc::new(RegExpConfig::new());
         let char_class = Expression::new_character_class(btreeset!['a'], btreeset!['b'], &config);
         assert_eq!(char_class.to_string(), "[ab]");
     }
 
+    #[test]
+    fn ensure_correct_string_representation_of_single_char_class() {
+        let config = Arc::new(RegExpConfig::new());
+        for (c, expected_output) in [('a', "a"), ('.', "\\."), ('^', "\\^"), ('-', "\\-")] {
+            let char_class = Expression::CharacterClass(btreeset![c], config.clone());
+            assert_eq!(char_class.to_string(), expected_output);
+        }
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "Character class must not be empty")]
+    fn ensure_empty_character_class_is_rejected() {
+        let config = Arc::new(RegExpConfig::new());
+        Expression::CharacterClass(BTreeSet::new(), config).to_string();
+    }
+
+    #[test]
+    #[cfg(not(debug_assertions))]
+    fn ensure_empty_character_class_never_matches() {
+        let config = Arc::new(RegExpConfig::new());
+        let char_class = Expression::CharacterClass(BTreeSet::new(), config);
+        assert_eq!(char_class.to_string(), "[]");
+    }
+
+    #[test]
+    fn ensure_correct_string_representation_of_repeated_single_char_class() {
+        let mut config = RegExpConfig::new();
+        config.is_non_ascii_char_escaped = true;
+        config.is_astral_code_point_converted_to_surrogate = true;
+        let config = Arc::new(config);
+        let char_class = Expression::CharacterClass(btreeset!['💩'], config.clone());
+        let repetition = Expression::new_repetition(char_class, Quantifier::QuestionMark, &config);
+        assert_eq!(repetition.to_string(), "(?:\\u{d83d}\\u{dca9})?");
+    }
+
     #[test]
     fn ensure_correct_string_representation_of_character_class_2() {
-        let config = RegExpConfig::new();
+        let config = Arc::new(RegExpConfig::new());
         let char_class =
             Expression::new_character_class(btreeset!['a', 'b'], btreeset!['c'], &config);
         assert_eq!(char_class.to_string(), "[a-c]");
@@ -522,7 +1471,7 @@ mod tests {
 
     #[test]
     fn ensure_correct_string_representation_of_concatenation_1() {
-        let config = RegExpConfig::new();
+        let config = Arc::new(RegExpConfig::new());
         let literal1 = Expression::new_literal(GraphemeCluster::from("abc", &config), &config);
         let literal2 = Expression::new_literal(GraphemeCluster::from("def", &config), &config);
         let concatenation = Expression::new_concatenation(literal1, literal2, &config);
@@ -531,7 +1480,7 @@ mod tests {
 
     #[test]
     fn ensure_correct_string_representation_of_concatenation_2() {
-        let config = RegExpConfig::new();
+        let config = Arc::new(RegExpConfig::new());
         let literal1 = Expression::new_literal(GraphemeCluster::from("abc", &config), &config);
         let literal2 = Expression::new_literal(GraphemeCluster::from("def", &config), &config);
         let repetition = Expression::new_repetition(literal1, Quantifier::KleeneStar, &config);
@@ -541,13 +1490,13 @@ mod tests {
 
     #[test]
     fn ensure_correct_removal_of_prefix_in_literal() {
-        let config = RegExpConfig::new();
+        let config = Arc::new(RegExpConfig::new());
         let mut literal =
             Expression::new_literal(GraphemeCluster::from("abcdef", &config), &config);
         assert_eq!(
             literal.value(None),
             Some(
-                vec!["a", "b", "c", "d", "e", "f"]
+                ["a", "b", "c", "d", "e", "f"]
                     .iter()
                     .map(|&it| Grapheme::from(it, &config))
                     .collect_vec()
@@ -558,7 +1507,7 @@ mod tests {
         assert_eq!(
             literal.value(None),
             Some(
-                vec!["c", "d", "e", "f"]
+                ["c", "d", "e", "f"]
                     .iter()
                     .map(|&it| Grapheme::from(it, &config))
                     .collect_vec()
@@ -568,13 +1517,13 @@ mod tests {
 
     #[test]
     fn ensure_correct_removal_of_suffix_in_literal() {
-        let config = RegExpConfig::new();
+        let config = Arc::new(RegExpConfig::new());
         let mut literal =
             Expression::new_literal(GraphemeCluster::from("abcdef", &config), &config);
         assert_eq!(
             literal.value(None),
             Some(
-                vec!["a", "b", "c", "d", "e", "f"]
+                ["a", "b", "c", "d", "e", "f"]
                     .iter()
                     .map(|&it| Grapheme::from(it, &config))
                     .collect_vec()
@@ -585,7 +1534,7 @@ mod tests {
         assert_eq!(
             literal.value(None),
             Some(
-                vec!["a", "b", "c", "d"]
+                ["a", "b", "c", "d"]
                     .iter()
                     .map(|&it| Grapheme::from(it, &config))
                     .collect_vec()
@@ -593,9 +1542,38 @@ mod tests {
         );
     }
 
+    #[test]
+    fn ensure_correct_match_count_of_concatenation() {
+        let config = Arc::new(RegExpConfig::new());
+        let char_class1 = Expression::new_character_class(btreeset!['a'], btreeset!['b'], &config);
+        let char_class2 = Expression::new_character_class(btreeset!['c'], btreeset!['d'], &config);
+        let concatenation = Expression::new_concatenation(char_class1, char_class2, &config);
+        assert_eq!(concatenation.to_string(), "[ab][cd]");
+        assert_eq!(concatenation.match_count(), Some(4));
+    }
+
+    #[test]
+    fn ensure_missing_match_count_of_unbounded_repetition() {
+        let config = Arc::new(RegExpConfig::new());
+        let literal = Expression::new_literal(GraphemeCluster::from("abc", &config), &config);
+        let repetition = Expression::new_repetition(literal, Quantifier::KleeneStar, &config);
+        assert_eq!(repetition.match_count(), None);
+    }
+
+    #[test]
+    fn ensure_correct_string_representation_of_length_quantified_char_class() {
+        let config = Arc::new(RegExpConfig::new());
+        let char_class =
+            Expression::new_length_quantified_char_class(btreeset!['a', 'b', 'c'], 2, 4, &config);
+        assert_eq!(char_class.to_string(), "[a-c]{2,4}");
+
+        let literal = Expression::new_length_quantified_char_class(btreeset!['a'], 3, 3, &config);
+        assert_eq!(literal.to_string(), "a{3}");
+    }
+
     #[test]
     fn ensure_correct_string_representation_of_repetition_1() {
-        let config = RegExpConfig::new();
+        let config = Arc::new(RegExpConfig::new());
         let literal = Expression::new_literal(GraphemeCluster::from("abc", &config), &config);
         let repetition = Expression::new_repetition(literal, Quantifier::KleeneStar, &config);
         assert_eq!(repetition.to_string(), "(?:abc)*");
@@ -603,9 +1581,115 @@ mod tests {
 
     #[test]
     fn ensure_correct_string_representation_of_repetition_2() {
-        let config = RegExpConfig::new();
+        let config = Arc::new(RegExpConfig::new());
         let literal = Expression::new_literal(GraphemeCluster::from("a", &config), &config);
         let repetition = Expression::new_repetition(literal, Quantifier::QuestionMark, &config);
         assert_eq!(repetition.to_string(), "a?");
     }
+
+    fn new_repetition_of_literal(
+        value: &str,
+        quantifier: Quantifier,
+        config: &Arc<RegExpConfig>,
+    ) -> Expression {
+        Expression::new_repetition(
+            Expression::new_literal(GraphemeCluster::from(value, config), config),
+            quantifier,
+            config,
+        )
+    }
+
+    #[test]
+    fn ensure_correct_merging_of_adjacent_repetitions() {
+        let config = Arc::new(RegExpConfig::new());
+        let concatenation = Expression::new_concatenation(
+            new_repetition_of_literal("a", Quantifier::Range(2, 2), &config),
+            new_repetition_of_literal("a", Quantifier::Range(3, 3), &config),
+            &config,
+        );
+        assert_eq!(concatenation.to_string(), "a{2}a{3}");
+        assert_eq!(
+            concatenation.merge_adjacent_repetitions().to_string(),
+            "a{5}"
+        );
+    }
+
+    #[test]
+    fn ensure_correct_merging_of_adjacent_repetitions_with_different_quantifiers() {
+        let config = Arc::new(RegExpConfig::new());
+        let concatenation = Expression::new_concatenation(
+            new_repetition_of_literal("ab", Quantifier::QuestionMark, &config),
+            Expression::new_concatenation(
+                new_repetition_of_literal("ab", Quantifier::Range(1, 3), &config),
+                Expression::new_concatenation(
+                    new_repetition_of_literal("ab", Quantifier::KleeneStar, &config),
+                    Expression::new_literal(GraphemeCluster::from("c", &config), &config),
+                    &config,
+                ),
+                &config,
+            ),
+            &config,
+        );
+        assert_eq!(concatenation.to_string(), "(?:ab)?(?:ab){1,3}(?:ab)*c");
+        assert_eq!(
+            concatenation.merge_adjacent_repetitions().to_string(),
+            "(?:ab)+c"
+        );
+    }
+
+    #[test]
+    fn ensure_correct_merging_of_adjacent_repetitions_in_nested_concatenations() {
+        let config = Arc::new(RegExpConfig::new());
+        let concatenation = Expression::new_concatenation(
+            Expression::new_concatenation(
+                Expression::new_literal(GraphemeCluster::from("x", &config), &config),
+                new_repetition_of_literal("a", Quantifier::QuestionMark, &config),
+                &config,
+            ),
+            Expression::new_concatenation(
+                new_repetition_of_literal("a", Quantifier::QuestionMark, &config),
+                Expression::new_literal(GraphemeCluster::from("y", &config), &config),
+                &config,
+            ),
+            &config,
+        );
+        assert_eq!(concatenation.to_string(), "xa?a?y");
+        assert_eq!(
+            concatenation.merge_adjacent_repetitions().to_string(),
+            "xa{0,2}y"
+        );
+    }
+
+    #[test]
+    fn ensure_no_merging_of_adjacent_repetitions_of_different_units() {
+        let config = Arc::new(RegExpConfig::new());
+        let concatenation = Expression::new_concatenation(
+            new_repetition_of_literal("a", Quantifier::Range(2, 2), &config),
+            new_repetition_of_literal("b", Quantifier::Range(3, 3), &config),
+            &config,
+        );
+        assert_eq!(
+            concatenation.clone().merge_adjacent_repetitions(),
+            concatenation
+        );
+        assert_eq!(concatenation.to_string(), "a{2}b{3}");
+    }
+
+    #[test]
+    fn ensure_no_merging_of_repetitions_separated_by_other_expressions() {
+        let config = Arc::new(RegExpConfig::new());
+        let concatenation = Expression::new_concatenation(
+            new_repetition_of_literal("a", Quantifier::Range(2, 2), &config),
+            Expression::new_concatenation(
+                Expression::new_literal(GraphemeCluster::from("b", &config), &config),
+                new_repetition_of_literal("a", Quantifier::Range(3, 3), &config),
+                &config,
+            ),
+            &config,
+        );
+        assert_eq!(
+            concatenation.clone().merge_adjacent_repetitions(),
+            concatenation
+        );
+    }
 }
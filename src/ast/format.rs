@@ -15,46 +15,65 @@
  */
 
 use crate::ast::{Expression, Quantifier};
-use crate::char::{ColorizableString, GraphemeCluster};
-use crate::regexp::RegExpConfig;
+use crate::char::{
+    char_range_len, count_capturing_groups, escape_code_point, escape_control_char,
+    escape_utf8_bytes, is_strictly_escaped, without_colorization, ColorizableString, Grapheme,
+    GraphemeCluster, UNICODE_SCALAR_VALUE_COUNT,
+};
+use crate::regexp::{RegExpConfig, RegExpFlavor};
 use itertools::Itertools;
 use std::collections::BTreeSet;
 use std::fmt::{Display, Formatter, Result};
-use unic_char_range::CharRange;
+use std::ops::RangeInclusive;
+use std::sync::Arc;
+
+/// The number of ascii hex digits `0-9`, `A-F` and `a-f`.
+const HEX_DIGIT_COUNT: usize = 22;
 
 impl Display for Expression {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
         match self {
             Expression::Alternation(options, config) => {
-                format_alternation(f, &self, options, config)
+                format_alternation(f, self, options, config)
             }
             Expression::CharacterClass(char_set, config) => {
                 format_character_class(f, char_set, config)
             }
+            Expression::CharacterRange(range, config) => format_character_range(f, range, config),
             Expression::Concatenation(expr1, expr2, config) => {
-                format_concatenation(f, &self, expr1, expr2, config)
+                format_concatenation(f, self, expr1, expr2, config)
             }
             Expression::Literal(cluster, config) => format_literal(f, cluster, config),
             Expression::Repetition(expr, quantifier, config) => {
-                format_repetition(f, &self, expr, quantifier, config)
+                format_repetition(f, self, expr, quantifier, config)
             }
         }
     }
 }
 
+/// Returns the position of the given character among all characters. The surrogate
+/// code points `U+D800` to `U+DFFF` are not characters, so they are skipped, making
+/// `U+D7FF` and `U+E000` adjacent.
 fn get_codepoint_position(c: char) -> usize {
-    CharRange::all().iter().position(|it| it == c).unwrap()
+    let code_point = c as usize;
+    if code_point > 0xdfff {
+        code_point - 0x800
+    } else {
+        code_point
+    }
 }
 
 fn format_alternation(
     f: &mut Formatter<'_>,
     expr: &Expression,
     options: &[Expression],
-    config: &RegExpConfig,
+    config: &Arc<RegExpConfig>,
 ) -> Result {
-    let (left_parenthesis, right_parenthesis, pipe) = vec![
-        if config.is_capturing_group_enabled() {
+    let (left_parenthesis, right_parenthesis, pipe) = [
+        if config.is_alternation_captured() {
             ColorizableString::CapturingLeftParenthesis
+        } else if config.is_atomic_group_used() {
+            ColorizableString::AtomicLeftParenthesis
         } else {
             ColorizableString::NonCapturingLeftParenthesis
         },
@@ -62,7 +81,7 @@ fn format_alternation(
         ColorizableString::Pipe,
     ]
     .iter()
-    .map(|it| it.to_colorized_string(config.is_output_colorized))
+    .map(|it| it.to_colorized_string(config))
     .collect_tuple()
     .unwrap();
 
@@ -80,78 +99,170 @@ fn format_alternation(
     write!(f, "{}", alternation_str)
 }
 
+fn format_wildcard(
+    f: &mut Formatter<'_>,
+    is_newline_contained: bool,
+    config: &Arc<RegExpConfig>,
+) -> Result {
+    let wildcard = if is_newline_contained && !config.is_dotall_enabled {
+        vec![
+            ColorizableString::LeftBracket,
+            ColorizableString::SpaceCharClass,
+            ColorizableString::NonSpaceCharClass,
+            ColorizableString::RightBracket,
+        ]
+    } else {
+        vec![ColorizableString::Dot]
+    };
+
+    write!(
+        f,
+        "{}",
+        wildcard
+            .iter()
+            .map(|it| it.to_colorized_string(config))
+            .join("")
+    )
+}
+
+/// Formats a character class. Character classes are only ever created from
+/// at least one character, so an empty set is a bug which is reported in debug builds.
+/// Release builds emit the never-matching class `[]` in this case.
 fn format_character_class(
     f: &mut Formatter<'_>,
     char_set: &BTreeSet<char>,
-    config: &RegExpConfig,
+    config: &Arc<RegExpConfig>,
 ) -> Result {
-    let chars_to_escape = ['[', ']', '\\', '-', '^'];
-    let escaped_char_set = char_set
-        .iter()
-        .map(|c| {
-            if chars_to_escape.contains(&c) {
-                format!("{}{}", "\\", c)
-            } else if c == &'\n' {
-                "\\n".to_string()
-            } else if c == &'\r' {
-                "\\r".to_string()
-            } else if c == &'\t' {
-                "\\t".to_string()
-            } else {
-                c.to_string()
-            }
-        })
-        .collect_vec();
-    let char_positions = char_set
-        .iter()
-        .map(|&it| get_codepoint_position(it))
-        .collect_vec();
+    debug_assert!(!char_set.is_empty(), "Character class must not be empty");
 
-    let mut subsets = vec![];
-    let mut subset = vec![];
+    if char_set.len() > config.wildcard_threshold {
+        return format_wildcard(f, char_set.contains(&'\n'), config);
+    }
+
+    if let Some(literal) = Expression::new_single_char_literal(char_set, config) {
+        return write!(f, "{}", literal);
+    }
 
-    for ((first_c, first_pos), (second_c, second_pos)) in
-        escaped_char_set.iter().zip(char_positions).tuple_windows()
+    if config.flavor.is_hex_digit_shorthand_supported() {
+        if is_hex_digit_class(char_set) {
+            return write!(
+                f,
+                "{}",
+                ColorizableString::HexDigitCharClass.to_colorized_string(config)
+            );
+        }
+        if is_non_hex_digit_class(char_set) {
+            return write!(
+                f,
+                "{}",
+                ColorizableString::NonHexDigitCharClass.to_colorized_string(config)
+            );
+        }
+    }
+
+    if config.flavor == RegExpFlavor::RustBytes
+        && !config.is_byte_mode
+        && char_set.iter().any(|it| !it.is_ascii())
     {
+        return format_byte_character_class(f, char_set, config);
+    }
+
+    let chars = char_set.iter().copied().collect_vec();
+    let mut char_ranges = vec![];
+
+    if config.is_range_compression_enabled {
+        let mut subsets = vec![];
+        let mut subset = vec![];
+
+        for (first_c, second_c) in chars.iter().tuple_windows() {
+            if subset.is_empty() {
+                subset.push(*first_c);
+            }
+            if get_codepoint_position(*second_c) == get_codepoint_position(*first_c) + 1 {
+                subset.push(*second_c);
+            } else {
+                subsets.push(subset);
+                subset = vec![*second_c];
+            }
+        }
+
         if subset.is_empty() {
-            subset.push(first_c);
+            subset.extend(chars.first());
         }
-        if second_pos == first_pos + 1 {
-            subset.push(second_c);
-        } else {
+        if !subset.is_empty() {
             subsets.push(subset);
-            subset = vec![];
-            subset.push(second_c);
         }
+
+        for subset in subsets.iter() {
+            if (subset.len() as u32) < config.minimum_range_length {
+                char_ranges.extend(subset.iter().map(|&c| (c, c)));
+            } else {
+                char_ranges.push((*subset.first().unwrap(), *subset.last().unwrap()));
+            }
+        }
+    } else {
+        char_ranges.extend(chars.iter().map(|&c| (c, c)));
+    }
+
+    format_char_ranges(f, &char_ranges, config)
+}
+
+/// Formats a character class created from a range. The characters of the range are only
+/// collected if they are rendered one by one anyway, so even large ranges are rendered quickly.
+fn format_character_range(
+    f: &mut Formatter<'_>,
+    range: &RangeInclusive<char>,
+    config: &Arc<RegExpConfig>,
+) -> Result {
+    let char_count = char_range_len(range);
+
+    if char_count > config.wildcard_threshold {
+        return format_wildcard(f, range.contains(&'\n'), config);
     }
 
-    subsets.push(subset);
+    let is_compressed =
+        config.is_range_compression_enabled && char_count as u32 >= config.minimum_range_length;
+    let is_byte_class =
+        config.flavor == RegExpFlavor::RustBytes && !config.is_byte_mode && !range.end().is_ascii();
+
+    if char_count == 1 || !is_compressed || is_byte_class {
+        return format_character_class(f, &range.clone().collect(), config);
+    }
+    format_char_ranges(f, &[(*range.start(), *range.end())], config)
+}
 
-    let mut char_class_strs = vec![];
-    let (hyphen, left_bracket, right_bracket) = vec![
-        ColorizableString::Hyphen,
+fn format_char_ranges(
+    f: &mut Formatter<'_>,
+    char_ranges: &[(char, char)],
+    config: &Arc<RegExpConfig>,
+) -> Result {
+    let (hyphen, left_bracket, right_bracket) = [
+        ColorizableString::RangeHyphen,
         ColorizableString::LeftBracket,
         ColorizableString::RightBracket,
     ]
     .iter()
-    .map(|it| it.to_colorized_string(config.is_output_colorized))
+    .map(|it| it.to_colorized_string(config))
     .collect_tuple()
     .unwrap();
 
-    for subset in subsets.iter() {
-        if subset.len() <= 2 {
-            for c in subset.iter() {
-                char_class_strs.push((*c).to_string());
+    let last_idx = char_ranges.len().saturating_sub(1);
+    let char_class_strs = char_ranges
+        .iter()
+        .enumerate()
+        .map(|(idx, &(first_c, last_c))| {
+            if first_c == last_c {
+                escape_char_class_member(first_c, idx == 0, idx == 0 || idx == last_idx, config)
+            } else {
+                format!(
+                    "{}{}{}",
+                    escape_char_class_member(first_c, idx == 0, false, config),
+                    hyphen,
+                    escape_char_class_member(last_c, false, false, config)
+                )
             }
-        } else {
-            char_class_strs.push(format!(
-                "{}{}{}",
-                subset.first().unwrap(),
-                hyphen,
-                subset.last().unwrap()
-            ));
-        }
-    }
+        })
+        .collect_vec();
 
     write!(
         f,
@@ -162,19 +273,108 @@ fn format_character_class(
     )
 }
 
+fn is_hex_digit_class(char_set: &BTreeSet<char>) -> bool {
+    char_set.len() == HEX_DIGIT_COUNT && char_set.iter().all(|it| it.is_ascii_hexdigit())
+}
+
+/// Returns `true` if the character class contains every character except the hex digits.
+fn is_non_hex_digit_class(char_set: &BTreeSet<char>) -> bool {
+    char_set.len() as u128 == UNICODE_SCALAR_VALUE_COUNT - HEX_DIGIT_COUNT as u128
+        && !char_set.iter().any(|it| it.is_ascii_hexdigit())
+}
+
+/// Escapes a member of a character class. The caret only needs to be escaped
+/// at the beginning of the class where it would negate the class otherwise.
+/// The hyphen can stay literal as a single member at the beginning or the end
+/// of the class where it cannot denote a range.
+fn escape_char_class_member(
+    c: char,
+    is_leading: bool,
+    is_hyphen_literal: bool,
+    config: &Arc<RegExpConfig>,
+) -> String {
+    if let Some(sequence) = config.custom_escapes.get(&c) {
+        return sequence.clone();
+    }
+    if !config.is_byte_mode {
+        if let Some(escaped_char) = escape_control_char(c, &config.escape_style) {
+            return escaped_char;
+        }
+    }
+    match c {
+        '[' | ']' | '\\' => format!("\\{}", c),
+        _ if config.is_strict_escaping && is_strictly_escaped(c) => format!("\\{}", c),
+        _ if config.is_escaped_delimiter(c) => format!("\\{}", c),
+        '^' if is_leading => "\\^".to_string(),
+        '-' if !is_hyphen_literal => "\\-".to_string(),
+        '\n' => "\\n".to_string(),
+        '\r' => "\\r".to_string(),
+        '\t' => "\\t".to_string(),
+        _ if config.is_byte_mode && (!c.is_ascii() || c.is_ascii_control()) => {
+            format!("\\x{:02x}", c as u32)
+        }
+        _ if config.is_non_ascii_char_escaped && !c.is_ascii() => {
+            escape_code_point(c as u32, &config.escape_style)
+        }
+        _ => c.to_string(),
+    }
+}
+
+fn format_byte_character_class(
+    f: &mut Formatter<'_>,
+    char_set: &BTreeSet<char>,
+    config: &Arc<RegExpConfig>,
+) -> Result {
+    let (ascii_chars, non_ascii_chars): (BTreeSet<char>, BTreeSet<char>) =
+        char_set.iter().partition(|it| it.is_ascii());
+    let byte_escapes = format!(
+        "(?-u:{})",
+        non_ascii_chars
+            .iter()
+            .map(|&it| escape_utf8_bytes(it))
+            .join("|")
+    );
+
+    if ascii_chars.is_empty() {
+        return write!(f, "{}", byte_escapes);
+    }
+
+    let (left_parenthesis, right_parenthesis, pipe) = [
+        if config.is_alternation_captured() {
+            ColorizableString::CapturingLeftParenthesis
+        } else {
+            ColorizableString::NonCapturingLeftParenthesis
+        },
+        ColorizableString::RightParenthesis,
+        ColorizableString::Pipe,
+    ]
+    .iter()
+    .map(|it| it.to_colorized_string(config))
+    .collect_tuple()
+    .unwrap();
+
+    let ascii_expr = Expression::CharacterClass(ascii_chars, config.clone());
+
+    write!(
+        f,
+        "{}{}{}{}{}",
+        left_parenthesis, ascii_expr, pipe, byte_escapes, right_parenthesis
+    )
+}
+
 fn format_concatenation(
     f: &mut Formatter<'_>,
     expr: &Expression,
     expr1: &Expression,
     expr2: &Expression,
-    config: &RegExpConfig,
+    config: &Arc<RegExpConfig>,
 ) -> Result {
-    let expr_strs = vec![expr1, expr2]
+    let expr_strs = [expr1, expr2]
         .iter()
         .map(|&it| {
             if it.precedence() < expr.precedence() && !it.is_single_codepoint() {
-                let (left_parenthesis, right_parenthesis) = vec![
-                    if config.is_capturing_group_enabled() {
+                let (left_parenthesis, right_parenthesis) = [
+                    if config.is_concatenation_captured() {
                         ColorizableString::CapturingLeftParenthesis
                     } else {
                         ColorizableString::NonCapturingLeftParenthesis
@@ -182,7 +382,7 @@ fn format_concatenation(
                     ColorizableString::RightParenthesis,
                 ]
                 .iter()
-                .map(|it| it.to_colorized_string(config.is_output_colorized))
+                .map(|it| it.to_colorized_string(config))
                 .collect_tuple()
                 .unwrap();
 
@@ -204,9 +404,9 @@ fn format_concatenation(
 fn format_literal(
     f: &mut Formatter<'_>,
     cluster: &GraphemeCluster,
-    config: &RegExpConfig,
+    config: &Arc<RegExpConfig>,
 ) -> Result {
-    let literal_str = cluster
+    let escaped_graphemes = cluster
         .graphemes()
         .iter()
         .cloned()
@@ -229,42 +429,116 @@ fn format_literal(
             }
             grapheme.to_string()
         })
-        .join("");
+        .collect_vec();
+
+    if !config.is_literal_quoting_used() {
+        return write!(f, "{}", escaped_graphemes.join(""));
+    }
+
+    let mut literal_str = String::new();
+    let mut run = vec![];
+
+    for (grapheme, escaped_grapheme) in cluster.graphemes().iter().zip(escaped_graphemes) {
+        match quotable_char(grapheme, config) {
+            Some(c) => run.push((c, escaped_grapheme)),
+            None => {
+                push_literal_run(&mut literal_str, &mut run);
+                literal_str.push_str(&escaped_grapheme);
+            }
+        }
+    }
+    push_literal_run(&mut literal_str, &mut run);
 
     write!(f, "{}", literal_str)
 }
 
+/// Returns the character of the given grapheme if it can be written verbatim
+/// within `\Q...\E`. Control characters, characters that are escaped by other settings
+/// and graphemes representing repetitions or character classes cannot.
+fn quotable_char(grapheme: &Grapheme, config: &RegExpConfig) -> Option<char> {
+    if grapheme.has_repetitions()
+        || grapheme.minimum() != 1
+        || grapheme.maximum() != 1
+        || grapheme.chars().len() != 1
+    {
+        return None;
+    }
+    let c = grapheme.chars()[0].chars().exactly_one().ok()?;
+    let is_escaped_otherwise = c.is_control()
+        || (!c.is_ascii() && config.is_non_ascii_char_escaped)
+        || config.is_escaped_delimiter(c)
+        || config.custom_escapes.contains_key(&c);
+
+    if is_escaped_otherwise {
+        None
+    } else {
+        Some(c)
+    }
+}
+
+/// Writes the collected run of characters, quoting it if at least two of them
+/// would have to be escaped otherwise. An `\E` within the run is written as `\E\\E\Q`.
+fn push_literal_run(literal_str: &mut String, run: &mut Vec<(char, String)>) {
+    let escaped_char_count = run
+        .iter()
+        .filter(|(c, escaped_grapheme)| escaped_grapheme != &c.to_string())
+        .count();
+
+    if escaped_char_count >= 2 {
+        let quoted_str = run
+            .iter()
+            .map(|(c, _)| c)
+            .collect::<String>()
+            .replace("\\E", "\\E\\\\E\\Q");
+        literal_str.push_str(&format!("\\Q{}\\E", quoted_str));
+    } else {
+        literal_str.extend(
+            run.iter()
+                .map(|(_, escaped_grapheme)| escaped_grapheme.as_str()),
+        );
+    }
+    run.clear();
+}
+
 fn format_repetition(
     f: &mut Formatter<'_>,
     expr: &Expression,
     expr1: &Expression,
     quantifier: &Quantifier,
-    config: &RegExpConfig,
+    config: &Arc<RegExpConfig>,
 ) -> Result {
-    let (colored_quantifier, left_parenthesis, right_parenthesis) = vec![
-        match quantifier {
-            Quantifier::KleeneStar => ColorizableString::Asterisk,
-            Quantifier::QuestionMark => ColorizableString::QuestionMark,
-        },
-        if config.is_capturing_group_enabled() {
+    let colored_quantifier = quantifier.render(config);
+    let (left_parenthesis, right_parenthesis) = [
+        if config.is_repetition_captured() {
             ColorizableString::CapturingLeftParenthesis
+        } else if config.is_atomic_group_used() {
+            ColorizableString::AtomicLeftParenthesis
         } else {
             ColorizableString::NonCapturingLeftParenthesis
         },
         ColorizableString::RightParenthesis,
     ]
     .iter()
-    .map(|it| it.to_colorized_string(config.is_output_colorized))
+    .map(|it| it.to_colorized_string(config))
     .collect_tuple()
     .unwrap();
 
-    if expr1.precedence() < expr.precedence() && !expr1.is_single_codepoint() {
-        write!(
-            f,
-            "{}{}{}{}",
-            left_parenthesis, expr1, right_parenthesis, colored_quantifier
-        )
+    let is_unit_grouped = expr1.precedence() < expr.precedence() && !expr1.is_single_codepoint();
+    let unit = if is_unit_grouped {
+        format!("{}{}{}", left_parenthesis, expr1, right_parenthesis)
     } else {
-        write!(f, "{}{}", expr1, colored_quantifier)
+        expr1.to_string()
+    };
+    // Copies of a capturing group would be numbered separately, so the unit is not expanded.
+    let is_unit_captured = (is_unit_grouped && config.is_repetition_captured())
+        || without_colorization(|| count_capturing_groups(&expr1.to_string())) > 0;
+
+    match quantifier {
+        Quantifier::Range(min, max)
+            if config.is_repetition_expanded(*min, *max) && !is_unit_captured =>
+        {
+            write!(f, "{}", unit.repeat(*min as usize))
+        }
+        _ => write!(f, "{}{}", unit, colored_quantifier),
     }
 }
@@ -0,0 +1,582 @@
+/*
+ * Copyright © 2019-2020 Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::ast::Expression;
+use crate::char::{char_range_len, Grapheme, UNBOUNDED_REPETITIONS};
+use crate::unicode_tables::{DECIMAL_NUMBER, WHITE_SPACE, WORD};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::ops::RangeInclusive;
+
+/// Repetitions with larger bounds are treated as unbounded by [`Automaton`](struct.Automaton.html)
+/// in order to keep its size small. The automaton then accepts more strings than the expression.
+const MAX_UNROLLED_REPETITIONS: u32 = 32;
+
+/// A node of the syntax tree which a match goes through, identified by its path from
+/// the root of the syntax tree. A path consists of the indices of the child nodes,
+/// the index of the node itself being the last one.
+#[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
+enum MatchedNode {
+    /// An option of an alternation.
+    Option(Vec<usize>),
+    /// A character class together with the member which has been matched by it.
+    ClassMember(Vec<usize>, char),
+}
+
+type MatchedNodes = BTreeSet<MatchedNode>;
+
+impl Expression {
+    /// Returns `true` if this expression matches all of the given characters.
+    pub(crate) fn is_match(&self, chars: &[char]) -> bool {
+        self.match_ends(chars, 0).contains(&chars.len())
+    }
+
+    /// Returns the paths of all alternation options which a match of this expression
+    /// against all of the given characters goes through. The children of concatenations
+    /// have the indices 0 and 1, the child of a repetition has the index 0.
+    pub(crate) fn matched_options(&self, chars: &[char]) -> BTreeSet<Vec<usize>> {
+        self.matched_nodes(chars)
+            .into_iter()
+            .filter_map(|node| match node {
+                MatchedNode::Option(path) => Some(path),
+                MatchedNode::ClassMember(_, _) => None,
+            })
+            .collect()
+    }
+
+    /// Returns the paths of all character classes which a match of this expression against
+    /// all of the given characters goes through, each together with the matched member.
+    pub(crate) fn matched_class_members(&self, chars: &[char]) -> BTreeSet<(Vec<usize>, char)> {
+        self.matched_nodes(chars)
+            .into_iter()
+            .filter_map(|node| match node {
+                MatchedNode::Option(_) => None,
+                MatchedNode::ClassMember(path, member) => Some((path, member)),
+            })
+            .collect()
+    }
+
+    fn matched_nodes(&self, chars: &[char]) -> MatchedNodes {
+        self.traced_match_ends(chars, 0, &mut vec![])
+            .remove(&chars.len())
+            .unwrap_or_default()
+    }
+
+    /// Returns all positions at which a match of this expression can end if it starts at
+    /// position `start`, together with the nodes the matches go through.
+    fn traced_match_ends(
+        &self,
+        chars: &[char],
+        start: usize,
+        path: &mut Vec<usize>,
+    ) -> BTreeMap<usize, MatchedNodes> {
+        let mut result = BTreeMap::new();
+        match self {
+            Expression::Alternation(options, _) => {
+                for (i, option) in options.iter().enumerate() {
+                    path.push(i);
+                    for (end, mut matched_nodes) in option.traced_match_ends(chars, start, path) {
+                        matched_nodes.insert(MatchedNode::Option(path.clone()));
+                        insert_matched_nodes(&mut result, end, matched_nodes);
+                    }
+                    path.pop();
+                }
+            }
+            Expression::Concatenation(expr1, expr2, _) => {
+                path.push(0);
+                let first_ends = expr1.traced_match_ends(chars, start, path);
+                path.pop();
+                path.push(1);
+                for (middle, first_nodes) in first_ends {
+                    for (end, second_nodes) in expr2.traced_match_ends(chars, middle, path) {
+                        insert_matched_nodes(&mut result, end, first_nodes.clone());
+                        insert_matched_nodes(&mut result, end, second_nodes);
+                    }
+                }
+                path.pop();
+            }
+            Expression::Repetition(expr, quantifier, _) => {
+                let (min, max) = quantifier.bounds();
+                let mut positions = BTreeMap::from([(start, MatchedNodes::new())]);
+                let mut count = 0;
+                path.push(0);
+
+                loop {
+                    if count >= min {
+                        for (position, matched_nodes) in positions.iter() {
+                            insert_matched_nodes(&mut result, *position, matched_nodes.clone());
+                        }
+                    }
+                    if count == max || positions.is_empty() {
+                        break;
+                    }
+                    let mut next_positions = BTreeMap::new();
+                    for (position, matched_nodes) in positions.iter() {
+                        for (end, unit_nodes) in expr.traced_match_ends(chars, *position, path) {
+                            insert_matched_nodes(&mut next_positions, end, matched_nodes.clone());
+                            insert_matched_nodes(&mut next_positions, end, unit_nodes);
+                        }
+                    }
+                    let is_exhausted = next_positions.iter().all(|(position, matched_nodes)| {
+                        result
+                            .get(position)
+                            .is_some_and(|it: &MatchedNodes| matched_nodes.is_subset(it))
+                    });
+                    if count >= min && is_exhausted {
+                        break;
+                    }
+                    positions = next_positions;
+                    count += 1;
+                }
+                path.pop();
+            }
+            Expression::CharacterClass(_, _) | Expression::CharacterRange(_, _) => {
+                for end in self.match_ends(chars, start) {
+                    let member = MatchedNode::ClassMember(path.clone(), chars[start]);
+                    result.insert(end, MatchedNodes::from([member]));
+                }
+            }
+            Expression::Literal(_, _) => {
+                for end in self.match_ends(chars, start) {
+                    result.insert(end, MatchedNodes::new());
+                }
+            }
+        }
+        result
+    }
+
+    /// Returns all positions in `chars` at which a match of this expression
+    /// can end if it starts at position `start`.
+    fn match_ends(&self, chars: &[char], start: usize) -> BTreeSet<usize> {
+        match self {
+            Expression::Alternation(options, _) => options
+                .iter()
+                .flat_map(|it| it.match_ends(chars, start))
+                .collect(),
+            Expression::CharacterClass(char_set, config) => match chars.get(start) {
+                // A wildcard is rendered as `[\s\S]` if the class contains a newline and as `.`
+                // otherwise, which only matches a newline if dotall mode is enabled.
+                Some(c)
+                    if char_set.len() > config.wildcard_threshold
+                        && (*c != '\n' || char_set.contains(c) || config.is_dotall_enabled) =>
+                {
+                    BTreeSet::from([start + 1])
+                }
+                Some(c) if char_set.contains(c) => BTreeSet::from([start + 1]),
+                _ => BTreeSet::new(),
+            },
+            Expression::CharacterRange(range, config) => match chars.get(start) {
+                Some(c)
+                    if char_range_len(range) > config.wildcard_threshold
+                        && (*c != '\n' || range.contains(c) || config.is_dotall_enabled) =>
+                {
+                    BTreeSet::from([start + 1])
+                }
+                Some(c) if range.contains(c) => BTreeSet::from([start + 1]),
+                _ => BTreeSet::new(),
+            },
+            Expression::Concatenation(expr1, expr2, _) => expr1
+                .match_ends(chars, start)
+                .into_iter()
+                .flat_map(|it| expr2.match_ends(chars, it))
+                .collect(),
+            Expression::Literal(cluster, _) => {
+                cluster
+                    .graphemes()
+                    .iter()
+                    .fold(BTreeSet::from([start]), |positions, grapheme| {
+                        positions
+                            .into_iter()
+                            .flat_map(|it| grapheme_match_ends(grapheme, chars, it))
+                            .collect()
+                    })
+            }
+            Expression::Repetition(expr, quantifier, _) => {
+                let (min, max) = quantifier.bounds();
+                repeated_match_ends(start, min, max, |it| expr.match_ends(chars, it))
+            }
+        }
+    }
+}
+
+fn insert_matched_nodes(
+    result: &mut BTreeMap<usize, MatchedNodes>,
+    end: usize,
+    matched_nodes: MatchedNodes,
+) {
+    result.entry(end).or_default().extend(matched_nodes);
+}
+
+fn grapheme_match_ends(grapheme: &Grapheme, chars: &[char], start: usize) -> BTreeSet<usize> {
+    let unit_match_ends = |position: usize| {
+        if grapheme.has_repetitions() {
+            grapheme
+                .repetitions
+                .iter()
+                .fold(BTreeSet::from([position]), |positions, it| {
+                    positions
+                        .into_iter()
+                        .flat_map(|position| grapheme_match_ends(it, chars, position))
+                        .collect()
+                })
+        } else {
+            value_match_end(&grapheme.value(), chars, position)
+                .into_iter()
+                .collect()
+        }
+    };
+    repeated_match_ends(
+        start,
+        grapheme.minimum(),
+        grapheme.maximum(),
+        unit_match_ends,
+    )
+}
+
+/// Matches the value of a grapheme which consists of literal characters
+/// and the shorthand character classes created by the conversion features.
+fn value_match_end(value: &str, chars: &[char], start: usize) -> Option<usize> {
+    let mut position = start;
+
+    for condition in value_conditions(value) {
+        if !condition.contains(*chars.get(position)?) {
+            return None;
+        }
+        position += 1;
+    }
+    Some(position)
+}
+
+/// Returns the condition on each character matched by the value of a grapheme.
+fn value_conditions(value: &str) -> Vec<CharCondition> {
+    let mut conditions = vec![];
+    let mut value_chars = value.chars().peekable();
+
+    while let Some(c) = value_chars.next() {
+        let shorthand_condition = match (c, value_chars.peek()) {
+            ('\\', Some('d')) => Some(CharCondition::Table(DECIMAL_NUMBER, false)),
+            ('\\', Some('s')) => Some(CharCondition::Table(WHITE_SPACE, false)),
+            ('\\', Some('w')) => Some(CharCondition::Table(WORD, false)),
+            ('\\', Some('D')) => Some(CharCondition::Table(DECIMAL_NUMBER, true)),
+            ('\\', Some('S')) => Some(CharCondition::Table(WHITE_SPACE, true)),
+            ('\\', Some('W')) => Some(CharCondition::Table(WORD, true)),
+            _ => None,
+        };
+        match shorthand_condition {
+            Some(condition) => {
+                value_chars.next();
+                conditions.push(condition);
+            }
+            None => conditions.push(CharCondition::Chars(BTreeSet::from([c]))),
+        }
+    }
+    conditions
+}
+
+fn repeated_match_ends(
+    start: usize,
+    min: u32,
+    max: u32,
+    unit_match_ends: impl Fn(usize) -> BTreeSet<usize>,
+) -> BTreeSet<usize> {
+    let mut result = BTreeSet::new();
+    let mut positions = BTreeSet::from([start]);
+    let mut count = 0;
+
+    loop {
+        if count >= min {
+            result.extend(positions.iter().copied());
+        }
+        if count == max || positions.is_empty() {
+            return result;
+        }
+        let next_positions: BTreeSet<usize> = positions
+            .iter()
+            .flat_map(|&it| unit_match_ends(it))
+            .collect();
+        if count >= min && next_positions.is_subset(&result) {
+            return result;
+        }
+        positions = next_positions;
+        count += 1;
+    }
+}
+
+fn is_in_table(c: char, table: &[(char, char)]) -> bool {
+    table.iter().any(|&(start, end)| (start..=end).contains(&c))
+}
+
+/// A condition which a single character of the input has to meet.
+#[derive(Clone, Debug)]
+enum CharCondition {
+    /// The character is one of the given ones.
+    Chars(BTreeSet<char>),
+    /// The character is contained in the given range.
+    Range(RangeInclusive<char>),
+    /// Any character matches, a newline only if the flag is set.
+    Any(bool),
+    /// The character is contained in the given table, or not contained if the flag is set.
+    /// This is how the shorthand character classes such as `\d` are represented.
+    Table(&'static [(char, char)], bool),
+}
+
+impl CharCondition {
+    fn contains(&self, c: char) -> bool {
+        match self {
+            CharCondition::Chars(chars) => chars.contains(&c),
+            CharCondition::Range(range) => range.contains(&c),
+            CharCondition::Any(is_newline_matched) => c != '\n' || *is_newline_matched,
+            CharCondition::Table(table, is_negated) => is_in_table(c, table) != *is_negated,
+        }
+    }
+
+    fn intersects(&self, other: &CharCondition) -> bool {
+        match (self, other) {
+            (CharCondition::Chars(chars), _) => chars.iter().any(|&c| other.contains(c)),
+            (_, CharCondition::Chars(_)) => other.intersects(self),
+            (CharCondition::Range(range), CharCondition::Range(other_range)) => {
+                range.start() <= other_range.end() && other_range.start() <= range.end()
+            }
+            (CharCondition::Range(range), _) => range.clone().any(|c| other.contains(c)),
+            (_, CharCondition::Range(_)) => other.intersects(self),
+            (CharCondition::Table(table, false), _) => table
+                .iter()
+                .any(|&(start, end)| (start..=end).any(|c| other.contains(c))),
+            (_, CharCondition::Table(_, false)) => other.intersects(self),
+            // Each of the remaining conditions is met by all but a small part of the characters.
+            _ => true,
+        }
+    }
+}
+
+/// A nondeterministic finite automaton which accepts the strings matched by an expression.
+/// Every transition either consumes a character meeting its condition or none at all.
+/// The automaton starts in state 0 and accepts in state 1.
+pub(crate) struct Automaton {
+    transitions: Vec<Vec<(Option<CharCondition>, usize)>>,
+}
+
+impl Automaton {
+    const START_STATE: usize = 0;
+    const ACCEPTING_STATE: usize = 1;
+
+    pub(crate) fn from(expr: &Expression) -> Self {
+        let mut automaton = Self {
+            transitions: vec![vec![], vec![]],
+        };
+        automaton.add_expression(expr, Self::START_STATE, Self::ACCEPTING_STATE);
+        automaton
+    }
+
+    /// Returns `true` if this automaton accepts a proper prefix of any string
+    /// accepted by the other automaton.
+    pub(crate) fn accepts_proper_prefix_of(&self, other: &Automaton) -> bool {
+        let continuable_states = other.continuable_states();
+        let mut visited_states = HashSet::new();
+        let mut states = vec![(Self::START_STATE, Self::START_STATE)];
+
+        while let Some((state, other_state)) = states.pop() {
+            if !visited_states.insert((state, other_state)) {
+                continue;
+            }
+            if state == Self::ACCEPTING_STATE && continuable_states.contains(&other_state) {
+                return true;
+            }
+            for (condition, next_state) in self.transitions[state].iter() {
+                match condition {
+                    Some(condition) => {
+                        for (other_condition, other_next_state) in
+                            other.transitions[other_state].iter()
+                        {
+                            if other_condition
+                                .as_ref()
+                                .is_some_and(|it| condition.intersects(it))
+                            {
+                                states.push((*next_state, *other_next_state));
+                            }
+                        }
+                    }
+                    None => states.push((*next_state, other_state)),
+                }
+            }
+            for (other_condition, other_next_state) in other.transitions[other_state].iter() {
+                if other_condition.is_none() {
+                    states.push((state, *other_next_state));
+                }
+            }
+        }
+        false
+    }
+
+    /// Returns the states from which at least one more character has to be consumed
+    /// in order to reach the accepting state.
+    fn continuable_states(&self) -> HashSet<usize> {
+        let accepting_states = self.predecessors(HashSet::from([Self::ACCEPTING_STATE]), true);
+        let consuming_states = self
+            .transitions
+            .iter()
+            .enumerate()
+            .filter(|(_, transitions)| {
+                transitions.iter().any(|(condition, next_state)| {
+                    condition.is_some() && accepting_states.contains(next_state)
+                })
+            })
+            .map(|(state, _)| state)
+            .collect();
+        self.predecessors(consuming_states, false)
+    }
+
+    /// Returns the given states together with all states from which they can be reached,
+    /// either by any transitions or by those which do not consume a character.
+    fn predecessors(
+        &self,
+        mut states: HashSet<usize>,
+        is_consuming_transition_followed: bool,
+    ) -> HashSet<usize> {
+        let mut is_changed = true;
+        while is_changed {
+            is_changed = false;
+            for (state, transitions) in self.transitions.iter().enumerate() {
+                if !states.contains(&state)
+                    && transitions.iter().any(|(condition, next_state)| {
+                        (is_consuming_transition_followed || condition.is_none())
+                            && states.contains(next_state)
+                    })
+                {
+                    states.insert(state);
+                    is_changed = true;
+                }
+            }
+        }
+        states
+    }
+
+    fn add_state(&mut self) -> usize {
+        self.transitions.push(vec![]);
+        self.transitions.len() - 1
+    }
+
+    fn add_transition(&mut self, from: usize, condition: Option<CharCondition>, to: usize) {
+        self.transitions[from].push((condition, to));
+    }
+
+    fn add_expression(&mut self, expr: &Expression, from: usize, to: usize) {
+        match expr {
+            Expression::Alternation(options, _) => {
+                for option in options {
+                    self.add_expression(option, from, to);
+                }
+            }
+            Expression::CharacterClass(char_set, config) => {
+                let condition = if char_set.len() > config.wildcard_threshold {
+                    CharCondition::Any(char_set.contains(&'\n') || config.is_dotall_enabled)
+                } else {
+                    CharCondition::Chars(char_set.clone())
+                };
+                self.add_transition(from, Some(condition), to);
+            }
+            Expression::CharacterRange(range, config) => {
+                let condition = if char_range_len(range) > config.wildcard_threshold {
+                    CharCondition::Any(range.contains(&'\n') || config.is_dotall_enabled)
+                } else {
+                    CharCondition::Range(range.clone())
+                };
+                self.add_transition(from, Some(condition), to);
+            }
+            Expression::Concatenation(expr1, expr2, _) => {
+                let middle = self.add_state();
+                self.add_expression(expr1, from, middle);
+                self.add_expression(expr2, middle, to);
+            }
+            Expression::Literal(cluster, _) => self.add_graphemes(cluster.graphemes(), from, to),
+            Expression::Repetition(expr, quantifier, _) => {
+                let (min, max) = quantifier.bounds();
+                self.add_repetition(min, max, from, to, &|automaton, from, to| {
+                    automaton.add_expression(expr, from, to)
+                });
+            }
+        }
+    }
+
+    fn add_graphemes(&mut self, graphemes: &[Grapheme], from: usize, to: usize) {
+        let mut state = from;
+        for grapheme in graphemes {
+            let next_state = self.add_state();
+            self.add_repetition(
+                grapheme.minimum(),
+                grapheme.maximum(),
+                state,
+                next_state,
+                &|automaton, from, to| {
+                    if grapheme.has_repetitions() {
+                        automaton.add_graphemes(&grapheme.repetitions, from, to);
+                    } else {
+                        automaton.add_chars(value_conditions(&grapheme.value()), from, to);
+                    }
+                },
+            );
+            state = next_state;
+        }
+        self.add_transition(state, None, to);
+    }
+
+    fn add_chars(&mut self, conditions: Vec<CharCondition>, from: usize, to: usize) {
+        let mut state = from;
+        for condition in conditions {
+            let next_state = self.add_state();
+            self.add_transition(state, Some(condition), next_state);
+            state = next_state;
+        }
+        self.add_transition(state, None, to);
+    }
+
+    fn add_repetition(
+        &mut self,
+        min: u32,
+        max: u32,
+        from: usize,
+        to: usize,
+        add_unit: &dyn Fn(&mut Self, usize, usize),
+    ) {
+        let min = min.min(MAX_UNROLLED_REPETITIONS);
+        let max = if max > MAX_UNROLLED_REPETITIONS {
+            UNBOUNDED_REPETITIONS
+        } else {
+            max
+        };
+        let mut state = from;
+
+        for _ in 0..min {
+            let next_state = self.add_state();
+            add_unit(self, state, next_state);
+            state = next_state;
+        }
+        if max == UNBOUNDED_REPETITIONS {
+            // The loop needs a state of its own as the given ones may be shared with other paths.
+            let loop_state = self.add_state();
+            self.add_transition(state, None, loop_state);
+            add_unit(self, loop_state, loop_state);
+            state = loop_state;
+        } else {
+            for _ in min..max {
+                self.add_transition(state, None, to);
+                let next_state = self.add_state();
+                add_unit(self, state, next_state);
+                state = next_state;
+            }
+        }
+        self.add_transition(state, None, to);
+    }
+}
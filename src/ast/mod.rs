@@ -14,11 +14,46 @@
  * limitations under the License.
  */
 
+//! The syntax tree of a generated regular expression.
+//!
+//! A [`RegExp`](../struct.RegExp.html) holds its tree in an [`Expression`](enum.Expression.html)
+//! which can be retrieved with [`RegExp::ast`](../struct.RegExp.html#method.ast).
+//! Trees can also be built from scratch, modified and rendered again with
+//! the `Display` implementation of `Expression`:
+//!
+//! ```
+//! use grex::ast::{Expression, Quantifier};
+//! use grex::RegExpConfig;
+//! use std::sync::Arc;
+//!
+//! let config = Arc::new(RegExpConfig::new());
+//! let expr = Expression::concatenation(
+//!     Expression::literal("ab", &config),
+//!     Expression::repetition(
+//!         Expression::alternation(
+//!             vec![
+//!                 Expression::literal("cd", &config),
+//!                 Expression::character_class(vec!['x', 'y', 'z'], &config),
+//!             ],
+//!             &config,
+//!         ),
+//!         Quantifier::Range(1, 3),
+//!         &config,
+//!     ),
+//!     &config,
+//! );
+//! assert_eq!(expr.to_string(), "ab(?:cd|[x-z]){1,3}");
+//! ```
+
+mod explain;
 mod expression;
 mod format;
+mod matching;
+mod ordering;
+mod pretty;
 mod quantifier;
 mod substring;
 
 pub use expression::Expression;
 pub use quantifier::Quantifier;
-pub use substring::Substring;
+pub(crate) use substring::Substring;
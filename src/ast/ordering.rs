@@ -0,0 +1,153 @@
+/*
+ * Copyright © 2019-2020 Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::ast::matching::Automaton;
+use crate::ast::Expression;
+use itertools::Itertools;
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+
+impl Expression {
+    /// Sorts the options of all alternations by descending frequency. The frequency of an option
+    /// is the total number of occurrences of the test cases whose match goes through the option.
+    /// Options of equal frequency keep their previous order.
+    pub(crate) fn order_alternations_by_frequency(
+        self,
+        frequencies: &[(Vec<char>, usize)],
+    ) -> Self {
+        let mut option_frequencies = BTreeMap::new();
+        for (chars, count) in frequencies {
+            for option in self.matched_options(chars) {
+                *option_frequencies.entry(option).or_insert(0) += count;
+            }
+        }
+        self.order_alternations_by(&mut vec![], &|path: &[usize]| {
+            Reverse(option_frequencies.get(path).copied().unwrap_or(0))
+        })
+    }
+
+    /// Sorts the options of all alternations by the position of the first test case
+    /// whose match goes through the respective option. Options used by the same
+    /// test case first keep their previous order.
+    pub(crate) fn order_alternations_by_input(self, test_cases: &[Vec<char>]) -> Self {
+        let mut first_positions = BTreeMap::new();
+        for (position, chars) in test_cases.iter().enumerate() {
+            for option in self.matched_options(chars) {
+                first_positions.entry(option).or_insert(position);
+            }
+        }
+        self.order_alternations_by(&mut vec![], &|path: &[usize]| {
+            first_positions
+                .get(path)
+                .copied()
+                .unwrap_or(test_cases.len())
+        })
+    }
+
+    /// Reorders the options of all alternations so that none of them matches a proper prefix
+    /// of a string matched by an earlier one, keeping the previous order wherever possible.
+    pub(crate) fn order_prefixes_last(self) -> Self {
+        match self {
+            Expression::Alternation(options, config) => Expression::Alternation(
+                order_options_prefixes_last(
+                    options
+                        .into_iter()
+                        .map(|it| it.order_prefixes_last())
+                        .collect_vec(),
+                ),
+                config,
+            ),
+            Expression::Concatenation(expr1, expr2, config) => Expression::Concatenation(
+                Box::from(expr1.order_prefixes_last()),
+                Box::from(expr2.order_prefixes_last()),
+                config,
+            ),
+            Expression::Repetition(expr, quantifier, config) => {
+                Expression::Repetition(Box::from(expr.order_prefixes_last()), quantifier, config)
+            }
+            _ => self,
+        }
+    }
+
+    /// Sorts the options of all alternations by the key of their path from the root
+    /// of the syntax tree, as returned by [`matched_options`](#method.matched_options).
+    fn order_alternations_by<K: Ord, F: Fn(&[usize]) -> K>(
+        self,
+        path: &mut Vec<usize>,
+        key: &F,
+    ) -> Self {
+        match self {
+            Expression::Alternation(options, config) => {
+                let mut options = options
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, it)| {
+                        path.push(i);
+                        let option_key = key(path);
+                        let option = it.order_alternations_by(path, key);
+                        path.pop();
+                        (option_key, option)
+                    })
+                    .collect_vec();
+                options.sort_by(|(first_key, _), (second_key, _)| first_key.cmp(second_key));
+                Expression::Alternation(options.into_iter().map(|(_, it)| it).collect(), config)
+            }
+            Expression::Concatenation(expr1, expr2, config) => {
+                path.push(0);
+                let expr1 = expr1.order_alternations_by(path, key);
+                path.pop();
+                path.push(1);
+                let expr2 = expr2.order_alternations_by(path, key);
+                path.pop();
+                Expression::Concatenation(Box::from(expr1), Box::from(expr2), config)
+            }
+            Expression::Repetition(expr, quantifier, config) => {
+                path.push(0);
+                let expr = expr.order_alternations_by(path, key);
+                path.pop();
+                Expression::Repetition(Box::from(expr), quantifier, config)
+            }
+            _ => self,
+        }
+    }
+}
+
+/// Reorders the given options so that none of them matches a proper prefix of a string
+/// matched by an earlier one. If the options are prefixes of each other, such as `a(?:bc)?`
+/// and `ab`, no such order exists and the first of them is kept in front.
+pub(crate) fn order_options_prefixes_last(options: Vec<Expression>) -> Vec<Expression> {
+    let automata = options.iter().map(Automaton::from).collect_vec();
+    let mut remaining = (0..options.len()).collect_vec();
+    let mut order = vec![];
+
+    while !remaining.is_empty() {
+        let i = remaining
+            .iter()
+            .position(|&i| {
+                !remaining
+                    .iter()
+                    .any(|&j| i != j && automata[i].accepts_proper_prefix_of(&automata[j]))
+            })
+            .unwrap_or(0);
+        order.push(remaining.remove(i));
+    }
+
+    let mut options = options.into_iter().map(Some).collect_vec();
+    order
+        .into_iter()
+        .map(|i| options[i].take().unwrap())
+        .collect_vec()
+}
@@ -0,0 +1,54 @@
+/*
+ * Copyright © 2019-2020 Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::ast::Expression;
+
+const INDENTATION: &str = "  ";
+
+impl Expression {
+    /// Appends an indented line for every node of this expression to `lines`,
+    /// starting at the given depth. The result is meant for inspection only.
+    pub(crate) fn pretty_print(&self, depth: usize, lines: &mut Vec<String>) {
+        let indentation = INDENTATION.repeat(depth);
+        match self {
+            Expression::Alternation(options, _) => {
+                lines.push(format!("{}Alternation", indentation));
+                for option in options {
+                    option.pretty_print(depth + 1, lines);
+                }
+            }
+            Expression::CharacterClass(_, _) | Expression::CharacterRange(_, _) => {
+                lines.push(format!("{}CharacterClass {}", indentation, self));
+            }
+            Expression::Concatenation(expr1, expr2, _) => {
+                lines.push(format!("{}Concatenation", indentation));
+                expr1.pretty_print(depth + 1, lines);
+                expr2.pretty_print(depth + 1, lines);
+            }
+            Expression::Literal(_, _) => {
+                lines.push(format!("{}Literal {}", indentation, self));
+            }
+            Expression::Repetition(expr, quantifier, _) => {
+                lines.push(format!("{}Repetition", indentation));
+                expr.pretty_print(depth + 1, lines);
+                lines.push(format!(
+                    "{}{}Quantifier {}",
+                    indentation, INDENTATION, quantifier
+                ));
+            }
+        }
+    }
+}
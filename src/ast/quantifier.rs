@@ -14,23 +14,147 @@
  * limitations under the License.
  */
 
+use crate::char::{ColorizableString, UNBOUNDED_REPETITIONS};
+use crate::regexp::RegExpConfig;
+use itertools::Itertools;
 use std::fmt::{Display, Formatter, Result};
 
+/// The quantifier of a repetition in the syntax tree of a regular expression.
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum Quantifier {
+    /// Zero or more times, rendered as `*`.
     KleeneStar,
+    /// Zero or one time, rendered as `?`.
     QuestionMark,
+    /// Between a minimum and a maximum number of times, rendered as `{min,max}`
+    /// or `{n}` if both are equal.
+    Range(u32, u32),
+}
+
+impl Quantifier {
+    /// Creates the quantifier for the given bounds, preferring `*` and `?` over ranges.
+    /// A maximum of `UNBOUNDED_REPETITIONS` denotes an open range.
+    pub(crate) fn from_bounds(min: u32, max: u32) -> Self {
+        match (min, max) {
+            (0, UNBOUNDED_REPETITIONS) => Quantifier::KleeneStar,
+            (0, 1) => Quantifier::QuestionMark,
+            _ => Quantifier::Range(min, max),
+        }
+    }
+
+    /// Returns the minimum and the maximum number of repetitions.
+    /// A maximum of `UNBOUNDED_REPETITIONS` denotes an open range.
+    pub(crate) fn bounds(&self) -> (u32, u32) {
+        match self {
+            Quantifier::KleeneStar => (0, UNBOUNDED_REPETITIONS),
+            Quantifier::QuestionMark => (0, 1),
+            Quantifier::Range(min, max) => (*min, *max),
+        }
+    }
+
+    /// Returns the quantifier of two adjacent repetitions of the same unit
+    /// by adding up their bounds, so that `{2}` and `{3}` become `{5}`.
+    pub(crate) fn add(&self, other: &Quantifier) -> Self {
+        let (min1, max1) = self.bounds();
+        let (min2, max2) = other.bounds();
+        Self::from_bounds(min1.saturating_add(min2), max1.saturating_add(max2))
+    }
+
+    /// Renders the quantifier as part of a regular expression with the given settings.
+    /// The shorthands `?`, `*` and `+` are used if they are enabled, open ranges are
+    /// rendered as `{n,}` and a range of exactly one repetition is rendered as the empty string.
+    /// Syntax highlighting is applied if it is enabled.
+    ///
+    /// In contrast, the `Display` implementation renders the quantifier independently
+    /// of any settings, which is used for inspecting the syntax tree.
+    pub(crate) fn render(&self, config: &RegExpConfig) -> String {
+        let (min, max) = self.bounds();
+        let is_shorthand_enabled = config.is_quantifier_shorthand_enabled;
+        let strings = match (min, max) {
+            (1, 1) => vec![],
+            (0, 1) if is_shorthand_enabled => vec![ColorizableString::QuestionMark],
+            (0, UNBOUNDED_REPETITIONS) if is_shorthand_enabled => {
+                vec![ColorizableString::Asterisk]
+            }
+            (1, UNBOUNDED_REPETITIONS) if is_shorthand_enabled => vec![ColorizableString::Plus],
+            (_, UNBOUNDED_REPETITIONS) => vec![
+                ColorizableString::LeftBrace,
+                ColorizableString::Number(min),
+                ColorizableString::Comma,
+                ColorizableString::RightBrace,
+            ],
+            _ if min == max => vec![
+                ColorizableString::LeftBrace,
+                ColorizableString::Number(min),
+                ColorizableString::RightBrace,
+            ],
+            _ => vec![
+                ColorizableString::LeftBrace,
+                ColorizableString::Number(min),
+                ColorizableString::Comma,
+                ColorizableString::Number(max),
+                ColorizableString::RightBrace,
+            ],
+        };
+
+        strings
+            .iter()
+            .map(|it| it.to_colorized_string(config).to_string())
+            .join("")
+    }
 }
 
 impl Display for Quantifier {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
-        write!(
-            f,
-            "{}",
-            match self {
-                Quantifier::KleeneStar => '*',
-                Quantifier::QuestionMark => '?',
-            }
-        )
+        match self {
+            Quantifier::KleeneStar => write!(f, "*"),
+            Quantifier::QuestionMark => write!(f, "?"),
+            Quantifier::Range(min, max) if min == max => write!(f, "{{{}}}", min),
+            Quantifier::Range(min, max) => write!(f, "{{{},{}}}", min, max),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ensure_correct_rendering_of_all_bounds() {
+        let mut config = RegExpConfig::new();
+        let quantifiers = [
+            (Quantifier::Range(1, 1), "", ""),
+            (Quantifier::QuestionMark, "?", "{0,1}"),
+            (Quantifier::Range(0, 1), "?", "{0,1}"),
+            (Quantifier::KleeneStar, "*", "{0,}"),
+            (Quantifier::Range(0, UNBOUNDED_REPETITIONS), "*", "{0,}"),
+            (Quantifier::Range(1, UNBOUNDED_REPETITIONS), "+", "{1,}"),
+            (Quantifier::Range(3, UNBOUNDED_REPETITIONS), "{3,}", "{3,}"),
+            (Quantifier::Range(0, 0), "{0}", "{0}"),
+            (Quantifier::Range(3, 3), "{3}", "{3}"),
+            (Quantifier::Range(0, 5), "{0,5}", "{0,5}"),
+            (Quantifier::Range(2, 5), "{2,5}", "{2,5}"),
+        ];
+
+        for (quantifier, expected_shorthand, expected_explicit) in quantifiers.iter() {
+            config.is_quantifier_shorthand_enabled = true;
+            assert_eq!(&quantifier.render(&config), expected_shorthand);
+            config.is_quantifier_shorthand_enabled = false;
+            assert_eq!(&quantifier.render(&config), expected_explicit);
+        }
+    }
+
+    #[test]
+    fn ensure_quantifiers_are_created_from_bounds() {
+        assert_eq!(Quantifier::from_bounds(0, 1), Quantifier::QuestionMark);
+        assert_eq!(
+            Quantifier::from_bounds(0, UNBOUNDED_REPETITIONS),
+            Quantifier::KleeneStar
+        );
+        assert_eq!(Quantifier::from_bounds(2, 5), Quantifier::Range(2, 5));
+        assert_eq!(
+            Quantifier::from_bounds(2, 5).add(&Quantifier::KleeneStar),
+            Quantifier::Range(2, UNBOUNDED_REPETITIONS)
+        );
     }
 }
@@ -14,13 +14,14 @@
  * limitations under the License.
  */
 
-use crate::char::Grapheme;
+use crate::char::{Grapheme, UNBOUNDED_REPETITIONS};
 use crate::regexp::RegExpConfig;
-use crate::unicode_tables::{DECIMAL_NUMBER, WHITE_SPACE, WORD};
+use crate::unicode_tables::{DECIMAL_NUMBER, EXTENDED_PICTOGRAPHIC, WHITE_SPACE, WORD};
 use itertools::Itertools;
 use std::cmp::Ordering;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::ops::Range;
+use std::sync::Arc;
 use unic_char_range::CharRange;
 use unic_ucd_category::GeneralCategory;
 use unicode_segmentation::UnicodeSegmentation;
@@ -28,19 +29,31 @@ use unicode_segmentation::UnicodeSegmentation;
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct GraphemeCluster {
     graphemes: Vec<Grapheme>,
-    config: RegExpConfig,
+    config: Arc<RegExpConfig>,
 }
 
 impl GraphemeCluster {
-    pub(crate) fn from(s: &str, config: &RegExpConfig) -> Self {
+    pub(crate) fn from(s: &str, config: &Arc<RegExpConfig>) -> Self {
+        if config.is_byte_mode {
+            return Self {
+                graphemes: s
+                    .chars()
+                    .map(|c| Grapheme::from(&c.to_string(), config))
+                    .collect_vec(),
+                config: config.clone(),
+            };
+        }
         Self {
             graphemes: UnicodeSegmentation::graphemes(s, true)
                 .flat_map(|it| {
                     let starts_with_backslash = it.chars().count() == 2 && it.starts_with('\\');
                     let contains_combining_mark =
                         it.chars().any(|c| GeneralCategory::of(c).is_mark());
+                    let is_emoji_zwj_sequence =
+                        it.contains('\u{200d}') && it.chars().any(is_extended_pictographic);
 
-                    if starts_with_backslash || contains_combining_mark {
+                    if starts_with_backslash || (contains_combining_mark && !is_emoji_zwj_sequence)
+                    {
                         it.chars()
                             .map(|c| Grapheme::from(&c.to_string(), config))
                             .collect_vec()
@@ -53,14 +66,14 @@ impl GraphemeCluster {
         }
     }
 
-    pub(crate) fn from_graphemes(graphemes: Vec<Grapheme>, config: &RegExpConfig) -> Self {
+    pub(crate) fn from_graphemes(graphemes: Vec<Grapheme>, config: &Arc<RegExpConfig>) -> Self {
         Self {
             graphemes,
             config: config.clone(),
         }
     }
 
-    pub(crate) fn new(grapheme: Grapheme, config: &RegExpConfig) -> Self {
+    pub(crate) fn new(grapheme: Grapheme, config: &Arc<RegExpConfig>) -> Self {
         Self {
             graphemes: vec![grapheme],
             config: config.clone(),
@@ -74,6 +87,7 @@ impl GraphemeCluster {
         let is_non_space_converted = self.config.is_non_space_converted();
         let is_word_converted = self.config.is_word_converted();
         let is_non_word_converted = self.config.is_non_word_converted();
+        let is_byte_mode = self.config.is_byte_mode;
 
         let valid_numeric_chars = convert_chars_to_range(DECIMAL_NUMBER);
         let valid_alphanumeric_chars = convert_chars_to_range(WORD);
@@ -86,12 +100,21 @@ impl GraphemeCluster {
                 .map(|it| {
                     it.chars()
                         .map(|c| {
-                            let is_digit =
-                                valid_numeric_chars.iter().any(|range| range.contains(c));
-                            let is_word = valid_alphanumeric_chars
-                                .iter()
-                                .any(|range| range.contains(c));
-                            let is_space = valid_space_chars.iter().any(|range| range.contains(c));
+                            let (is_digit, is_word, is_space) = if is_byte_mode {
+                                (
+                                    c.is_ascii_digit(),
+                                    c.is_ascii_alphanumeric() || c == '_',
+                                    matches!(c, '\t' | '\n' | '\u{b}' | '\u{c}' | '\r' | ' '),
+                                )
+                            } else {
+                                (
+                                    valid_numeric_chars.iter().any(|range| range.contains(c)),
+                                    valid_alphanumeric_chars
+                                        .iter()
+                                        .any(|range| range.contains(c)),
+                                    valid_space_chars.iter().any(|range| range.contains(c)),
+                                )
+                            };
 
                             if is_digit_converted && is_digit {
                                 "\\d".to_string()
@@ -115,10 +138,34 @@ impl GraphemeCluster {
         }
     }
 
+    pub(crate) fn make_noise_chars_optional(&mut self) {
+        let noise_chars = &self.config.noise_chars;
+        for grapheme in self.graphemes.iter_mut() {
+            let is_noise_char = match grapheme.chars.as_slice() {
+                [value] => {
+                    let mut chars = value.chars();
+                    match (chars.next(), chars.next()) {
+                        (Some(c), None) => noise_chars.contains(&c),
+                        _ => false,
+                    }
+                }
+                _ => false,
+            };
+            if is_noise_char
+                && !grapheme.has_repetitions()
+                && grapheme.minimum() == 1
+                && grapheme.maximum() == 1
+            {
+                grapheme.make_optional();
+            }
+        }
+    }
+
     pub(crate) fn convert_repetitions(&mut self) {
         let mut repetitions = vec![];
-        convert_repetitions(self.graphemes(), repetitions.as_mut(), &self.config);
-        if !repetitions.is_empty() {
+        let is_converted =
+            convert_repetitions(self.graphemes(), repetitions.as_mut(), &self.config).is_ok();
+        if is_converted && !repetitions.is_empty() {
             self.graphemes = repetitions;
         }
     }
@@ -126,7 +173,7 @@ impl GraphemeCluster {
     pub(crate) fn merge(
         first: &GraphemeCluster,
         second: &GraphemeCluster,
-        config: &RegExpConfig,
+        config: &Arc<RegExpConfig>,
     ) -> Self {
         let mut graphemes = vec![];
         graphemes.extend_from_slice(&first.graphemes);
@@ -161,24 +208,60 @@ impl GraphemeCluster {
     }
 }
 
+/// Returns the range of the first repetition which would split an extended
+/// grapheme cluster, in which case `repetitions` must be discarded.
 fn convert_repetitions(
     graphemes: &[Grapheme],
     repetitions: &mut Vec<Grapheme>,
-    config: &RegExpConfig,
-) {
-    let repeated_substrings = collect_repeated_substrings(graphemes);
+    config: &Arc<RegExpConfig>,
+) -> Result<(), Range<usize>> {
+    let boundaries = find_cluster_boundaries(graphemes, config);
+    let repeated_substrings = collect_repeated_substrings(graphemes, &boundaries);
     let ranges_of_repetitions = create_ranges_of_repetitions(repeated_substrings);
     let coalesced_repetitions = coalesce_repetitions(ranges_of_repetitions);
-    replace_graphemes_with_repetitions(coalesced_repetitions, graphemes, repetitions, config)
+    replace_graphemes_with_repetitions(
+        coalesced_repetitions,
+        graphemes,
+        &boundaries,
+        repetitions,
+        config,
+    )
 }
 
-fn collect_repeated_substrings(graphemes: &[Grapheme]) -> HashMap<Vec<String>, Vec<usize>> {
+/// Returns for each index into `graphemes`, including the end index, whether it lies on
+/// a boundary of an extended grapheme cluster. Combining marks are stored as separate
+/// graphemes, so not every index does. In byte mode, every index is a boundary.
+fn find_cluster_boundaries(graphemes: &[Grapheme], config: &RegExpConfig) -> Vec<bool> {
+    if config.is_byte_mode {
+        return vec![true; graphemes.len() + 1];
+    }
+    let joined = graphemes.iter().map(|it| it.value()).join("");
+    let byte_offsets = UnicodeSegmentation::grapheme_indices(joined.as_str(), true)
+        .map(|(offset, _)| offset)
+        .chain(std::iter::once(joined.len()))
+        .collect::<HashSet<_>>();
+    let mut offset = 0;
+    let mut boundaries = vec![true];
+    for grapheme in graphemes {
+        offset += grapheme.value().len();
+        boundaries.push(byte_offsets.contains(&offset));
+    }
+    boundaries
+}
+
+fn collect_repeated_substrings(
+    graphemes: &[Grapheme],
+    boundaries: &[bool],
+) -> HashMap<Vec<String>, Vec<usize>> {
     let mut map = HashMap::new();
 
     for i in 0..graphemes.len() {
+        if !boundaries[i] {
+            continue;
+        }
         let suffix = &graphemes[i..];
         for j in 1..=graphemes.len() / 2 {
-            if suffix.len() >= j {
+            if suffix.len() >= j && boundaries[i + j] {
                 let prefix = suffix[..j].iter().map(|it| it.value()).collect_vec();
                 let indices = map.entry(prefix).or_insert_with(Vec::new);
                 indices.push(i);
@@ -263,11 +346,12 @@ fn coalesce_repetitions(
 fn replace_graphemes_with_repetitions(
     coalesced_repetitions: Vec<(Range<usize>, Vec<String>)>,
     graphemes: &[Grapheme],
+    boundaries: &[bool],
     repetitions: &mut Vec<Grapheme>,
-    config: &RegExpConfig,
-) {
+    config: &Arc<RegExpConfig>,
+) -> Result<(), Range<usize>> {
     if coalesced_repetitions.is_empty() {
-        return;
+        return Ok(());
     }
 
     for grapheme in graphemes {
@@ -297,11 +381,30 @@ fn replace_graphemes_with_repetitions(
             break;
         }
 
+        let is_aligned = boundaries[range.start] && boundaries[range.end];
+        debug_assert!(
+            is_aligned,
+            "repetition {:?} splits a grapheme cluster",
+            range
+        );
+        if !is_aligned {
+            return Err(range.clone());
+        }
+
         repetitions.splice(
             range.clone(),
-            [Grapheme::new(substr.clone(), count, count, config)]
-                .iter()
-                .cloned(),
+            [Grapheme::new(
+                substr.clone(),
+                count,
+                if config.is_repetition_unbounded {
+                    UNBOUNDED_REPETITIONS
+                } else {
+                    count
+                },
+                config,
+            )]
+            .iter()
+            .cloned(),
         );
     }
 
@@ -314,8 +417,25 @@ fn replace_graphemes_with_repetitions(
                 .collect_vec(),
             new_grapheme.repetitions.as_mut(),
             config,
-        );
+        )?;
     }
+    Ok(())
+}
+
+/// Returns `true` if the character is an emoji or another pictograph
+/// which can be joined with others by a zero width joiner.
+fn is_extended_pictographic(c: char) -> bool {
+    EXTENDED_PICTOGRAPHIC
+        .binary_search_by(|&(start, end)| {
+            if end < c {
+                Ordering::Less
+            } else if start > c {
+                Ordering::Greater
+            } else {
+                Ordering::Equal
+            }
+        })
+        .is_ok()
 }
 
 fn convert_chars_to_range(chars: &[(char, char)]) -> Vec<CharRange> {
@@ -324,3 +444,64 @@ fn convert_chars_to_range(chars: &[(char, char)]) -> Vec<CharRange> {
         .map(|&(start, end)| CharRange::closed(start, end))
         .collect_vec()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ensure_emoji_zwj_sequence_is_single_grapheme() {
+        let config = Arc::new(RegExpConfig::new());
+        for emoji in &["👨\u{200d}👩\u{200d}👧", "👨\u{200d}❤\u{fe0f}\u{200d}👨"] {
+            let cluster = GraphemeCluster::from(emoji, &config);
+            assert_eq!(cluster.size(), 1);
+
+            let grapheme = cluster.graphemes().first().unwrap();
+            assert_eq!(grapheme.value(), *emoji);
+            assert_eq!(grapheme.char_count(false), emoji.chars().count());
+            assert!(grapheme.logical_char_count() > 1);
+        }
+    }
+
+    #[test]
+    fn ensure_zwj_without_emoji_does_not_join_combining_marks() {
+        let config = Arc::new(RegExpConfig::new());
+        let cluster = GraphemeCluster::from("e\u{301}\u{200d}", &config);
+        assert_eq!(cluster.size(), 3);
+    }
+
+    #[test]
+    fn ensure_emoji_modifier_sequence_is_single_grapheme() {
+        let config = Arc::new(RegExpConfig::new());
+        let cluster = GraphemeCluster::from("👍🏽", &config);
+        assert_eq!(cluster.size(), 1);
+        assert_eq!(cluster.graphemes().first().unwrap().value(), "👍🏽");
+    }
+
+    #[test]
+    fn ensure_combining_marks_are_separate_graphemes() {
+        let config = Arc::new(RegExpConfig::new());
+        let cluster = GraphemeCluster::from("e\u{301}", &config);
+        assert_eq!(cluster.size(), 2);
+    }
+
+    #[test]
+    fn ensure_repetitions_align_to_cluster_boundaries() {
+        let config = Arc::new(RegExpConfig::new());
+        let cluster = GraphemeCluster::from("e\u{301}\u{301}e\u{301}\u{301}e", &config);
+        assert_eq!(
+            find_cluster_boundaries(cluster.graphemes(), &config),
+            vec![true, false, false, true, false, false, true, true]
+        );
+
+        let mut cluster = cluster;
+        cluster.convert_repetitions();
+        let values = cluster
+            .graphemes()
+            .iter()
+            .map(|it| it.value())
+            .collect_vec();
+        assert_eq!(values, vec!["e\u{301}\u{301}", "e"]);
+        assert_eq!(cluster.graphemes()[0].maximum(), 2);
+    }
+}
@@ -14,33 +14,85 @@
  * limitations under the License.
  */
 
-use colored::{ColoredString, Colorize};
+#[cfg(feature = "coloring")]
+use crate::char::theme::nearest_ansi_color;
+use crate::regexp::RegExpConfig;
+#[cfg(feature = "coloring")]
+use colored::{Color, Colorize};
+use std::cell::Cell;
 use std::fmt::{Display, Formatter, Result};
 
+/// The string type produced by syntax highlighting.
+/// Without the `coloring` feature, this is a plain `String`.
+#[cfg(feature = "coloring")]
+pub(crate) type ColoredString = colored::ColoredString;
+#[cfg(not(feature = "coloring"))]
+pub(crate) type ColoredString = String;
+
+thread_local! {
+    static IS_COLORIZATION_SUPPRESSED: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Runs the given closure with syntax highlighting turned off on the current thread,
+/// regardless of the settings in `RegExpConfig`.
+pub fn without_colorization<T>(f: impl FnOnce() -> T) -> T {
+    let _guard = ColorizationSuppression::new();
+    f()
+}
+
+/// Suppresses syntax highlighting on the current thread until it is dropped,
+/// so that the previous state is restored even if the closure panics.
+struct ColorizationSuppression {
+    was_suppressed: bool,
+}
+
+impl ColorizationSuppression {
+    fn new() -> Self {
+        let was_suppressed = IS_COLORIZATION_SUPPRESSED.with(|it| it.replace(true));
+        Self { was_suppressed }
+    }
+}
+
+impl Drop for ColorizationSuppression {
+    fn drop(&mut self) {
+        IS_COLORIZATION_SUPPRESSED.with(|it| it.set(self.was_suppressed));
+    }
+}
+
 pub enum ColorizableString {
     Asterisk,
+    AtomicLeftParenthesis,
     CapturingLeftParenthesis,
     Caret,
     Comma,
     DigitCharClass,
     DollarSign,
+    Dot,
+    DotAllFlag,
     EmptyString,
-    Hyphen,
+    HexDigitCharClass,
     IgnoreCaseFlag,
+    InputEndAnchor,
+    InputStartAnchor,
+    MultiLineFlag,
     LeftBrace,
     LeftBracket,
     NonCapturingLeftParenthesis,
     NonDigitCharClass,
+    NonHexDigitCharClass,
     NonSpaceCharClass,
     NonWordCharClass,
     Number(u32),
     Other(String),
     Pipe,
+    Plus,
     QuestionMark,
+    RangeHyphen,
     RightBrace,
     RightBracket,
     RightParenthesis,
     SpaceCharClass,
+    UnicodeDisabledFlag,
     WordCharClass,
 }
 
@@ -57,40 +109,74 @@ impl ColorizableString {
         }
     }
 
-    pub fn to_colorized_string(&self, is_output_colorized: bool) -> ColoredString {
+    /// Returns the plain string representation
+    /// as syntax highlighting is not available without the `coloring` feature.
+    #[cfg(not(feature = "coloring"))]
+    pub fn to_colorized_string(&self, _config: &RegExpConfig) -> ColoredString {
+        self.to_string()
+    }
+
+    #[cfg(feature = "coloring")]
+    pub fn to_colorized_string(&self, config: &RegExpConfig) -> ColoredString {
         let string_repr = self.to_string();
         let repr = string_repr.as_str();
 
-        if !is_output_colorized {
+        if !config.is_output_colorized || IS_COLORIZATION_SUPPRESSED.with(Cell::get) {
             return repr.clear();
         }
 
+        let theme = &config.color_theme;
+        let color = |color: Color| {
+            if config.is_truecolor_disabled {
+                nearest_ansi_color(color)
+            } else {
+                color
+            }
+        };
+
         match self {
-            ColorizableString::IgnoreCaseFlag => repr.bright_yellow().on_black(),
-            ColorizableString::Pipe => repr.red().bold(),
-            ColorizableString::Asterisk | ColorizableString::QuestionMark => repr.purple().bold(),
-            ColorizableString::Caret | ColorizableString::DollarSign => repr.yellow().bold(),
+            ColorizableString::IgnoreCaseFlag
+            | ColorizableString::UnicodeDisabledFlag
+            | ColorizableString::MultiLineFlag
+            | ColorizableString::DotAllFlag => repr.color(color(theme.flags)).on_black(),
+            ColorizableString::Pipe => repr.color(color(theme.pipe)).bold(),
+            ColorizableString::RangeHyphen => repr.color(color(theme.range_hyphen)).bold(),
+            ColorizableString::Number(_) => repr.color(color(theme.numbers)).on_bright_blue(),
             ColorizableString::EmptyString | ColorizableString::Other(_) => repr.clear(),
 
+            ColorizableString::Asterisk
+            | ColorizableString::Plus
+            | ColorizableString::QuestionMark => repr.color(color(theme.quantifiers)).bold(),
+
+            ColorizableString::Caret
+            | ColorizableString::DollarSign
+            | ColorizableString::InputStartAnchor
+            | ColorizableString::InputEndAnchor => repr.color(color(theme.anchors)).bold(),
+
             ColorizableString::NonCapturingLeftParenthesis
             | ColorizableString::CapturingLeftParenthesis
-            | ColorizableString::RightParenthesis => repr.green().bold(),
+            | ColorizableString::AtomicLeftParenthesis
+            | ColorizableString::RightParenthesis => repr.color(color(theme.parentheses)).bold(),
 
-            ColorizableString::Number(_)
-            | ColorizableString::LeftBrace
+            ColorizableString::LeftBrace
             | ColorizableString::RightBrace
-            | ColorizableString::Comma => repr.white().on_bright_blue(),
+            | ColorizableString::Comma => repr.color(color(theme.braces)).on_bright_blue(),
 
-            ColorizableString::LeftBracket
-            | ColorizableString::RightBracket
-            | ColorizableString::Hyphen => repr.cyan().bold(),
+            ColorizableString::LeftBracket | ColorizableString::RightBracket => {
+                repr.color(color(theme.brackets)).bold()
+            }
 
             ColorizableString::DigitCharClass
+            | ColorizableString::Dot
+            | ColorizableString::HexDigitCharClass
             | ColorizableString::SpaceCharClass
             | ColorizableString::WordCharClass
             | ColorizableString::NonDigitCharClass
+            | ColorizableString::NonHexDigitCharClass
             | ColorizableString::NonSpaceCharClass
-            | ColorizableString::NonWordCharClass => repr.black().on_bright_yellow(),
+            | ColorizableString::NonWordCharClass => {
+                repr.color(color(theme.char_classes)).on_bright_yellow()
+            }
         }
     }
 }
@@ -102,24 +188,34 @@ impl Display for ColorizableString {
             "{}",
             match self {
                 ColorizableString::Asterisk => "*".to_string(),
+                ColorizableString::AtomicLeftParenthesis => "(?>".to_string(),
                 ColorizableString::CapturingLeftParenthesis => "(".to_string(),
                 ColorizableString::Caret => "^".to_string(),
                 ColorizableString::Comma => ",".to_string(),
                 ColorizableString::DigitCharClass => "\\d".to_string(),
                 ColorizableString::DollarSign => "$".to_string(),
-                ColorizableString::Hyphen => "-".to_string(),
+                ColorizableString::Dot => ".".to_string(),
+                ColorizableString::DotAllFlag => "(?s)".to_string(),
                 ColorizableString::IgnoreCaseFlag => "(?i)".to_string(),
+                ColorizableString::InputEndAnchor => "\\z".to_string(),
+                ColorizableString::InputStartAnchor => "\\A".to_string(),
+                ColorizableString::MultiLineFlag => "(?m)".to_string(),
+                ColorizableString::UnicodeDisabledFlag => "(?-u)".to_string(),
                 ColorizableString::LeftBrace => "{".to_string(),
                 ColorizableString::LeftBracket => "[".to_string(),
                 ColorizableString::EmptyString => "".to_string(),
+                ColorizableString::HexDigitCharClass => "\\h".to_string(),
                 ColorizableString::NonCapturingLeftParenthesis => "(?:".to_string(),
                 ColorizableString::NonDigitCharClass => "\\D".to_string(),
+                ColorizableString::NonHexDigitCharClass => "\\H".to_string(),
                 ColorizableString::NonSpaceCharClass => "\\S".to_string(),
                 ColorizableString::NonWordCharClass => "\\W".to_string(),
                 ColorizableString::Number(number) => number.to_string(),
                 ColorizableString::Other(value) => value.to_string(),
                 ColorizableString::Pipe => "|".to_string(),
+                ColorizableString::Plus => "+".to_string(),
                 ColorizableString::QuestionMark => "?".to_string(),
+                ColorizableString::RangeHyphen => "-".to_string(),
                 ColorizableString::RightBrace => "}".to_string(),
                 ColorizableString::RightBracket => "]".to_string(),
                 ColorizableString::RightParenthesis => ")".to_string(),
@@ -129,3 +225,42 @@ impl Display for ColorizableString {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "coloring")]
+    use crate::char::ColorTheme;
+    use std::panic::catch_unwind;
+
+    #[test]
+    fn ensure_colorization_is_restored_after_panic() {
+        let result = catch_unwind(|| without_colorization(|| panic!("closure panicked")));
+        assert!(result.is_err());
+        assert!(!IS_COLORIZATION_SUPPRESSED.with(Cell::get));
+    }
+
+    #[test]
+    #[cfg(feature = "coloring")]
+    fn ensure_truecolor_is_replaced_only_if_disabled() {
+        let truecolor = Color::TrueColor {
+            r: 255,
+            g: 100,
+            b: 0,
+        };
+        let mut config = RegExpConfig::new();
+        config.is_output_colorized = true;
+        config.color_theme = ColorTheme::new().with_bracket_color(truecolor).clone();
+
+        assert_eq!(
+            ColorizableString::LeftBracket.to_colorized_string(&config),
+            "[".color(truecolor).bold()
+        );
+
+        config.is_truecolor_disabled = true;
+        assert_eq!(
+            ColorizableString::LeftBracket.to_colorized_string(&config),
+            "[".color(Color::BrightRed).bold()
+        );
+    }
+}
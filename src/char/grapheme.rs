@@ -14,27 +14,72 @@
  * limitations under the License.
  */
 
+use crate::ast::Quantifier;
 use crate::char::ColorizableString;
-use crate::regexp::RegExpConfig;
-use colored::ColoredString;
+use crate::regexp::{EscapeStyle, RegExpConfig, RegExpFlavor};
+use crate::unicode_tables::{DECIMAL_NUMBER, WHITE_SPACE, WORD};
 use itertools::Itertools;
 use std::fmt::{Display, Formatter, Result};
+use std::ops::RangeInclusive;
+use std::str::Chars;
+use std::sync::Arc;
 
 const CHARS_TO_ESCAPE: [&str; 14] = [
     "(", ")", "[", "]", "{", "}", "+", "*", "-", ".", "?", "|", "^", "$",
 ];
 
+pub(crate) const UNICODE_SCALAR_VALUE_COUNT: u128 = 0x110000 - 0x800;
+
+pub(crate) const UNBOUNDED_REPETITIONS: u32 = u32::MAX;
+
+/// Returns the number of characters in the given range. The surrogate code points
+/// `U+D800` to `U+DFFF` are not characters, so they are not counted.
+pub(crate) fn char_range_len(range: &RangeInclusive<char>) -> usize {
+    let (start, end) = (*range.start() as usize, *range.end() as usize);
+    if start > end {
+        0
+    } else if start < 0xd800 && end > 0xdfff {
+        end - start + 1 - 0x800
+    } else {
+        end - start + 1
+    }
+}
+
+/// Describes how the repetitions of two graphemes relate to each other.
+/// It is returned by method [`Grapheme::overlap_with`](./struct.Grapheme.html#method.overlap_with).
+#[derive(Clone, Copy, Debug, Hash, Ord, PartialOrd, Eq, PartialEq)]
+pub enum GraphemeOverlapState {
+    /// Both graphemes have the same value and the same maximum count, and the minimum count
+    /// of the other grapheme is not smaller, e.g. `a{2,3}` covers `a{3}`. The other grapheme
+    /// is matched by this one as it is.
+    Covered,
+
+    /// Both graphemes have the same value and can be merged into one grapheme which is repeated
+    /// from the smaller minimum count to the larger maximum count. This is the case if they
+    /// have the same maximum count, e.g. `a{2,3}` and `a{1,3}`, or if the other grapheme
+    /// starts after this one ends with no more missing counts in between than allowed by
+    /// [`RegExpBuilder.with_repetition_merge_gap_tolerance`](../struct.RegExpBuilder.html#method.with_repetition_merge_gap_tolerance),
+    /// e.g. `a{1,2}` and `a{3,4}` without any tolerance.
+    Mergeable,
+
+    /// The graphemes have different values or their repetitions can neither be covered nor merged,
+    /// e.g. `a` and `b` or `a{1,2}` and `a{5}` without any tolerance.
+    Disjoint,
+}
+
+/// A grapheme of a test case together with the number of times it is repeated,
+/// such as `a` or `a{2,3}`. This is the unit in which repetitions are merged.
 #[derive(Clone, Debug, Hash, Ord, PartialOrd, Eq, PartialEq)]
 pub struct Grapheme {
     pub(crate) chars: Vec<String>,
     pub(crate) repetitions: Vec<Grapheme>,
     min: u32,
     max: u32,
-    config: RegExpConfig,
+    config: Arc<RegExpConfig>,
 }
 
 impl Grapheme {
-    pub(crate) fn from(s: &str, config: &RegExpConfig) -> Self {
+    pub(crate) fn from(s: &str, config: &Arc<RegExpConfig>) -> Self {
         Self {
             chars: vec![s.to_string()],
             repetitions: vec![],
@@ -44,7 +89,7 @@ impl Grapheme {
         }
     }
 
-    pub(crate) fn new(chars: Vec<String>, min: u32, max: u32, config: &RegExpConfig) -> Self {
+    pub(crate) fn new(chars: Vec<String>, min: u32, max: u32, config: &Arc<RegExpConfig>) -> Self {
         Self {
             chars,
             repetitions: vec![],
@@ -54,7 +99,19 @@ impl Grapheme {
         }
     }
 
-    pub(crate) fn value(&self) -> String {
+    /// Creates a grapheme of the given value which is repeated
+    /// at least `min` and at most `max` times.
+    ///
+    /// ⚠ Panics if `min` is greater than `max`.
+    pub fn repeated(value: &str, min: u32, max: u32, config: &Arc<RegExpConfig>) -> Self {
+        if min > max {
+            panic!("The minimum count of a repetition must not be greater than its maximum count");
+        }
+        Self::new(vec![value.to_string()], min, max, config)
+    }
+
+    /// Returns the string which is repeated, without any quantifier.
+    pub fn value(&self) -> String {
         self.chars.join("")
     }
 
@@ -74,14 +131,44 @@ impl Grapheme {
         &mut self.repetitions
     }
 
-    pub(crate) fn minimum(&self) -> u32 {
+    pub(crate) fn make_optional(&mut self) {
+        self.min = 0;
+    }
+
+    pub(crate) fn is_unbounded(&self) -> bool {
+        self.max == UNBOUNDED_REPETITIONS
+    }
+
+    /// Returns the minimum number of times this grapheme is repeated.
+    pub fn minimum(&self) -> u32 {
         self.min
     }
 
-    pub(crate) fn maximum(&self) -> u32 {
+    /// Returns the maximum number of times this grapheme is repeated,
+    /// which is `u32::MAX` for unbounded repetitions.
+    pub fn maximum(&self) -> u32 {
         self.max
     }
 
+    /// Returns how the repetitions of the other grapheme relate to the ones of this grapheme.
+    /// The gap tolerance is taken from the settings of this grapheme. See
+    /// [`GraphemeOverlapState`](./enum.GraphemeOverlapState.html) for the possible states.
+    pub fn overlap_with(&self, other: &Grapheme) -> GraphemeOverlapState {
+        if self.value() != other.value() {
+            return GraphemeOverlapState::Disjoint;
+        }
+        let is_within_gap_tolerance = other.min > self.max
+            && other.min - self.max - 1 <= self.config.repetition_merge_gap_tolerance;
+
+        if self.max == other.max && other.min >= self.min {
+            GraphemeOverlapState::Covered
+        } else if self.max == other.max || is_within_gap_tolerance {
+            GraphemeOverlapState::Mergeable
+        } else {
+            GraphemeOverlapState::Disjoint
+        }
+    }
+
     pub(crate) fn char_count(&self, is_non_ascii_char_escaped: bool) -> usize {
         if is_non_ascii_char_escaped {
             self.chars
@@ -95,6 +182,30 @@ impl Grapheme {
         }
     }
 
+    pub(crate) fn logical_char_count(&self) -> usize {
+        self.chars.iter().map(|it| count_logical_chars(it)).sum()
+    }
+
+    pub(crate) fn match_count(&self) -> Option<u128> {
+        let unit_count = if self.repetitions.is_empty() {
+            self.chars.iter().try_fold(1u128, |count, it| {
+                count.checked_mul(count_chars_matched_by(it)?)
+            })
+        } else {
+            self.repetitions
+                .iter()
+                .try_fold(1u128, |count, it| count.checked_mul(it.match_count()?))
+        }?;
+
+        if self.is_unbounded() {
+            return None;
+        }
+
+        (self.min..=self.max).try_fold(0u128, |count, exponent| {
+            count.checked_add(unit_count.checked_pow(exponent)?)
+        })
+    }
+
     pub(crate) fn escape_non_ascii_chars(&mut self, use_surrogate_pairs: bool) {
         self.chars = self
             .chars
@@ -112,150 +223,545 @@ impl Grapheme {
         is_non_ascii_char_escaped: bool,
         is_astral_code_point_converted_to_surrogate: bool,
     ) {
-        let characters = self.chars_mut();
+        let config = self.config.clone();
 
-        #[allow(clippy::needless_range_loop)]
-        for i in 0..characters.len() {
-            let mut character = characters[i].clone();
+        for character in self.chars_mut().iter_mut() {
+            *character = if config.custom_escapes.is_empty() || is_shorthand(character) {
+                escape_symbols(character, &config)
+            } else {
+                character
+                    .chars()
+                    .map(|c| match config.custom_escapes.get(&c) {
+                        Some(sequence) => sequence.clone(),
+                        None => escape_symbols(&c.to_string(), &config),
+                    })
+                    .join("")
+            };
+        }
 
-            for char_to_escape in CHARS_TO_ESCAPE.iter() {
-                character =
-                    character.replace(char_to_escape, &format!("{}{}", "\\", char_to_escape));
-            }
+        if self.config.is_byte_mode {
+            self.escape_bytes();
+        } else if self.config.flavor == RegExpFlavor::RustBytes {
+            self.escape_non_ascii_bytes();
+        } else if is_non_ascii_char_escaped {
+            self.escape_non_ascii_chars(is_astral_code_point_converted_to_surrogate);
+        } else if self.config.escape_style == EscapeStyle::ControlChar {
+            self.escape_control_chars();
+        }
+    }
 
-            character = character
-                .replace("\n", "\\n")
-                .replace("\r", "\\r")
-                .replace("\t", "\\t");
+    fn escape_control_chars(&mut self) {
+        let style = &self.config.escape_style;
+        self.chars = self
+            .chars
+            .iter()
+            .map(|it| {
+                it.chars()
+                    .map(|c| escape_control_char(c, style).unwrap_or_else(|| c.to_string()))
+                    .join("")
+            })
+            .collect_vec();
+    }
 
-            if character == "\\" {
-                character = "\\\\".to_string();
-            }
+    fn escape_bytes(&mut self) {
+        self.chars = self
+            .chars
+            .iter()
+            .map(|it| {
+                it.chars()
+                    .map(|c| {
+                        if c.is_ascii() && !c.is_ascii_control() {
+                            c.to_string()
+                        } else {
+                            format!("\\x{:02x}", c as u32)
+                        }
+                    })
+                    .join("")
+            })
+            .collect_vec();
+    }
 
-            characters[i] = character;
-        }
+    fn escape_non_ascii_bytes(&mut self) {
+        self.chars = self
+            .chars
+            .iter()
+            .map(|it| {
+                let mut escaped_value = String::new();
+                let mut non_ascii_bytes = String::new();
 
-        if is_non_ascii_char_escaped {
-            self.escape_non_ascii_chars(is_astral_code_point_converted_to_surrogate);
-        }
+                for c in it.chars() {
+                    if c.is_ascii() {
+                        push_byte_escape_group(&mut escaped_value, &mut non_ascii_bytes);
+                        escaped_value.push(c);
+                    } else {
+                        non_ascii_bytes.push_str(&escape_utf8_bytes(c));
+                    }
+                }
+                push_byte_escape_group(&mut escaped_value, &mut non_ascii_bytes);
+                escaped_value
+            })
+            .collect_vec();
     }
 
     fn escape(&self, c: char, use_surrogate_pairs: bool) -> String {
-        if c.is_ascii() {
+        if let Some(escaped_char) = escape_control_char(c, &self.config.escape_style) {
+            escaped_char
+        } else if c.is_ascii() {
             c.to_string()
-        } else if use_surrogate_pairs && ('\u{10000}'..'\u{10ffff}').contains(&c) {
+        } else if use_surrogate_pairs && ('\u{10000}'..='\u{10ffff}').contains(&c) {
             self.convert_to_surrogate_pair(c)
         } else {
-            c.escape_unicode().to_string()
+            escape_code_point(c as u32, &self.config.escape_style)
         }
     }
 
     fn convert_to_surrogate_pair(&self, c: char) -> String {
         c.encode_utf16(&mut [0; 2])
             .iter()
-            .map(|it| format!("\\u{{{:x}}}", it))
+            .map(|&it| escape_code_point(it as u32, &self.config.escape_style))
             .join("")
     }
 }
 
 impl Display for Grapheme {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
-        let is_single_char = self.char_count(false) == 1
-            || (self.chars.len() == 1 && self.chars[0].matches('\\').count() == 1);
-        let is_range = self.min < self.max;
-        let is_repetition = self.min > 1;
+        let is_single_char = self.logical_char_count() == 1;
         let value = if self.repetitions.is_empty() {
             self.value()
         } else {
             self.repetitions.iter().map(|it| it.to_string()).join("")
         };
+        let quantifier = Quantifier::from_bounds(self.min, self.max).render(&self.config);
 
-        let (
-            colored_value,
-            comma,
-            left_brace,
-            right_brace,
-            left_parenthesis,
-            right_parenthesis,
-            min,
-            max,
-        ) = to_colorized_string(
-            vec![
-                ColorizableString::from(&value),
-                ColorizableString::Comma,
-                ColorizableString::LeftBrace,
-                ColorizableString::RightBrace,
-                if self.config.is_capturing_group_enabled() {
-                    ColorizableString::CapturingLeftParenthesis
-                } else {
-                    ColorizableString::NonCapturingLeftParenthesis
-                },
-                ColorizableString::RightParenthesis,
-                ColorizableString::Number(self.min),
-                ColorizableString::Number(self.max),
-            ],
-            &self.config,
-        );
+        let (colored_value, left_parenthesis, right_parenthesis) = [
+            ColorizableString::from(&value),
+            if self.config.is_repetition_captured() {
+                ColorizableString::CapturingLeftParenthesis
+            } else if self.config.is_atomic_group_used() {
+                ColorizableString::AtomicLeftParenthesis
+            } else {
+                ColorizableString::NonCapturingLeftParenthesis
+            },
+            ColorizableString::RightParenthesis,
+        ]
+        .iter()
+        .map(|it| it.to_colorized_string(&self.config))
+        .collect_tuple()
+        .unwrap();
 
-        if !is_range && is_repetition && is_single_char {
-            write!(f, "{}{}{}{}", colored_value, left_brace, min, right_brace)
-        } else if !is_range && is_repetition && !is_single_char {
-            write!(
-                f,
-                "{}{}{}{}{}{}",
-                left_parenthesis, colored_value, right_parenthesis, left_brace, min, right_brace
-            )
-        } else if is_range && is_single_char {
-            write!(
-                f,
-                "{}{}{}{}{}{}",
-                colored_value, left_brace, min, comma, max, right_brace
-            )
-        } else if is_range && !is_single_char {
+        if self.config.is_repetition_expanded(self.min, self.max) {
+            write!(f, "{}", colored_value.to_string().repeat(self.min as usize))
+        } else if quantifier.is_empty() || is_single_char {
+            write!(f, "{}{}", colored_value, quantifier)
+        } else {
             write!(
                 f,
-                "{}{}{}{}{}{}{}{}",
-                left_parenthesis,
-                colored_value,
-                right_parenthesis,
-                left_brace,
-                min,
-                comma,
-                max,
-                right_brace
+                "{}{}{}{}",
+                left_parenthesis, colored_value, right_parenthesis, quantifier
             )
+        }
+    }
+}
+
+pub(crate) fn escape_utf8_bytes(c: char) -> String {
+    c.encode_utf8(&mut [0; 4])
+        .bytes()
+        .map(|it| format!("\\x{:02x}", it))
+        .join("")
+}
+
+/// Returns the number of capturing groups in the given uncolored regular expression.
+/// Character classes and literal runs quoted with `\Q...\E` cannot contain groups,
+/// so their parentheses are skipped.
+pub(crate) fn count_capturing_groups(regexp: &str) -> usize {
+    let mut count = 0;
+    let mut is_in_char_class = false;
+    let mut is_in_quoted_run = false;
+    let mut chars = regexp.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if is_in_quoted_run {
+            if c == '\\' && chars.peek() == Some(&'E') {
+                chars.next();
+                is_in_quoted_run = false;
+            }
+            continue;
+        }
+        match c {
+            '\\' => is_in_quoted_run = chars.next() == Some('Q'),
+            '[' => is_in_char_class = true,
+            ']' => is_in_char_class = false,
+            '(' if !is_in_char_class && chars.peek() != Some(&'?') => count += 1,
+            _ => {}
+        }
+    }
+
+    count
+}
+
+/// Returns the caret notation of the control characters `U+0001` to `U+001A`,
+/// e.g. `\cA` for `U+0001`, if the given style is [`EscapeStyle::ControlChar`].
+/// The characters `\t`, `\n` and `\r` are left to their conventional escape sequences.
+pub(crate) fn escape_control_char(c: char, style: &EscapeStyle) -> Option<String> {
+    if style == &EscapeStyle::ControlChar
+        && ('\u{1}'..='\u{1a}').contains(&c)
+        && !matches!(c, '\t' | '\n' | '\r')
+    {
+        Some(format!("\\c{}", (b'A' + c as u8 - 1) as char))
+    } else {
+        None
+    }
+}
+
+pub(crate) fn escape_code_point(code_point: u32, style: &EscapeStyle) -> String {
+    match style {
+        EscapeStyle::FourDigitU if code_point <= 0xffff => format!("\\u{:04x}", code_point),
+        EscapeStyle::HexByte if code_point <= 0xff => format!("\\x{:02x}", code_point),
+        EscapeStyle::HexByte => format!("\\x{{{:x}}}", code_point),
+        EscapeStyle::HexBraces => format!("\\x{{{:04x}}}", code_point),
+        _ => format!("\\u{{{:x}}}", code_point),
+    }
+}
+
+/// Escapes the metacharacters and the common control characters of the given grapheme.
+fn escape_symbols(value: &str, config: &RegExpConfig) -> String {
+    let mut character = if config.is_strict_escaping {
+        escape_non_alphanumeric_chars(value)
+    } else {
+        let mut character = value.to_string();
+        for char_to_escape in CHARS_TO_ESCAPE.iter() {
+            character = character.replace(char_to_escape, &format!("{}{}", "\\", char_to_escape));
+        }
+        character
+    };
+
+    character = character
+        .replace("\n", "\\n")
+        .replace("\r", "\\r")
+        .replace("\t", "\\t");
+
+    if config.literal_delimiter.is_some() || config.is_slash_escaped {
+        character = character
+            .chars()
+            .map(|c| {
+                if config.is_escaped_delimiter(c) && !is_escaped_symbol(c, config) {
+                    format!("\\{}", c)
+                } else {
+                    c.to_string()
+                }
+            })
+            .join("");
+    }
+
+    if character == "\\" {
+        character = "\\\\".to_string();
+    }
+    character
+}
+
+/// Returns `true` if the given character has already been escaped by [`escape_symbols`].
+fn is_escaped_symbol(c: char, config: &RegExpConfig) -> bool {
+    if config.is_strict_escaping {
+        is_strictly_escaped(c)
+    } else {
+        CHARS_TO_ESCAPE.contains(&c.to_string().as_str())
+    }
+}
+
+/// Returns `true` if the given grapheme is a shorthand character class
+/// such as `\d` created by the conversion features.
+fn is_shorthand(value: &str) -> bool {
+    matches!(value, "\\d" | "\\D" | "\\s" | "\\S" | "\\w" | "\\W")
+}
+
+/// Returns `true` if the given custom escape sequence denotes exactly the character `c`,
+/// so that it can replace the character in literals and character classes alike.
+pub(crate) fn is_escape_sequence_safe(c: char, sequence: &str) -> bool {
+    unescape_char(sequence) == Some(c)
+}
+
+/// Returns the single character denoted by the given escape sequence. Sequences which denote
+/// anything else, such as several characters, shorthand classes like `\d`, assertions like `\b`,
+/// backreferences or the quotes `\Q` and `\E`, result in `None`. Supported are plain characters
+/// other than metacharacters, escaped punctuation such as `\.`, the escapes `\t`, `\n`, `\r`,
+/// `\f`, `\v` and `\a`, caret notation such as `\cA` and hexadecimal escapes such as `\x09`,
+/// `\x{9}`, `\u0009` or `\u{9}`.
+fn unescape_char(sequence: &str) -> Option<char> {
+    let mut chars = sequence.chars();
+    let first_char = chars.next()?;
+
+    let c = if first_char != '\\' {
+        if CHARS_TO_ESCAPE.contains(&first_char.to_string().as_str()) {
+            return None;
+        }
+        first_char
+    } else {
+        match chars.next()? {
+            't' => '\t',
+            'n' => '\n',
+            'r' => '\r',
+            'f' => '\u{c}',
+            'v' => '\u{b}',
+            'a' => '\u{7}',
+            'c' => {
+                let letter = chars.next().filter(char::is_ascii_alphabetic)?;
+                (letter.to_ascii_uppercase() as u8 - b'A' + 1) as char
+            }
+            'x' => unescape_hex_digits(&mut chars, 2)?,
+            'u' => unescape_hex_digits(&mut chars, 4)?,
+            // `\<` and `\>` denote word boundaries in some flavors.
+            '<' | '>' => return None,
+            escaped_char if escaped_char == ' ' || escaped_char.is_ascii_punctuation() => {
+                escaped_char
+            }
+            _ => return None,
+        }
+    };
+
+    if chars.next().is_some() {
+        None
+    } else {
+        Some(c)
+    }
+}
+
+/// Parses the hexadecimal digits of an escape sequence, either enclosed in braces
+/// or exactly `digit_count` of them, and advances `chars` behind them.
+fn unescape_hex_digits(chars: &mut Chars<'_>, digit_count: usize) -> Option<char> {
+    let rest = chars.as_str();
+    let (digits, remainder) = match rest.strip_prefix('{') {
+        Some(braced_digits) => {
+            let end = braced_digits.find('}')?;
+            (&braced_digits[..end], &braced_digits[end + 1..])
+        }
+        None if rest.is_char_boundary(digit_count) => rest.split_at(digit_count),
+        None => return None,
+    };
+    if digits.is_empty() || !digits.chars().all(|it| it.is_ascii_hexdigit()) {
+        return None;
+    }
+    *chars = remainder.chars();
+    u32::from_str_radix(digits, 16)
+        .ok()
+        .and_then(std::char::from_u32)
+}
+
+/// Returns `true` if the character is escaped by strict escaping, which applies to all
+/// printable ascii characters except letters and digits. The angle brackets are left
+/// unescaped because `\<` and `\>` denote word boundaries in some flavors.
+pub(crate) fn is_strictly_escaped(c: char) -> bool {
+    (c == ' ' || c.is_ascii_punctuation()) && c != '<' && c != '>'
+}
+
+/// Escapes all characters for which [`is_strictly_escaped`] holds, leaving the shorthand
+/// character classes created by the conversion features intact.
+fn escape_non_alphanumeric_chars(value: &str) -> String {
+    let mut escaped_value = String::new();
+    let mut chars = value.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c == '\\' && matches!(chars.peek(), Some('d' | 'D' | 's' | 'S' | 'w' | 'W')) {
+            escaped_value.push(c);
+            escaped_value.extend(chars.next());
+        } else if is_strictly_escaped(c) {
+            escaped_value.push('\\');
+            escaped_value.push(c);
         } else {
-            write!(f, "{}", colored_value)
+            escaped_value.push(c);
+        }
+    }
+    escaped_value
+}
+
+fn push_byte_escape_group(escaped_value: &mut String, byte_escapes: &mut String) {
+    if !byte_escapes.is_empty() {
+        escaped_value.push_str(&format!("(?-u:{})", byte_escapes));
+        byte_escapes.clear();
+    }
+}
+
+fn count_logical_chars(value: &str) -> usize {
+    let mut count = 0;
+    let mut chars = value.chars();
+
+    while let Some(c) = chars.next() {
+        if c == '(' && chars.as_str().starts_with("?-u:") {
+            chars.find(|&it| it == ')');
+        } else if c == '\\' {
+            let digit_count = match chars.next() {
+                Some('u') => 4,
+                Some('x') => 2,
+                Some('c') => {
+                    chars.next();
+                    0
+                }
+                _ => 0,
+            };
+            if digit_count > 0 {
+                if chars.clone().next() == Some('{') {
+                    chars.find(|&it| it == '}');
+                } else if chars
+                    .clone()
+                    .take(digit_count)
+                    .all(|it| it.is_ascii_hexdigit())
+                {
+                    chars.nth(digit_count - 1);
+                }
+            }
+        }
+        count += 1;
+    }
+    count
+}
+
+fn count_chars_matched_by(value: &str) -> Option<u128> {
+    let mut count = 1u128;
+    let mut chars = value.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        let char_class_size = match (c, chars.peek()) {
+            ('\\', Some('d')) => Some(count_chars_in_table(DECIMAL_NUMBER)),
+            ('\\', Some('s')) => Some(count_chars_in_table(WHITE_SPACE)),
+            ('\\', Some('w')) => Some(count_chars_in_table(WORD)),
+            ('\\', Some('D')) => {
+                Some(UNICODE_SCALAR_VALUE_COUNT - count_chars_in_table(DECIMAL_NUMBER))
+            }
+            ('\\', Some('S')) => {
+                Some(UNICODE_SCALAR_VALUE_COUNT - count_chars_in_table(WHITE_SPACE))
+            }
+            ('\\', Some('W')) => Some(UNICODE_SCALAR_VALUE_COUNT - count_chars_in_table(WORD)),
+            _ => None,
+        };
+        if let Some(size) = char_class_size {
+            chars.next();
+            count = count.checked_mul(size)?;
         }
     }
+    Some(count)
 }
 
-fn to_colorized_string(
-    strings: Vec<ColorizableString>,
-    config: &RegExpConfig,
-) -> (
-    ColoredString,
-    ColoredString,
-    ColoredString,
-    ColoredString,
-    ColoredString,
-    ColoredString,
-    ColoredString,
-    ColoredString,
-) {
-    let v = strings
+fn count_chars_in_table(table: &[(char, char)]) -> u128 {
+    table
         .iter()
-        .map(|it| it.to_colorized_string(config.is_output_colorized))
-        .collect_vec();
-
-    (
-        v[0].clone(),
-        v[1].clone(),
-        v[2].clone(),
-        v[3].clone(),
-        v[4].clone(),
-        v[5].clone(),
-        v[6].clone(),
-        v[7].clone(),
-    )
+        .map(|&(start, end)| (end as u128) - (start as u128) + 1)
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ensure_unbounded_grapheme_has_no_match_count() {
+        let config = Arc::new(RegExpConfig::new());
+        let grapheme = Grapheme::new(vec!["a".to_string()], 1, UNBOUNDED_REPETITIONS, &config);
+        assert_eq!(grapheme.to_string(), "a+");
+        assert_eq!(grapheme.match_count(), None);
+    }
+
+    #[test]
+    fn ensure_graphemes_share_config() {
+        let config = Arc::new(RegExpConfig::new());
+        let graphemes = [
+            Grapheme::from("a", &config),
+            Grapheme::new(vec!["b".to_string()], 2, 3, &config),
+        ];
+        assert!(graphemes.iter().all(|it| Arc::ptr_eq(&it.config, &config)));
+        assert_eq!(Arc::strong_count(&config), 3);
+        assert_eq!(graphemes.iter().join(""), "ab{2,3}");
+    }
+
+    #[test]
+    fn ensure_correct_overlap_states() {
+        let config = Arc::new(RegExpConfig::new());
+        let grapheme = Grapheme::repeated("a", 2, 3, &config);
+        for (min, max, expected_state) in [
+            (3, 3, GraphemeOverlapState::Covered),
+            (2, 3, GraphemeOverlapState::Covered),
+            (1, 3, GraphemeOverlapState::Mergeable),
+            (4, 6, GraphemeOverlapState::Mergeable),
+            (5, 6, GraphemeOverlapState::Disjoint),
+            (1, 2, GraphemeOverlapState::Disjoint),
+        ] {
+            let other = Grapheme::repeated("a", min, max, &config);
+            assert_eq!(
+                grapheme.overlap_with(&other),
+                expected_state,
+                "a{{{},{}}}",
+                min,
+                max
+            );
+        }
+        let other = Grapheme::repeated("b", 2, 3, &config);
+        assert_eq!(
+            grapheme.overlap_with(&other),
+            GraphemeOverlapState::Disjoint
+        );
+    }
+
+    #[test]
+    fn ensure_overlap_respects_gap_tolerance() {
+        let mut config = RegExpConfig::new();
+        config.repetition_merge_gap_tolerance = 1;
+        let config = Arc::new(config);
+        let grapheme = Grapheme::repeated("a", 1, 2, &config);
+        let other = Grapheme::repeated("a", 4, 4, &config);
+        assert_eq!(
+            grapheme.overlap_with(&other),
+            GraphemeOverlapState::Mergeable
+        );
+        let other = Grapheme::repeated("a", 5, 5, &config);
+        assert_eq!(
+            grapheme.overlap_with(&other),
+            GraphemeOverlapState::Disjoint
+        );
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "The minimum count of a repetition must not be greater than its maximum count"
+    )]
+    fn ensure_repeated_grapheme_rejects_inverted_bounds() {
+        Grapheme::repeated("a", 3, 2, &Arc::new(RegExpConfig::new()));
+    }
+
+    #[test]
+    fn ensure_correct_validation_of_escape_sequences() {
+        for (c, sequence) in [
+            ('\t', "\\x09"),
+            ('\t', "\\t"),
+            ('\t', "\\x{9}"),
+            ('\t', "\\u0009"),
+            ('\t', "\\cI"),
+            ('\u{1f4a9}', "\\u{1f4a9}"),
+            ('.', "\\."),
+            ('\\', "\\\\"),
+            ('#', "\\#"),
+            ('x', "x"),
+        ] {
+            assert!(is_escape_sequence_safe(c, sequence), "{}", sequence);
+        }
+        for (c, sequence) in [
+            ('\t', ""),
+            ('.', "."),
+            ('a', "a|b"),
+            ('\\', "\\"),
+            ('a', "a\\"),
+            ('\t', "[\\t]"),
+            ('\t', "\\x{9"),
+            ('\t', "\\x9"),
+            ('9', "{9}"),
+            ('x', "y"),
+            ('x', "\\Q"),
+            ('x', "\\E"),
+            ('b', "\\b"),
+            ('<', "\\<"),
+            ('1', "\\1"),
+            ('1', "\\d"),
+            ('a', "\\p{L}"),
+            ('a', "\\x61b"),
+        ] {
+            assert!(!is_escape_sequence_safe(c, sequence), "{}", sequence);
+        }
+    }
 }
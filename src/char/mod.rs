@@ -14,10 +14,47 @@
  * limitations under the License.
  */
 
+//! The graphemes from which the generated regular expressions are built.
+//!
+//! Repetitions of the same grapheme are merged while the expression is created,
+//! and [`Grapheme::overlap_with`](struct.Grapheme.html#method.overlap_with) tells
+//! whether and how two repetitions can be merged:
+//!
+//! ```
+//! use grex::char::{Grapheme, GraphemeOverlapState};
+//! use grex::RegExpConfig;
+//! use std::sync::Arc;
+//!
+//! let config = Arc::new(RegExpConfig::new());
+//! let grapheme = Grapheme::repeated("a", 1, 2, &config);
+//! assert_eq!(
+//!     grapheme.overlap_with(&Grapheme::repeated("a", 2, 2, &config)),
+//!     GraphemeOverlapState::Covered
+//! );
+//! assert_eq!(
+//!     grapheme.overlap_with(&Grapheme::repeated("a", 3, 4, &config)),
+//!     GraphemeOverlapState::Mergeable
+//! );
+//! assert_eq!(
+//!     grapheme.overlap_with(&Grapheme::repeated("a", 5, 5, &config)),
+//!     GraphemeOverlapState::Disjoint
+//! );
+//! ```
+
 mod cluster;
 mod color;
 mod grapheme;
+#[cfg(feature = "coloring")]
+mod theme;
 
-pub use cluster::GraphemeCluster;
-pub use color::ColorizableString;
-pub use grapheme::Grapheme;
+pub(crate) use cluster::GraphemeCluster;
+pub(crate) use color::ColoredString;
+pub(crate) use color::{without_colorization, ColorizableString};
+pub(crate) use grapheme::{
+    char_range_len, count_capturing_groups, escape_code_point, escape_control_char,
+    escape_utf8_bytes, is_escape_sequence_safe, is_strictly_escaped, UNBOUNDED_REPETITIONS,
+    UNICODE_SCALAR_VALUE_COUNT,
+};
+pub use grapheme::{Grapheme, GraphemeOverlapState};
+#[cfg(feature = "coloring")]
+pub use theme::ColorTheme;
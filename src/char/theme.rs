@@ -0,0 +1,237 @@
+/*
+ * Copyright © 2019-2020 Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use colored::Color;
+use std::cmp::Ordering;
+use std::hash::{Hash, Hasher};
+
+/// This struct specifies the colors which are used for syntax highlighting
+/// if it has been enabled with method
+/// [`RegExpBuilder.with_syntax_highlighting`](./struct.RegExpBuilder.html#method.with_syntax_highlighting).
+///
+/// The default theme uses the same colors as previous versions of *grex*.
+/// Each color can be overridden individually, either with one of the 16 ANSI colors
+/// or with an RGB value such as `Color::TrueColor { r: 255, g: 128, b: 0 }`.
+///
+/// RGB values are emitted as 24-bit escape sequences if the terminal announces
+/// truecolor support via the environment variable `COLORTERM`. Otherwise, or if
+/// [`RegExpBuilder.with_truecolor_disabled`](./struct.RegExpBuilder.html#method.with_truecolor_disabled)
+/// has been called, the nearest ANSI color is used instead.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct ColorTheme {
+    #[cfg_attr(feature = "serde", serde(with = "ColorDef"))]
+    pub(crate) anchors: Color,
+    #[cfg_attr(feature = "serde", serde(with = "ColorDef"))]
+    pub(crate) braces: Color,
+    #[cfg_attr(feature = "serde", serde(with = "ColorDef"))]
+    pub(crate) brackets: Color,
+    #[cfg_attr(feature = "serde", serde(with = "ColorDef"))]
+    pub(crate) char_classes: Color,
+    #[cfg_attr(feature = "serde", serde(with = "ColorDef"))]
+    pub(crate) flags: Color,
+    #[cfg_attr(feature = "serde", serde(with = "ColorDef"))]
+    pub(crate) numbers: Color,
+    #[cfg_attr(feature = "serde", serde(with = "ColorDef"))]
+    pub(crate) parentheses: Color,
+    #[cfg_attr(feature = "serde", serde(with = "ColorDef"))]
+    pub(crate) pipe: Color,
+    #[cfg_attr(feature = "serde", serde(with = "ColorDef"))]
+    pub(crate) quantifiers: Color,
+    #[cfg_attr(feature = "serde", serde(with = "ColorDef"))]
+    pub(crate) range_hyphen: Color,
+}
+
+impl ColorTheme {
+    /// Creates the default color theme.
+    pub fn new() -> Self {
+        Self {
+            anchors: Color::Yellow,
+            braces: Color::White,
+            brackets: Color::Cyan,
+            char_classes: Color::Black,
+            flags: Color::BrightYellow,
+            numbers: Color::White,
+            parentheses: Color::Green,
+            pipe: Color::Red,
+            quantifiers: Color::Magenta,
+            range_hyphen: Color::Cyan,
+        }
+    }
+
+    /// Sets the color of the anchors `^` and `$`.
+    pub fn with_anchor_color(&mut self, color: Color) -> &mut Self {
+        self.anchors = color;
+        self
+    }
+
+    /// Sets the color of the braces `{` and `}` and the comma in between.
+    pub fn with_brace_color(&mut self, color: Color) -> &mut Self {
+        self.braces = color;
+        self
+    }
+
+    /// Sets the color of the brackets `[` and `]` of character classes.
+    pub fn with_bracket_color(&mut self, color: Color) -> &mut Self {
+        self.brackets = color;
+        self
+    }
+
+    /// Sets the color of the shorthand character classes such as `\d` or `\w`.
+    pub fn with_char_class_color(&mut self, color: Color) -> &mut Self {
+        self.char_classes = color;
+        self
+    }
+
+    /// Sets the color of inline flags such as `(?i)`.
+    pub fn with_flag_color(&mut self, color: Color) -> &mut Self {
+        self.flags = color;
+        self
+    }
+
+    /// Sets the color of the numbers within `{min,max}` quantifiers.
+    pub fn with_number_color(&mut self, color: Color) -> &mut Self {
+        self.numbers = color;
+        self
+    }
+
+    /// Sets the color of capturing and non-capturing parentheses.
+    pub fn with_parenthesis_color(&mut self, color: Color) -> &mut Self {
+        self.parentheses = color;
+        self
+    }
+
+    /// Sets the color of the alternation operator `|`.
+    pub fn with_pipe_color(&mut self, color: Color) -> &mut Self {
+        self.pipe = color;
+        self
+    }
+
+    /// Sets the color of the quantifiers `*` and `?`.
+    pub fn with_quantifier_color(&mut self, color: Color) -> &mut Self {
+        self.quantifiers = color;
+        self
+    }
+
+    /// Sets the color of the hyphen within character class ranges such as `a-z`.
+    pub fn with_range_hyphen_color(&mut self, color: Color) -> &mut Self {
+        self.range_hyphen = color;
+        self
+    }
+
+    fn color_codes(&self) -> [String; 10] {
+        [
+            self.anchors,
+            self.braces,
+            self.brackets,
+            self.char_classes,
+            self.flags,
+            self.numbers,
+            self.parentheses,
+            self.pipe,
+            self.quantifiers,
+            self.range_hyphen,
+        ]
+        .map(|color| format!("{:?}", color))
+    }
+}
+
+const ANSI_COLORS: [(Color, (u8, u8, u8)); 16] = [
+    (Color::Black, (0, 0, 0)),
+    (Color::Red, (205, 0, 0)),
+    (Color::Green, (0, 205, 0)),
+    (Color::Yellow, (205, 205, 0)),
+    (Color::Blue, (0, 0, 238)),
+    (Color::Magenta, (205, 0, 205)),
+    (Color::Cyan, (0, 205, 205)),
+    (Color::White, (229, 229, 229)),
+    (Color::BrightBlack, (127, 127, 127)),
+    (Color::BrightRed, (255, 0, 0)),
+    (Color::BrightGreen, (0, 255, 0)),
+    (Color::BrightYellow, (255, 255, 0)),
+    (Color::BrightBlue, (92, 92, 255)),
+    (Color::BrightMagenta, (255, 0, 255)),
+    (Color::BrightCyan, (0, 255, 255)),
+    (Color::BrightWhite, (255, 255, 255)),
+];
+
+/// Returns the ANSI color which is closest to the given RGB color
+/// in terms of euclidean distance. ANSI colors are returned unchanged.
+pub(crate) fn nearest_ansi_color(color: Color) -> Color {
+    if let Color::TrueColor { r, g, b } = color {
+        let distance = |(red, green, blue): (u8, u8, u8)| {
+            [(r, red), (g, green), (b, blue)]
+                .iter()
+                .map(|&(first, second)| (i32::from(first) - i32::from(second)).pow(2))
+                .sum::<i32>()
+        };
+        ANSI_COLORS
+            .iter()
+            .min_by_key(|(_, rgb)| distance(*rgb))
+            .map(|(ansi_color, _)| *ansi_color)
+            .unwrap()
+    } else {
+        color
+    }
+}
+
+impl Default for ColorTheme {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(remote = "Color", rename_all = "snake_case")]
+enum ColorDef {
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+    BrightBlack,
+    BrightRed,
+    BrightGreen,
+    BrightYellow,
+    BrightBlue,
+    BrightMagenta,
+    BrightCyan,
+    BrightWhite,
+    TrueColor { r: u8, g: u8, b: u8 },
+}
+
+impl Hash for ColorTheme {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.color_codes().hash(state);
+    }
+}
+
+impl PartialOrd for ColorTheme {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ColorTheme {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.color_codes().cmp(&other.color_codes())
+    }
+}
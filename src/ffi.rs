@@ -0,0 +1,157 @@
+/*
+ * Copyright © 2019-2020 Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! A C interface to *grex*, available with the `ffi` feature.
+//! The corresponding declarations are found in `include/grex.h`.
+
+use crate::regexp::{try_generate, Feature, RegExpConfig};
+use std::ffi::{CStr, CString};
+use std::os::raw::{c_char, c_int};
+use std::panic::catch_unwind;
+use std::ptr;
+
+/// The generation succeeded and `out` points to the resulting expression.
+pub const GREX_OK: c_int = 0;
+/// `inputs`, one of its elements or `out` is a null pointer.
+pub const GREX_NULL_POINTER: c_int = 1;
+/// One of the inputs is not valid UTF-8.
+pub const GREX_INVALID_UTF8: c_int = 2;
+/// No inputs have been provided.
+pub const GREX_NO_INPUTS: c_int = 3;
+/// The expression could not be generated with the given settings.
+pub const GREX_GENERATION_FAILED: c_int = 4;
+
+/// The settings for [`grex_generate`](fn.grex_generate.html),
+/// mirroring the boolean settings of [`RegExpConfig`](../struct.RegExpConfig.html).
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct GrexConfig {
+    pub is_digit_converted: bool,
+    pub is_non_digit_converted: bool,
+    pub is_space_converted: bool,
+    pub is_non_space_converted: bool,
+    pub is_word_converted: bool,
+    pub is_non_word_converted: bool,
+    pub is_repetition_converted: bool,
+    pub is_case_insensitive_matching: bool,
+    pub is_capturing_group_enabled: bool,
+    pub is_non_ascii_char_escaped: bool,
+    pub is_astral_code_point_converted_to_surrogate: bool,
+}
+
+impl GrexConfig {
+    fn to_regexp_config(self) -> RegExpConfig {
+        let features = [
+            (self.is_digit_converted, Feature::Digit),
+            (self.is_non_digit_converted, Feature::NonDigit),
+            (self.is_space_converted, Feature::Space),
+            (self.is_non_space_converted, Feature::NonSpace),
+            (self.is_word_converted, Feature::Word),
+            (self.is_non_word_converted, Feature::NonWord),
+            (self.is_repetition_converted, Feature::Repetition),
+            (
+                self.is_case_insensitive_matching,
+                Feature::CaseInsensitivity,
+            ),
+            (self.is_capturing_group_enabled, Feature::CapturingGroup),
+        ];
+        let mut config = RegExpConfig::new();
+        config.conversion_features = features
+            .iter()
+            .filter(|(is_enabled, _)| *is_enabled)
+            .map(|(_, feature)| feature.clone())
+            .collect();
+        config.is_non_ascii_char_escaped = self.is_non_ascii_char_escaped;
+        config.is_astral_code_point_converted_to_surrogate =
+            self.is_non_ascii_char_escaped && self.is_astral_code_point_converted_to_surrogate;
+        config
+    }
+}
+
+/// Generates a regular expression from `n` null-terminated UTF-8 strings.
+///
+/// If `cfg` is a null pointer, the default settings are used.
+/// On success, `GREX_OK` is returned and `*out` is set to a newly allocated
+/// null-terminated string which is owned by the caller and must be released
+/// with [`grex_free`](fn.grex_free.html), not with `free()`.
+/// On failure, one of the other `GREX_*` error codes is returned and `*out` is set
+/// to a null pointer if `out` itself is not null.
+///
+/// # Safety
+///
+/// `inputs` must point to `n` valid pointers to null-terminated strings,
+/// `cfg` must be null or point to a valid `GrexConfig`
+/// and `out` must be null or point to writable memory for a pointer.
+#[no_mangle]
+pub unsafe extern "C" fn grex_generate(
+    inputs: *const *const c_char,
+    n: usize,
+    cfg: *const GrexConfig,
+    out: *mut *mut c_char,
+) -> c_int {
+    if out.is_null() {
+        return GREX_NULL_POINTER;
+    }
+    *out = ptr::null_mut();
+    if n == 0 {
+        return GREX_NO_INPUTS;
+    }
+    if inputs.is_null() {
+        return GREX_NULL_POINTER;
+    }
+
+    let mut test_cases = Vec::with_capacity(n);
+    for i in 0..n {
+        let input = *inputs.add(i);
+        if input.is_null() {
+            return GREX_NULL_POINTER;
+        }
+        match CStr::from_ptr(input).to_str() {
+            Ok(test_case) => test_cases.push(test_case),
+            Err(_) => return GREX_INVALID_UTF8,
+        }
+    }
+    let config = if cfg.is_null() {
+        RegExpConfig::new()
+    } else {
+        (*cfg).to_regexp_config()
+    };
+
+    match catch_unwind(|| try_generate(&test_cases, &config)) {
+        Ok(Ok(regexp)) => match CString::new(regexp) {
+            Ok(regexp) => {
+                *out = regexp.into_raw();
+                GREX_OK
+            }
+            Err(_) => GREX_GENERATION_FAILED,
+        },
+        _ => GREX_GENERATION_FAILED,
+    }
+}
+
+/// Releases a string which has been returned by [`grex_generate`](fn.grex_generate.html).
+/// Passing a null pointer does nothing.
+///
+/// # Safety
+///
+/// `s` must be null or a pointer returned by `grex_generate`
+/// which has not been released yet.
+#[no_mangle]
+pub unsafe extern "C" fn grex_free(s: *mut c_char) {
+    if !s.is_null() {
+        drop(CString::from_raw(s));
+    }
+}
@@ -14,7 +14,7 @@
  * limitations under the License.
  */
 
-use crate::char::{Grapheme, GraphemeCluster};
+use crate::char::{Grapheme, GraphemeCluster, GraphemeOverlapState};
 use crate::regexp::RegExpConfig;
 use itertools::Itertools;
 use petgraph::dot::{Config, Dot};
@@ -24,21 +24,26 @@ use petgraph::visit::Dfs;
 use petgraph::{Directed, Direction};
 use std::cmp::{max, min};
 use std::collections::{BTreeSet, HashMap, HashSet};
+use std::sync::Arc;
 
 type State = NodeIndex<u32>;
 type StateLabel = String;
 type EdgeLabel = Grapheme;
 
+#[allow(clippy::upper_case_acronyms)]
 pub struct DFA {
     alphabet: BTreeSet<Grapheme>,
     graph: StableGraph<StateLabel, EdgeLabel>,
     initial_state: State,
     final_state_indices: HashSet<usize>,
-    config: RegExpConfig,
+    config: Arc<RegExpConfig>,
 }
 
 impl DFA {
-    pub(crate) fn from(grapheme_clusters: Vec<GraphemeCluster>, config: &RegExpConfig) -> Self {
+    pub(crate) fn from(
+        grapheme_clusters: Vec<GraphemeCluster>,
+        config: &Arc<RegExpConfig>,
+    ) -> Self {
         let mut dfa = Self::new(config);
         for cluster in grapheme_clusters {
             dfa.insert(cluster);
@@ -60,7 +65,7 @@ impl DFA {
         states
     }
 
-    pub(crate) fn outgoing_edges(&self, state: State) -> Edges<Grapheme, Directed> {
+    pub(crate) fn outgoing_edges(&self, state: State) -> Edges<'_, Grapheme, Directed> {
         self.graph.edges_directed(state, Direction::Outgoing)
     }
 
@@ -78,7 +83,7 @@ impl DFA {
         println!("{:?}", self.final_state_indices);
     }
 
-    fn new(config: &RegExpConfig) -> Self {
+    fn new(config: &Arc<RegExpConfig>) -> Self {
         let mut graph = StableGraph::new();
         let initial_state = graph.add_node("".to_string());
         Self {
@@ -94,7 +99,6 @@ impl DFA {
         let mut current_state = self.initial_state;
 
         for grapheme in cluster.graphemes() {
-            self.alphabet.insert(grapheme.clone());
             current_state = self.get_next_state(current_state, grapheme);
         }
         self.final_state_indices.insert(current_state.index());
@@ -112,24 +116,35 @@ impl DFA {
             let edge_idx = self.graph.find_edge(current_state, next_state).unwrap();
             let current_grapheme = self.graph.edge_weight(edge_idx).unwrap();
 
-            if current_grapheme.value() != grapheme.value() {
-                continue;
-            }
-
-            if current_grapheme.maximum() == grapheme.maximum() - 1 {
-                let min = min(current_grapheme.minimum(), grapheme.minimum());
-                let max = max(current_grapheme.maximum(), grapheme.maximum());
-                let new_grapheme = Grapheme::new(grapheme.chars().clone(), min, max, &self.config);
-                self.graph
-                    .update_edge(current_state, next_state, new_grapheme);
-                return Some(next_state);
-            } else if current_grapheme.maximum() == grapheme.maximum() {
-                return Some(next_state);
+            match current_grapheme.overlap_with(grapheme) {
+                GraphemeOverlapState::Covered => return Some(next_state),
+                GraphemeOverlapState::Mergeable => {
+                    let is_minimum_lowered = grapheme.minimum() < current_grapheme.minimum();
+                    if !is_minimum_lowered || self.is_reached_only_by(current_state, next_state) {
+                        let min = min(current_grapheme.minimum(), grapheme.minimum());
+                        let max = max(current_grapheme.maximum(), grapheme.maximum());
+                        let new_grapheme =
+                            Grapheme::new(grapheme.chars().clone(), min, max, &self.config);
+                        self.graph
+                            .update_edge(current_state, next_state, new_grapheme);
+                        return Some(next_state);
+                    }
+                }
+                GraphemeOverlapState::Disjoint => {}
             }
         }
         None
     }
 
+    fn is_reached_only_by(&self, current_state: State, next_state: State) -> bool {
+        let is_leaf = self.outgoing_edges(next_state).next().is_none();
+        let is_reached_by_others = self
+            .graph
+            .neighbors_directed(next_state, Direction::Incoming)
+            .any(|state| state != current_state);
+        is_leaf && !is_reached_by_others
+    }
+
     fn add_new_state(&mut self, current_state: State, edge_label: &Grapheme) -> State {
         let next_state = self.graph.add_node("".to_string());
         self.graph
@@ -139,6 +154,13 @@ impl DFA {
 
     #[allow(clippy::many_single_char_names)]
     fn minimize(&mut self) {
+        self.alphabet = self
+            .graph
+            .edge_indices()
+            .filter_map(|edge| self.graph.edge_weight(edge))
+            .cloned()
+            .collect();
+
         let mut p = self.get_initial_partition();
         let mut w = p.iter().cloned().collect_vec();
 
@@ -213,10 +235,7 @@ impl DFA {
             for parent_state in direct_parent_states {
                 let edge = self.graph.find_edge(parent_state, state).unwrap();
                 let grapheme = self.graph.edge_weight(edge).unwrap();
-                if grapheme.value() == label.value()
-                    && (grapheme.maximum() == label.maximum()
-                        || grapheme.minimum() == label.minimum())
-                {
+                if grapheme == label {
                     x.insert(parent_state);
                     break;
                 }
@@ -238,6 +257,9 @@ impl DFA {
                 if self.initial_state == *old_state {
                     new_initial_state = Some(new_state);
                 }
+                if self.final_state_indices.contains(&old_state.index()) {
+                    final_state_indices.insert(new_state.index());
+                }
                 state_mappings.insert(*old_state, new_state);
             }
         }
@@ -256,10 +278,6 @@ impl DFA {
                 let new_target_state = state_mappings.get(&old_target_state).unwrap();
 
                 graph.add_edge(*new_source_state, *new_target_state, grapheme.clone());
-
-                if self.final_state_indices.contains(&old_target_state.index()) {
-                    final_state_indices.insert(new_target_state.index());
-                }
             }
         }
         self.initial_state = new_initial_state.unwrap();
@@ -274,36 +292,55 @@ mod tests {
 
     #[test]
     fn test_state_count() {
-        let config = RegExpConfig::new();
+        let config = Arc::new(RegExpConfig::new());
         let mut dfa = DFA::new(&config);
         assert_eq!(dfa.state_count(), 1);
 
-        dfa.insert(GraphemeCluster::from("abcd", &RegExpConfig::new()));
+        dfa.insert(GraphemeCluster::from(
+            "abcd",
+            &Arc::new(RegExpConfig::new()),
+        ));
         assert_eq!(dfa.state_count(), 5);
     }
 
     #[test]
     fn test_is_final_state() {
-        let config = RegExpConfig::new();
+        let config = Arc::new(RegExpConfig::new());
         let dfa = DFA::from(
-            vec![GraphemeCluster::from("abcd", &RegExpConfig::new())],
+            vec![GraphemeCluster::from(
+                "abcd",
+                &Arc::new(RegExpConfig::new()),
+            )],
             &config,
         );
 
         let intermediate_state = State::new(3);
-        assert_eq!(dfa.is_final_state(intermediate_state), false);
+        assert!(!dfa.is_final_state(intermediate_state));
 
         let final_state = State::new(4);
-        assert_eq!(dfa.is_final_state(final_state), true);
+        assert!(dfa.is_final_state(final_state));
+    }
+
+    #[test]
+    fn test_is_final_initial_state() {
+        let config = Arc::new(RegExpConfig::new());
+        let dfa = DFA::from(
+            vec![
+                GraphemeCluster::from("", &Arc::new(RegExpConfig::new())),
+                GraphemeCluster::from("ab", &Arc::new(RegExpConfig::new())),
+            ],
+            &config,
+        );
+        assert!(dfa.is_final_state(dfa.initial_state));
     }
 
     #[test]
     fn test_outgoing_edges() {
-        let config = RegExpConfig::new();
+        let config = Arc::new(RegExpConfig::new());
         let dfa = DFA::from(
             vec![
-                GraphemeCluster::from("abcd", &RegExpConfig::new()),
-                GraphemeCluster::from("abxd", &RegExpConfig::new()),
+                GraphemeCluster::from("abcd", &Arc::new(RegExpConfig::new())),
+                GraphemeCluster::from("abxd", &Arc::new(RegExpConfig::new())),
             ],
             &config,
         );
@@ -314,14 +351,14 @@ mod tests {
         assert!(first_edge.is_some());
         assert_eq!(
             first_edge.unwrap().weight(),
-            &Grapheme::from("c", &RegExpConfig::new())
+            &Grapheme::from("c", &Arc::new(RegExpConfig::new()))
         );
 
         let second_edge = edges.next();
         assert!(second_edge.is_some());
         assert_eq!(
             second_edge.unwrap().weight(),
-            &Grapheme::from("x", &RegExpConfig::new())
+            &Grapheme::from("x", &Arc::new(RegExpConfig::new()))
         );
 
         let third_edge = edges.next();
@@ -330,22 +367,22 @@ mod tests {
 
     #[test]
     fn test_states_in_depth_first_order() {
-        let config = RegExpConfig::new();
+        let config = Arc::new(RegExpConfig::new());
         let dfa = DFA::from(
             vec![
-                GraphemeCluster::from("abcd", &RegExpConfig::new()),
-                GraphemeCluster::from("axyz", &RegExpConfig::new()),
+                GraphemeCluster::from("abcd", &Arc::new(RegExpConfig::new())),
+                GraphemeCluster::from("axyz", &Arc::new(RegExpConfig::new())),
             ],
             &config,
         );
         let states = dfa.states_in_depth_first_order();
         assert_eq!(states.len(), 7);
 
-        let first_state = states.get(0).unwrap();
+        let first_state = states.first().unwrap();
         let mut edges = dfa.outgoing_edges(*first_state);
         assert_eq!(
             edges.next().unwrap().weight(),
-            &Grapheme::from("a", &RegExpConfig::new())
+            &Grapheme::from("a", &Arc::new(RegExpConfig::new()))
         );
         assert!(edges.next().is_none());
 
@@ -353,11 +390,11 @@ mod tests {
         edges = dfa.outgoing_edges(*second_state);
         assert_eq!(
             edges.next().unwrap().weight(),
-            &Grapheme::from("b", &RegExpConfig::new())
+            &Grapheme::from("b", &Arc::new(RegExpConfig::new()))
         );
         assert_eq!(
             edges.next().unwrap().weight(),
-            &Grapheme::from("x", &RegExpConfig::new())
+            &Grapheme::from("x", &Arc::new(RegExpConfig::new()))
         );
         assert!(edges.next().is_none());
 
@@ -365,7 +402,7 @@ mod tests {
         edges = dfa.outgoing_edges(*third_state);
         assert_eq!(
             edges.next().unwrap().weight(),
-            &Grapheme::from("y", &RegExpConfig::new())
+            &Grapheme::from("y", &Arc::new(RegExpConfig::new()))
         );
         assert!(edges.next().is_none());
 
@@ -373,7 +410,7 @@ mod tests {
         edges = dfa.outgoing_edges(*fourth_state);
         assert_eq!(
             edges.next().unwrap().weight(),
-            &Grapheme::from("z", &RegExpConfig::new())
+            &Grapheme::from("z", &Arc::new(RegExpConfig::new()))
         );
         assert!(edges.next().is_none());
 
@@ -385,7 +422,7 @@ mod tests {
         edges = dfa.outgoing_edges(*sixth_state);
         assert_eq!(
             edges.next().unwrap().weight(),
-            &Grapheme::from("c", &RegExpConfig::new())
+            &Grapheme::from("c", &Arc::new(RegExpConfig::new()))
         );
         assert!(edges.next().is_none());
 
@@ -393,23 +430,29 @@ mod tests {
         edges = dfa.outgoing_edges(*seventh_state);
         assert_eq!(
             edges.next().unwrap().weight(),
-            &Grapheme::from("d", &RegExpConfig::new())
+            &Grapheme::from("d", &Arc::new(RegExpConfig::new()))
         );
         assert!(edges.next().is_none());
     }
 
     #[test]
     fn test_minimization_algorithm() {
-        let config = RegExpConfig::new();
+        let config = Arc::new(RegExpConfig::new());
         let mut dfa = DFA::new(&config);
         assert_eq!(dfa.graph.node_count(), 1);
         assert_eq!(dfa.graph.edge_count(), 0);
 
-        dfa.insert(GraphemeCluster::from("abcd", &RegExpConfig::new()));
+        dfa.insert(GraphemeCluster::from(
+            "abcd",
+            &Arc::new(RegExpConfig::new()),
+        ));
         assert_eq!(dfa.graph.node_count(), 5);
         assert_eq!(dfa.graph.edge_count(), 4);
 
-        dfa.insert(GraphemeCluster::from("abxd", &RegExpConfig::new()));
+        dfa.insert(GraphemeCluster::from(
+            "abxd",
+            &Arc::new(RegExpConfig::new()),
+        ));
         assert_eq!(dfa.graph.node_count(), 7);
         assert_eq!(dfa.graph.edge_count(), 6);
 
@@ -420,11 +463,11 @@ mod tests {
 
     #[test]
     fn test_dfa_constructor() {
-        let config = RegExpConfig::new();
+        let config = Arc::new(RegExpConfig::new());
         let dfa = DFA::from(
             vec![
-                GraphemeCluster::from("abcd", &RegExpConfig::new()),
-                GraphemeCluster::from("abxd", &RegExpConfig::new()),
+                GraphemeCluster::from("abcd", &Arc::new(RegExpConfig::new())),
+                GraphemeCluster::from("abxd", &Arc::new(RegExpConfig::new())),
             ],
             &config,
         );
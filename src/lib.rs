@@ -204,23 +204,50 @@
 //! ### 5. How does it work?
 //!
 //! 1. A [deterministic finite automaton](https://en.wikipedia.org/wiki/Deterministic_finite_automaton) (DFA)
-//! is created from the input strings.
+//!    is created from the input strings.
 //!
 //! 2. The number of states and transitions between states in the DFA is reduced by applying
-//! [Hopcroft's DFA minimization algorithm](https://en.wikipedia.org/wiki/DFA_minimization#Hopcroft.27s_algorithm).
+//!    [Hopcroft's DFA minimization algorithm](https://en.wikipedia.org/wiki/DFA_minimization#Hopcroft.27s_algorithm).
 //!
 //! 3. The minimized DFA is expressed as a system of linear equations which are solved with
-//! [Brzozowski's algebraic method](http://cs.stackexchange.com/questions/2016/how-to-convert-finite-automata-to-regular-expressions#2392),
-//! resulting in the final regular expression.
+//!    [Brzozowski's algebraic method](http://cs.stackexchange.com/questions/2016/how-to-convert-finite-automata-to-regular-expressions#2392),
+//!    resulting in the final regular expression.
 
 #[macro_use]
 mod macros;
 
-mod ast;
-mod char;
+pub mod ast;
+pub mod char;
+#[cfg(feature = "ffi")]
+pub mod ffi;
 mod fsm;
+#[cfg(feature = "python")]
+mod python;
 mod regexp;
 mod unicode_tables;
+#[cfg(feature = "wasm")]
+mod wasm;
 
+#[cfg(feature = "coloring")]
+pub use crate::char::ColorTheme;
+#[cfg(feature = "coloring")]
+pub use colored::Color;
+pub use regexp::generate;
+pub use regexp::try_generate;
+pub use regexp::AlternationOrder;
+pub use regexp::DedupReport;
+pub use regexp::EscapeStyle;
+pub use regexp::ExactRepetitionStyle;
 pub use regexp::Feature;
+pub use regexp::GroupPolicy;
+pub use regexp::InputTrim;
+pub use regexp::Normalization;
+pub use regexp::RegExp;
 pub use regexp::RegExpBuilder;
+pub use regexp::RegExpConfig;
+pub use regexp::RegExpError;
+pub use regexp::RegExpFlavor;
+pub use regexp::RegExpStats;
+pub use regexp::RegexFlags;
+#[cfg(feature = "wasm")]
+pub use wasm::generate_wasm;
@@ -31,6 +31,7 @@ use structopt::StructOpt;
     version_short = "v",
     global_setting = AppSettings::AllowLeadingHyphen
 )]
+#[allow(clippy::upper_case_acronyms)]
 struct CLI {
     // --------------------
     // ARGS
@@ -228,7 +229,7 @@ fn obtain_input(cli: &CLI) -> Result<Vec<String>, Error> {
     if !cli.input.is_empty() {
         Ok(cli.input.clone())
     } else if let Some(file_path) = &cli.file_path {
-        match std::fs::read_to_string(&file_path) {
+        match std::fs::read_to_string(file_path) {
             Ok(file_content) => Ok(file_content.lines().map(|it| it.to_string()).collect_vec()),
             Err(error) => Err(error),
         }
@@ -0,0 +1,117 @@
+/*
+ * Copyright © 2019-2020 Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+//! Python bindings for *grex*, available with the `python` feature.
+
+use crate::regexp::{Feature, RegExpBuilder};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+/// Generates a regular expression from the given test cases.
+///
+/// The keyword arguments correspond to the flags and options of the command-line tool.
+/// Raises a `ValueError` if no test cases are given or if the options conflict with each other.
+#[pyfunction]
+#[pyo3(signature = (
+    inputs,
+    *,
+    digits = false,
+    non_digits = false,
+    spaces = false,
+    non_spaces = false,
+    words = false,
+    non_words = false,
+    repetitions = false,
+    escape = false,
+    with_surrogates = false,
+    ignore_case = false,
+    capture_groups = false,
+    min_repetitions = 1,
+    min_substring_length = 1
+))]
+#[allow(clippy::too_many_arguments)]
+fn generate(
+    inputs: Vec<String>,
+    digits: bool,
+    non_digits: bool,
+    spaces: bool,
+    non_spaces: bool,
+    words: bool,
+    non_words: bool,
+    repetitions: bool,
+    escape: bool,
+    with_surrogates: bool,
+    ignore_case: bool,
+    capture_groups: bool,
+    min_repetitions: u32,
+    min_substring_length: u32,
+) -> PyResult<String> {
+    if inputs.is_empty() {
+        return Err(PyValueError::new_err(
+            "No test cases have been provided for regular expression generation",
+        ));
+    }
+    if with_surrogates && !escape {
+        return Err(PyValueError::new_err(
+            "with_surrogates requires escape to be enabled",
+        ));
+    }
+    if min_repetitions == 0 {
+        return Err(PyValueError::new_err("min_repetitions must not be zero"));
+    }
+    if min_substring_length == 0 {
+        return Err(PyValueError::new_err(
+            "min_substring_length must not be zero",
+        ));
+    }
+
+    let features = [
+        (digits, Feature::Digit),
+        (non_digits, Feature::NonDigit),
+        (spaces, Feature::Space),
+        (non_spaces, Feature::NonSpace),
+        (words, Feature::Word),
+        (non_words, Feature::NonWord),
+        (repetitions, Feature::Repetition),
+        (ignore_case, Feature::CaseInsensitivity),
+        (capture_groups, Feature::CapturingGroup),
+    ];
+    let conversion_features = features
+        .iter()
+        .filter(|(is_enabled, _)| *is_enabled)
+        .map(|(_, feature)| feature.clone())
+        .collect::<Vec<_>>();
+
+    let mut builder = RegExpBuilder::from(&inputs);
+    if !conversion_features.is_empty() {
+        builder.with_conversion_of(&conversion_features);
+    }
+    if escape {
+        builder.with_escaping_of_non_ascii_chars(with_surrogates);
+    }
+    builder
+        .with_minimum_repetitions(min_repetitions)
+        .with_minimum_substring_length(min_substring_length)
+        .try_build()
+        .map_err(|error| PyValueError::new_err(error.to_string()))
+}
+
+/// grex generates regular expressions from user-provided test cases.
+#[pymodule]
+fn grex(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_function(wrap_pyfunction!(generate, module)?)?;
+    Ok(())
+}
@@ -0,0 +1,37 @@
+/*
+ * Copyright © 2019-2020 Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+/// This enum specifies the order of the branches of alternations which can be passed to method
+/// [`RegExpBuilder.with_alternation_order`](./struct.RegExpBuilder.html#method.with_alternation_order).
+#[derive(Clone, Debug, Hash, Ord, PartialOrd, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum AlternationOrder {
+    /// Longer branches come first, so that a regex engine prefers the longest match
+    /// in alternations like `abc|ab`. Branches of equal length keep their input order.
+    ///
+    /// This is the default order.
+    ByLength,
+
+    /// Branches are sorted by the position of the first test case whose match goes through
+    /// the respective branch, so that the test cases `a` and `bc` result in the
+    /// expression `^(?:a|bc)$`. The test cases themselves are still sorted before
+    /// the expression is created from them, so only the order of the branches follows the input.
+    Input,
+
+    /// Branches are sorted lexicographically by their string representation.
+    Lexicographic,
+}
@@ -14,8 +14,14 @@
  * limitations under the License.
  */
 
+use crate::char::is_escape_sequence_safe;
+#[cfg(feature = "coloring")]
+use crate::char::ColorTheme;
 use crate::regexp::feature::Feature;
-use crate::regexp::{RegExp, RegExpConfig};
+use crate::regexp::{
+    AlternationOrder, EscapeStyle, ExactRepetitionStyle, GroupPolicy, InputTrim, Normalization,
+    RegExp, RegExpConfig, RegExpError, RegExpFlavor,
+};
 use itertools::Itertools;
 use std::io::ErrorKind;
 use std::path::PathBuf;
@@ -31,6 +37,9 @@ impl RegExpBuilder {
     ///
     /// The test cases need not be sorted because `RegExpBuilder` sorts them internally.
     ///
+    /// An empty string is a valid test case. It makes the entire expression optional,
+    /// so `""` and `"a"` result in `^a?$` and `""`, `"ab"` and `"cd"` result in `^(?:ab|cd)?$`.
+    ///
     /// ⚠ Panics if `test_cases` is empty.
     pub fn from<T: Clone + Into<String>>(test_cases: &[T]) -> Self {
         if test_cases.is_empty() {
@@ -42,6 +51,30 @@ impl RegExpBuilder {
         }
     }
 
+    /// Specifies the test cases as raw bytes to build a regular expression from
+    /// which is meant to be used with [`regex::bytes::Regex`](https://docs.rs/regex/latest/regex/bytes/struct.Regex.html).
+    ///
+    /// Each test case is treated as a sequence of single bytes instead of graphemes.
+    /// The resulting expression starts with the `(?-u)` flag which disables Unicode support,
+    /// so non-ascii bytes and ascii control characters are written as `\xHH` escape sequences
+    /// and shorthand character classes such as `\w` only match ascii characters.
+    ///
+    /// ⚠ Panics if `test_cases` is empty.
+    pub fn from_bytes<T: AsRef<[u8]>>(test_cases: &[T]) -> Self {
+        if test_cases.is_empty() {
+            panic!("No test cases have been provided for regular expression generation");
+        }
+        let mut config = RegExpConfig::new();
+        config.is_byte_mode = true;
+        Self {
+            test_cases: test_cases
+                .iter()
+                .map(|it| it.as_ref().iter().map(|&byte| byte as char).collect())
+                .collect_vec(),
+            config,
+        }
+    }
+
     /// Specifies a text file containing test cases to build the regular expression from.
     ///
     /// The test cases need not be sorted because `RegExpBuilder` sorts them internally.
@@ -69,7 +102,7 @@ impl RegExpBuilder {
                 ErrorKind::PermissionDenied => {
                     panic!("Permission denied: The specified file could not be opened")
                 }
-                _ => panic!(error),
+                _ => panic!("{}", error),
             },
         }
     }
@@ -87,6 +120,19 @@ impl RegExpBuilder {
         self
     }
 
+    /// Specifies per construct whether groups are capturing or non-capturing.
+    /// If this method is called, the policy takes precedence over
+    /// [`Feature::CapturingGroup`](./enum.Feature.html#variant.CapturingGroup).
+    /// Otherwise, all groups are captured if and only if the feature is set.
+    ///
+    /// The group surrounding the enclosing characters detected by method
+    /// [`with_enclosing_char_detection`](#method.with_enclosing_char_detection)
+    /// is always capturing because it is referenced by a backreference.
+    pub fn with_group_policy(&mut self, policy: &GroupPolicy) -> &mut Self {
+        self.config.group_policy = Some(policy.clone());
+        self
+    }
+
     /// Specifies the minimum quantity of substring repetitions to be converted if
     /// [`Feature::Repetition`](./enum.Feature.html#variant.Repetition)
     /// is set as one of the features in method
@@ -119,6 +165,74 @@ impl RegExpBuilder {
         self
     }
 
+    /// Specifies how many unobserved repetition counts may lie between two repetition ranges
+    /// of the same substring so that they are still merged into a single range, if
+    /// [`Feature::Repetition`](./enum.Feature.html#variant.Repetition)
+    /// is set as one of the features in method
+    /// [`with_conversion_of`](./struct.RegExpBuilder.html#method.with_conversion_of).
+    ///
+    /// For example, the counts `{2}` and `{5}` are merged into `{2,5}` if the tolerance is at least 2,
+    /// so the resulting expression additionally matches 3 and 4 repetitions.
+    ///
+    /// If the tolerance is not explicitly set with this method, a default value of 0 will be used,
+    /// merging adjacent counts only.
+    pub fn with_repetition_merge_gap_tolerance(&mut self, tolerance: u32) -> &mut Self {
+        self.config.repetition_merge_gap_tolerance = tolerance;
+        self
+    }
+
+    /// Tells `RegExpBuilder` to leave the upper bound of converted repetitions open, if
+    /// [`Feature::Repetition`](./enum.Feature.html#variant.Repetition)
+    /// is set as one of the features in method
+    /// [`with_conversion_of`](./struct.RegExpBuilder.html#method.with_conversion_of).
+    ///
+    /// For example, the test cases `aa` and `aaaa` result in the expression `^a{2,}$`
+    /// which matches two or more repetitions of `a`.
+    pub fn with_unbounded_repetitions(&mut self) -> &mut Self {
+        self.config.is_repetition_unbounded = true;
+        self
+    }
+
+    /// Specifies how repetitions with an exact count are written, e.g. `a{3}` or `aaa`.
+    /// If the style is not explicitly set with this method,
+    /// [`ExactRepetitionStyle::Braces`](./enum.ExactRepetitionStyle.html#variant.Braces)
+    /// will be used.
+    pub fn with_exact_repetition_style(&mut self, style: ExactRepetitionStyle) -> &mut Self {
+        self.config.exact_repetition_style = style;
+        self
+    }
+
+    /// Specifies the maximum number of distinct characters a character class may contain.
+    /// Larger character classes are replaced with the wildcard `.` for the sake of readability,
+    /// or with `[\s\S]` if they contain a newline which is not matched by `.`.
+    ///
+    /// ⚠ The resulting expression matches many more strings than the test cases.
+    ///
+    /// If the threshold is not explicitly set with this method, character classes are never replaced.
+    pub fn with_wildcard_threshold(&mut self, threshold: usize) -> &mut Self {
+        self.config.wildcard_threshold = threshold;
+        self
+    }
+
+    /// Tells `RegExpBuilder` to replace character classes containing a newline with the
+    /// wildcard `.` as well, preceded by the flag `(?s)` which makes `.` match newlines.
+    /// Without this setting, `[\s\S]` is used instead for engines without dotall support.
+    /// This only has an effect together with
+    /// [`with_wildcard_threshold`](#method.with_wildcard_threshold).
+    pub fn with_dotall_mode(&mut self) -> &mut Self {
+        self.config.is_dotall_enabled = true;
+        self
+    }
+
+    /// Tells `RegExpBuilder` to precede the expression with the flag `(?m)`
+    /// so that the anchors `^` and `$` match at the beginning and end of each line
+    /// instead of the whole text. This is useful for searching multi-line texts
+    /// for lines which equal one of the test cases.
+    pub fn with_multiline_mode(&mut self) -> &mut Self {
+        self.config.is_multiline = true;
+        self
+    }
+
     /// Tells `RegExpBuilder` to convert non-ASCII characters to unicode escape sequences.
     /// The parameter `use_surrogate_pairs` specifies whether to convert astral code planes
     /// (range `U+010000` to `U+10FFFF`) to surrogate pairs.
@@ -128,20 +242,599 @@ impl RegExpBuilder {
         self
     }
 
+    /// Specifies the notation of the escape sequences created by
+    /// [`with_escaping_of_non_ascii_chars`](#method.with_escaping_of_non_ascii_chars).
+    /// If the style is not explicitly set with this method,
+    /// [`EscapeStyle::UnicodeBraces`](./enum.EscapeStyle.html#variant.UnicodeBraces) will be used.
+    ///
+    /// The caret notation of [`EscapeStyle::ControlChar`](./enum.EscapeStyle.html#variant.ControlChar)
+    /// applies to control characters in literals and character classes alike,
+    /// whether or not non-ascii characters are escaped.
+    pub fn with_escape_style(&mut self, style: EscapeStyle) -> &mut Self {
+        self.config.escape_style = style;
+        self
+    }
+
+    /// Tells `RegExpBuilder` to write the given character as the given escape sequence,
+    /// taking precedence over the built-in escaping. For instance, a tab can be written as
+    /// `\x09` instead of `\t`. This method can be called repeatedly for different characters.
+    /// The sequences are used verbatim within literals and character classes, so they are
+    /// neither escaped again nor converted by [`with_escaping_of_non_ascii_chars`](#method.with_escaping_of_non_ascii_chars).
+    ///
+    /// Supported are plain characters other than metacharacters, escaped punctuation such as `\#`,
+    /// the escapes `\t`, `\n`, `\r`, `\f`, `\v` and `\a`, caret notation such as `\cI`
+    /// and hexadecimal escapes such as `\x09`, `\x{9}`, `\u0009` or `\u{9}`.
+    ///
+    /// ⚠ Panics if `sequence` does not denote exactly the character `c`. This applies to
+    /// metacharacters such as `.`, to several characters, to shorthand classes such as `\d`,
+    /// to assertions such as `\b`, to backreferences and to the quotes `\Q` and `\E`,
+    /// all of which would change the meaning of the resulting expression.
+    pub fn with_custom_escape(&mut self, c: char, sequence: &str) -> &mut Self {
+        if !is_escape_sequence_safe(c, sequence) {
+            panic!(
+                "Custom escape sequence '{}' for character '{}' is not a safe escape sequence",
+                sequence, c
+            );
+        }
+        self.config.custom_escapes.insert(c, sequence.to_string());
+        self
+    }
+
+    /// Specifies which whitespace is removed from the test cases before regular expression
+    /// generation, such as trailing spaces of lines scraped from files. Test cases which are
+    /// equal after trimming are treated as duplicates, so `a ` and `a` result in `^a$`
+    /// with [`InputTrim::Trailing`](./enum.InputTrim.html#variant.Trailing).
+    /// If the trimming is not explicitly set with this method,
+    /// [`InputTrim::None`](./enum.InputTrim.html#variant.None) will be used.
+    ///
+    /// ⚠ The resulting expression does not match the removed whitespace,
+    /// so `^a$` does not match the original test case `a ` anymore.
+    pub fn with_input_trim(&mut self, trim: InputTrim) -> &mut Self {
+        self.config.input_trim = trim;
+        self
+    }
+
+    /// Specifies the Unicode normalization form which the test cases are converted to
+    /// before they are segmented into graphemes. This collapses test cases which look the same
+    /// but are encoded differently, such as `é` written as one code point and as `e` followed
+    /// by a combining accent. If the form is not explicitly set with this method,
+    /// [`Normalization::None`](./enum.Normalization.html#variant.None) will be used.
+    ///
+    /// ⚠ The resulting expression matches the normalized text, so strings in another
+    /// normalization form must be normalized in the same way before they are matched.
+    /// Test cases created with [`from_bytes`](#method.from_bytes) are never normalized.
+    pub fn with_normalization(&mut self, normalization: Normalization) -> &mut Self {
+        self.config.normalization = normalization;
+        self
+    }
+
+    /// Tells `RegExpBuilder` to guarantee that the resulting expression consists of
+    /// ascii characters only. This enables the escaping of non-ascii characters as described in
+    /// [`with_escaping_of_non_ascii_chars`](#method.with_escaping_of_non_ascii_chars)
+    /// without conversion to surrogate pairs, unless it has been enabled already.
+    ///
+    /// If the guarantee cannot be met, [`try_build`](#method.try_build) returns an error.
+    pub fn with_ascii_only_output(&mut self) -> &mut Self {
+        self.config.is_ascii_only = true;
+        self.config.is_non_ascii_char_escaped = true;
+        self
+    }
+
+    /// Tells `RegExpBuilder` to escape the forward slash `/` as `\/`.
+    /// This is necessary if the resulting expression is embedded in a regular expression
+    /// literal delimited by slashes, such as in JavaScript or Perl.
+    /// Note that the *regex* crate rejects this escape sequence.
+    pub fn with_escaping_of_slashes(&mut self) -> &mut Self {
+        self.config.is_slash_escaped = true;
+        self
+    }
+
+    /// Tells `RegExpBuilder` to escape every printable ascii character in literals and
+    /// character classes which is not a letter or a digit, such as the space, `#`, `/` and `"`,
+    /// in addition to the metacharacters which are always escaped. For example, the test case
+    /// `a b#c` results in the expression `^a\ b\#c$`. Such characters remain literal regardless
+    /// of the flavor and of flags like the extended mode `(?x)`. Only `<` and `>` stay unescaped
+    /// because `\<` and `\>` denote word boundaries in some flavors.
+    /// Note that the *regex* crate rejects most of these escape sequences before version 1.8.
+    pub fn with_strict_escaping(&mut self) -> &mut Self {
+        self.config.is_strict_escaping = true;
+        self
+    }
+
+    /// Tells `RegExpBuilder` to wrap the resulting expression in a regular expression literal
+    /// such as `/^a$/` which is delimited by the given character. Inline flags such as `(?i)`
+    /// are moved behind the closing delimiter, resulting in `/^a$/i` for instance.
+    ///
+    /// The delimiter is escaped wherever it occurs within the expression, so that `a#b` results
+    /// in `#^a\#b$#` for the delimiter `#`. The flag `(?-u)` of byte mode has no counterpart
+    /// behind the closing delimiter, so it remains inline, resulting in `/(?-u)^a$/`.
+    ///
+    /// ⚠ Panics if `delimiter` is a letter, a digit, a whitespace character or a backslash.
+    pub fn with_literal_delimiter(&mut self, delimiter: char) -> &mut Self {
+        if delimiter.is_alphanumeric() || delimiter.is_whitespace() || delimiter == '\\' {
+            panic!(
+                "The literal delimiter must not be a letter, a digit, a whitespace character or a backslash"
+            );
+        }
+        self.config.literal_delimiter = Some(delimiter);
+        self
+    }
+
+    /// Tells `RegExpBuilder` to additionally match the test cases if they are surrounded
+    /// by one of the given bracket pairs, such as `[foo]` or `{foo}` for the test case `foo`.
+    ///
+    /// If `ensure_balance` is `false`, the opening and closing brackets are made optional
+    /// independently of each other, resulting in a compact expression like `[\[{]?foo[\]}]?`.
+    /// If `ensure_balance` is `true`, an alternation like `(?:\[foo\]|\{foo\}|foo)` is created
+    /// instead which only matches matching bracket pairs.
+    ///
+    /// ⚠ Panics if `bracket_pairs` is empty.
+    pub fn with_optional_surrounding_brackets(
+        &mut self,
+        bracket_pairs: &[(char, char)],
+        ensure_balance: bool,
+    ) -> &mut Self {
+        if bracket_pairs.is_empty() {
+            panic!("No bracket pairs have been provided for regular expression generation");
+        }
+        self.config.optional_bracket_pairs = bracket_pairs.to_vec();
+        self.config.is_bracket_balance_ensured = ensure_balance;
+        self
+    }
+
+    /// Tells `RegExpBuilder` to detect whether all test cases are decimal numbers consisting of
+    /// ASCII digits, a single decimal point and further ASCII digits. If so, a compact expression
+    /// is created which only takes the observed numbers of digits into account. For example,
+    /// the test cases `1.5` and `23.75` result in the expression `^\d{1,2}\.\d{1,2}$`.
+    ///
+    /// ⚠ The resulting expression matches many more numbers than the test cases.
+    pub fn with_decimal_number_detection(&mut self) -> &mut Self {
+        self.config.is_decimal_number_detected = true;
+        self
+    }
+
+    /// Tells `RegExpBuilder` to detect whether all test cases are numbers consisting of
+    /// ASCII digits only. If so, a single `\d` is created which is quantified with the minimum
+    /// and maximum number of digits. For example, the test cases `007` and `123` result in the
+    /// expression `^\d{3}$` and the test cases `5` and `1234` in the expression `^\d{1,4}$`.
+    /// If [`with_unbounded_repetitions`](#method.with_unbounded_repetitions) has been called,
+    /// the upper bound is left open, resulting in the expression `^\d+$` for the latter.
+    ///
+    /// Test cases with a `+` or `-` sign are not considered numeric unless the signs have been
+    /// removed by [`with_optional_sign_detection`](#method.with_optional_sign_detection).
+    ///
+    /// ⚠ The resulting expression matches many more numbers than the test cases.
+    pub fn with_numeric_detection(&mut self) -> &mut Self {
+        self.config.is_numeric_detection_enabled = true;
+        self
+    }
+
+    /// Tells `RegExpBuilder` to detect whether the test cases differ in length only but consist
+    /// of single characters which all belong to a common character class, that is a single range
+    /// of characters without gaps. If so, this class is quantified with the minimum and maximum
+    /// length of the test cases. For example, the test cases `ab`, `abc` and `abcd` result in
+    /// the expression `^[a-d]{2,4}$`, whereas `cat` and `horse` do not share a class.
+    ///
+    /// ⚠ The resulting expression matches any combination of the characters in the class.
+    pub fn with_length_quantification(&mut self) -> &mut Self {
+        self.config.is_length_quantification_enabled = true;
+        self
+    }
+
+    /// Tells `RegExpBuilder` to detect whether every test case is a run of the same grapheme.
+    /// If so, this grapheme is quantified with the minimum and maximum length of the runs.
+    /// For example, the test cases `aaa`, `aaaa` and `aaaaa` result in the expression `^a{3,5}$`.
+    ///
+    /// ⚠ The resulting expression matches all run lengths in between, so that `aaa` and `aaaaa`
+    /// result in `^a{3,5}$` which matches `aaaa` as well.
+    pub fn with_run_detection(&mut self) -> &mut Self {
+        self.config.is_run_detection_enabled = true;
+        self
+    }
+
+    /// Tells `RegExpBuilder` to detect whether all test cases are hexadecimal strings consisting
+    /// of ASCII hex digits with at least one of the letters `a-f` or `A-F`. If so, the class of
+    /// all hex digits in the observed letter cases is quantified with the minimum and maximum
+    /// length of the test cases. For example, the test cases `cafe` and `deadbeef` result in the
+    /// expression `^[0-9a-f]{4,8}$` and the test cases `Cafe` and `BEEF` in the expression
+    /// `^[0-9A-Fa-f]{4}$`.
+    ///
+    /// This setting takes precedence over the length quantification enabled by method
+    /// [`with_length_quantification`](#method.with_length_quantification).
+    ///
+    /// ⚠ The resulting expression matches any hexadecimal string of the observed lengths.
+    pub fn with_hex_generalization(&mut self) -> &mut Self {
+        self.config.is_hex_generalization_enabled = true;
+        self
+    }
+
+    /// Tells `RegExpBuilder` to detect whether all test cases are numbers of which at least one
+    /// has a leading `+` or `-` sign. If so, the signs are removed from the test cases and an
+    /// optional sign is prepended to the resulting expression instead. For example,
+    /// the test cases `5`, `-5` and `+5` result in the expression `^[+-]?5$`.
+    ///
+    /// This setting composes with the decimal number detection enabled by method
+    /// [`with_decimal_number_detection`](./struct.RegExpBuilder.html#method.with_decimal_number_detection).
+    pub fn with_optional_sign_detection(&mut self) -> &mut Self {
+        self.config.is_optional_sign_detected = true;
+        self
+    }
+
+    /// Tells `RegExpBuilder` to detect a delimiter which separates the test cases into fields,
+    /// such as the comma in `a,b,c`. The fields of all test cases are converted to a single
+    /// expression which is repeated with the delimiter in between, so that the test cases
+    /// `a,b,c` and `x,y` result in the expression `^(?:[a-cxy],)*[a-cxy]$`.
+    ///
+    /// ⚠ The resulting expression matches any number of fields in any combination.
+    ///
+    /// A candidate is only detected if it occurs in at least one test case and if none of
+    /// the test cases contains an empty field. If several candidates qualify, the one which
+    /// occurs most often is used. The candidates can be set with method
+    /// [`with_delimiter_candidates`](#method.with_delimiter_candidates).
+    pub fn with_delimiter_detection(&mut self) -> &mut Self {
+        self.config.is_delimiter_detection_enabled = true;
+        self
+    }
+
+    /// Specifies the candidates for delimiter detection, in order of preference
+    /// in case several of them occur equally often.
+    /// If the candidates are not explicitly set with this method,
+    /// the comma, semicolon, pipe and space characters are used.
+    ///
+    /// The candidates only take effect if delimiter detection has been enabled with method
+    /// [`with_delimiter_detection`](#method.with_delimiter_detection).
+    ///
+    /// ⚠ Panics if `delimiters` is empty.
+    pub fn with_delimiter_candidates(&mut self, delimiters: &[char]) -> &mut Self {
+        if delimiters.is_empty() {
+            panic!("No delimiter candidates have been provided for regular expression generation");
+        }
+        self.config.delimiter_candidates = delimiters.to_vec();
+        self
+    }
+
+    /// Tells `RegExpBuilder` to treat the given characters as noise, such as the separators
+    /// in phone numbers. Each occurrence of a noise character is made optional, so that the
+    /// test case `555-1234` results in the expression `^555\-?1234$` for the noise character `-`.
+    /// Test cases which only differ from other test cases by missing noise characters are
+    /// covered by those and therefore not considered separately. Different noise characters
+    /// at the same position are merged into an optional class, such as `[ -]?`.
+    ///
+    /// Noise characters are only recognized if they are not converted to character classes
+    /// or repetitions at the same time.
+    ///
+    /// ⚠ Panics if `chars` is empty.
+    pub fn with_noise_chars(&mut self, chars: &[char]) -> &mut Self {
+        if chars.is_empty() {
+            panic!("No noise characters have been provided for regular expression generation");
+        }
+        self.config.noise_chars = chars.iter().cloned().collect();
+        self
+    }
+
+    /// Tells `RegExpBuilder` to detect whether every test case is enclosed by the same
+    /// character on both ends, such as `"foo"` and `'foo'`. If so, and if the enclosing
+    /// character differs across the test cases, it is captured in a group which is
+    /// referred to again at the end, resulting in an expression like `^(["'])foo\1$`.
+    ///
+    /// Backreferences are not supported by the [*regex*](https://crates.io/crates/regex) crate,
+    /// so this setting only takes effect for the flavors
+    /// [`Pcre`](./enum.RegExpFlavor.html#variant.Pcre) and
+    /// [`Ruby`](./enum.RegExpFlavor.html#variant.Ruby). It is ignored for all other flavors.
+    pub fn with_enclosing_char_detection(&mut self) -> &mut Self {
+        self.config.is_enclosing_char_detected = true;
+        self
+    }
+
+    /// Specifies the order of the branches of alternations.
+    /// If the order is not explicitly set with this method,
+    /// [`AlternationOrder::ByLength`](./enum.AlternationOrder.html#variant.ByLength) will be used.
+    pub fn with_alternation_order(&mut self, order: AlternationOrder) -> &mut Self {
+        self.config.alternation_order = order;
+        self
+    }
+
+    /// Tells `RegExpBuilder` to count how often each test case occurs and to sort the branches
+    /// of alternations by descending frequency, so that the most common cases are tried first.
+    /// The frequency of a branch is the total number of occurrences of the test cases whose
+    /// match goes through the branch. For example, the test cases `bc`, `a` and `a` result in
+    /// the expression `^(?:a|bc)$` instead of `^(?:bc|a)$`.
+    ///
+    /// Branches of equal frequency are ordered as specified by method
+    /// [`with_alternation_order`](#method.with_alternation_order).
+    /// Duplicate test cases are still listed in the [`DedupReport`](./struct.DedupReport.html).
+    pub fn with_frequency_ordering(&mut self) -> &mut Self {
+        self.config.is_frequency_ordered = true;
+        self
+    }
+
+    /// Tells `RegExpBuilder` to keep the order of the test cases throughout the generation.
+    ///
+    /// By default, the test cases are sorted by length and then lexicographically before they
+    /// are processed, so that any permutation of the same test cases results in the same
+    /// expression. Afterwards, the branches of alternations are ordered as specified by method
+    /// [`with_alternation_order`](#method.with_alternation_order).
+    ///
+    /// With this setting, the test cases are only deduplicated, keeping the first occurrence of
+    /// each one. The branches of alternations are then sorted by the position of the first test
+    /// case whose match goes through the respective branch, so that the test cases `a` and `bc`
+    /// result in the expression `^(?:a|bc)$` instead of `^(?:bc|a)$`. If frequency
+    /// ordering is enabled as well with method
+    /// [`with_frequency_ordering`](#method.with_frequency_ordering), it takes precedence and
+    /// branches of equal frequency are ordered by input.
+    ///
+    /// The members of character classes are always sorted by code point.
+    pub fn with_preserved_input_order(&mut self) -> &mut Self {
+        self.config.is_input_order_preserved = true;
+        self
+    }
+
+    /// Tells `RegExpBuilder` to make sure that no branch of an alternation is a prefix
+    /// of an earlier branch, so that `ab|abc` becomes `abc|ab`.
+    ///
+    /// Engines such as PCRE try the branches from left to right and settle on the first
+    /// one which matches, so a shorter branch can shadow a longer one in expressions
+    /// which are not anchored. Branches are compared by the strings they match, so that
+    /// `a` is moved behind `[ab]c` as well. They are only moved if necessary,
+    /// so the order set by the other settings is kept otherwise.
+    pub fn with_longest_branches_first(&mut self) -> &mut Self {
+        self.config.is_longest_first = true;
+        self
+    }
+
+    /// Specifies the regular expression flavor to generate.
+    /// If the flavor is not explicitly set with this method,
+    /// [`RegExpFlavor::Standard`](./enum.RegExpFlavor.html#variant.Standard) will be used.
+    ///
+    /// With [`RegExpFlavor::RustBytes`](./enum.RegExpFlavor.html#variant.RustBytes),
+    /// non-ascii characters are always converted to byte escapes, so the setting made
+    /// with [`with_escaping_of_non_ascii_chars`](#method.with_escaping_of_non_ascii_chars)
+    /// is ignored.
+    pub fn with_flavor(&mut self, flavor: RegExpFlavor) -> &mut Self {
+        self.config.flavor = flavor;
+        self
+    }
+
+    /// Tells `RegExpBuilder` to create atomic groups `(?>...)` around alternations and
+    /// repetitions which prevent backtracking into them, e.g. `(?>ab|cd)+`.
+    /// Atomic groups are only created for [`RegExpFlavor::Pcre`](./enum.RegExpFlavor.html#variant.Pcre)
+    /// and [`RegExpFlavor::Ruby`](./enum.RegExpFlavor.html#variant.Ruby)
+    /// as the other flavors do not support them. Non-capturing groups are created instead.
+    ///
+    /// Atomic groups cannot be capturing at the same time, so [`try_build`](#method.try_build)
+    /// returns [`RegExpError::AtomicCapturingGroup`](./enum.RegExpError.html#variant.AtomicCapturingGroup)
+    /// if alternations or repetitions are captured by
+    /// [`Feature::CapturingGroup`](./enum.Feature.html#variant.CapturingGroup) or
+    /// [`with_group_policy`](#method.with_group_policy).
+    ///
+    /// ⚠ An atomic alternation never reconsiders its other branches once a branch has matched,
+    /// so the resulting expression may not match all test cases if the branches overlap.
+    pub fn with_atomic_groups(&mut self) -> &mut Self {
+        self.config.is_atomic_groups_enabled = true;
+        self
+    }
+
+    /// Specifies the maximum number of characters of the resulting regular expression,
+    /// excluding any ANSI escape sequences added by syntax highlighting. Pathological test cases
+    /// can result in very long expressions, so [`try_build`](#method.try_build) returns
+    /// [`RegExpError::OutputTooLong`](./enum.RegExpError.html#variant.OutputTooLong)
+    /// instead if the expression is longer. It is up to the caller to choose a more general
+    /// representation then, e.g. by enabling the conversion of character classes.
+    ///
+    /// ⚠ Panics if `length` is zero.
+    pub fn with_max_output_length(&mut self, length: usize) -> &mut Self {
+        if length == 0 {
+            panic!("Maximum output length must not be zero");
+        }
+        self.config.max_output_length = Some(length);
+        self
+    }
+
+    /// Tells `RegExpBuilder` to quote runs of literal characters with `\Q...\E` instead of
+    /// escaping every metacharacter on its own, so that `a.b*c` becomes `\Qa.b*c\E`.
+    /// Only runs containing at least two characters which would have to be escaped are quoted.
+    /// An `\E` within the run is written as `\E\\E\Q`, which ends the quoted run,
+    /// matches `\E` literally and begins a new quoted run.
+    ///
+    /// Literal quoting is only applied for [`RegExpFlavor::Pcre`](./enum.RegExpFlavor.html#variant.Pcre)
+    /// as the other flavors do not support it. Characters are escaped as usual instead.
+    /// Do not enable it for .NET, which does not support `\Q...\E`.
+    pub fn with_literal_quoting(&mut self) -> &mut Self {
+        self.config.is_literal_quoting_enabled = true;
+        self
+    }
+
+    /// Tells `RegExpBuilder` not to wrap an expression which is an alternation as a whole
+    /// in a non-capturing group. The anchors are repeated for every branch instead, which
+    /// keeps the meaning of the expression, so that `^(?:abc|def)$` becomes `^abc$|^def$`.
+    ///
+    /// Capturing and atomic groups are kept as removing them would change the numbering of
+    /// the capturing groups or the backtracking behavior. The group is also kept if enclosing
+    /// characters have been detected as described in
+    /// [`with_enclosing_char_detection`](#method.with_enclosing_char_detection).
+    pub fn with_top_level_group_suppressed(&mut self) -> &mut Self {
+        self.config.is_top_level_group_suppressed = true;
+        self
+    }
+
+    /// Tells `RegExpBuilder` to match every non-empty prefix of the test cases as well,
+    /// which is useful for autocompletion. The test case `abc` results in `^a(?:bc?)?$`,
+    /// for instance, matching `a`, `ab` and `abc`. Prefixes are built grapheme by grapheme,
+    /// so a character is never separated from its combining marks.
+    ///
+    /// Enclosing characters as described in
+    /// [`with_enclosing_char_detection`](#method.with_enclosing_char_detection)
+    /// and optional number signs are detected before, so they are not subject to this setting.
+    pub fn with_prefix_matching(&mut self) -> &mut Self {
+        self.config.is_prefix_matching_enabled = true;
+        self
+    }
+
+    /// Tells `RegExpBuilder` to extract the longest common prefix of all branches of an alternation
+    /// into a leading literal, so that `foobar|foobaz|fooqux` becomes `foo(?:ba[rz]|qux)`.
+    /// Prefixes are compared grapheme by grapheme.
+    pub fn with_prefix_factoring(&mut self) -> &mut Self {
+        self.config.is_prefix_factoring_enabled = true;
+        self
+    }
+
+    /// Tells `RegExpBuilder` to extract the longest common suffix of all branches of an alternation
+    /// into a trailing literal, so that `running|jumping` becomes `(?:runn|jump)ing`.
+    /// If prefix factoring is enabled as well, prefixes are extracted first.
+    pub fn with_suffix_factoring(&mut self) -> &mut Self {
+        self.config.is_suffix_factoring_enabled = true;
+        self
+    }
+
+    /// Tells `RegExpBuilder` to merge two branches of an alternation which only differ
+    /// in a single inserted grapheme, so that `abxc|abc` becomes `abx?c`.
+    /// Branches of the generated alternations are merged this way before any common
+    /// prefixes or suffixes are extracted.
+    pub fn with_optional_char_detection(&mut self) -> &mut Self {
+        self.config.is_optional_char_detection_enabled = true;
+        self
+    }
+
+    /// Tells `RegExpBuilder` to always write quantifiers in `{min,max}` notation
+    /// instead of using the shorthands `?`, `*` and `+`. For example, `a?` becomes `a{0,1}`.
+    pub fn with_explicit_quantifiers(&mut self) -> &mut Self {
+        self.config.is_quantifier_shorthand_enabled = false;
+        self
+    }
+
+    /// Tells `RegExpBuilder` not to report duplicate test cases in the
+    /// [`DedupReport`](./struct.DedupReport.html) of the generated [`RegExp`](./struct.RegExp.html).
+    /// Duplicates never affect the resulting expression, so this only saves the bookkeeping.
+    pub fn with_dedup_disabled(&mut self) -> &mut Self {
+        self.config.is_dedup_enabled = false;
+        self
+    }
+
+    /// Tells [`RegExp::from_reader`](./struct.RegExp.html#method.from_reader) and
+    /// [`RegExp::from_file`](./struct.RegExp.html#method.from_file) to ignore lines
+    /// which are empty or consist of whitespace only.
+    pub fn with_skipping_of_blank_lines(&mut self) -> &mut Self {
+        self.config.is_blank_line_skipped = true;
+        self
+    }
+
+    /// Tells `RegExpBuilder` to record which test cases have contributed the members
+    /// of the character classes in the generated [`RegExp`](./struct.RegExp.html).
+    /// The annotations are not part of the expression itself but can be retrieved with
+    /// [`RegExp::char_class_annotations`](./struct.RegExp.html#method.char_class_annotations)
+    /// for debugging purposes.
+    pub fn with_char_class_annotations(&mut self) -> &mut Self {
+        self.config.is_char_class_annotated = true;
+        self
+    }
+
+    /// Tells `RegExpBuilder` to list every member of a character class instead of
+    /// compressing runs of consecutive characters to ranges, so that `[a-f]` becomes `[abcdef]`.
+    pub fn with_range_compression_disabled(&mut self) -> &mut Self {
+        self.config.is_range_compression_enabled = false;
+        self
+    }
+
+    /// Specifies how many consecutive characters a character class must contain
+    /// in order to be compressed to a range. With a length of 4, for instance,
+    /// `[abc]` is left as it is, but `[abcd]` becomes `[a-d]`.
+    ///
+    /// If the length is not explicitly set with this method, a default value of 3 will be used.
+    ///
+    /// ⚠ Panics if `length` is less than 2.
+    pub fn with_minimum_range_length(&mut self, length: u32) -> &mut Self {
+        if length < 2 {
+            panic!("Minimum range length must be at least 2");
+        }
+        self.config.minimum_range_length = length;
+        self
+    }
+
+    /// Tells `RegExpBuilder` to keep alternations of single characters such as `a|é|ü`
+    /// instead of converting them to character classes such as `[aéü]`.
+    pub fn with_alternation_to_class_disabled(&mut self) -> &mut Self {
+        self.config.is_alternation_to_class_enabled = false;
+        self
+    }
+
+    /// Specifies how many single characters an alternation must consist of
+    /// in order to be converted to a character class. With a threshold of 3, for instance,
+    /// `a|é` is left as it is, but `a|é|ü` becomes `[aéü]`.
+    ///
+    /// If the threshold is not explicitly set with this method, a default value of 2 will be used.
+    ///
+    /// ⚠ Panics if `threshold` is less than 2.
+    pub fn with_alternation_to_class_threshold(&mut self, threshold: usize) -> &mut Self {
+        if threshold < 2 {
+            panic!("Alternation to class threshold must be at least 2");
+        }
+        self.config.alternation_to_class_threshold = threshold;
+        self
+    }
+
     /// Tells `RegExpBuilder` to provide syntax highlighting for the resulting regular expression.
     ///
     /// ⚠ This method may only be used if the resulting regular expression is meant to
     /// be printed to the console. The regex string representation returned from enabling
     /// this setting cannot be fed into the [*regex*](https://crates.io/crates/regex) crate.
+    ///
+    /// Without the `coloring` feature, this method has no effect.
     pub fn with_syntax_highlighting(&mut self) -> &mut Self {
-        self.config.is_output_colorized = true;
+        self.config.is_output_colorized = cfg!(feature = "coloring");
         self
     }
 
+    /// Tells `RegExpBuilder` which colors to use for syntax highlighting.
+    /// If no theme is set explicitly, the default [`ColorTheme`](./struct.ColorTheme.html) is used.
+    ///
+    /// The theme only takes effect if syntax highlighting has been enabled with method
+    /// [`with_syntax_highlighting`](./struct.RegExpBuilder.html#method.with_syntax_highlighting).
+    #[cfg(feature = "coloring")]
+    pub fn with_color_theme(&mut self, theme: &ColorTheme) -> &mut Self {
+        self.config.color_theme = theme.clone();
+        self
+    }
+
+    /// Tells `RegExpBuilder` to replace all RGB colors of the color theme with their
+    /// nearest ANSI colors, even if the terminal announces truecolor support.
+    /// This is useful for dumb terminals which do not understand 24-bit escape sequences.
+    pub fn with_truecolor_disabled(&mut self) -> &mut Self {
+        self.config.is_truecolor_disabled = true;
+        self
+    }
+
+    /// Returns the settings which have been made so far.
+    pub fn config(&self) -> &RegExpConfig {
+        &self.config
+    }
+
     /// Builds the actual regular expression using the previously given settings.
     /// Every generated regular expression is surrounded by the anchors `^` and `$`
     /// so that substrings not being part of the test cases are not matched accidentally.
+    ///
+    /// ⚠ Panics if ascii-only output has been requested but cannot be guaranteed
+    /// or if the regular expression exceeds the maximum output length.
+    /// Use [`try_build`](#method.try_build) to handle these cases without panicking.
     pub fn build(&mut self) -> String {
-        RegExp::from(&mut self.test_cases, &self.config).to_string()
+        self.try_build().unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Builds the actual regular expression like [`build`](#method.build)
+    /// but returns an error instead of panicking if ascii-only output has been
+    /// requested with [`with_ascii_only_output`](#method.with_ascii_only_output)
+    /// but cannot be guaranteed, if atomic groups conflict with capturing groups
+    /// or if the regular expression exceeds the maximum output length.
+    pub fn try_build(&mut self) -> Result<String, RegExpError> {
+        self.config.check_atomic_groups()?;
+        let regexp = self.build_regexp();
+        regexp.check_ascii_only()?;
+        regexp.check_output_length()?;
+        Ok(regexp.to_string())
+    }
+
+    /// Builds the actual regular expression like [`build`](./struct.RegExpBuilder.html#method.build)
+    /// but returns the [`RegExp`](./struct.RegExp.html) itself instead of its string representation.
+    pub fn build_regexp(&mut self) -> RegExp {
+        RegExp::from(&mut self.test_cases.clone(), &self.config)
     }
 }
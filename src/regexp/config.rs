@@ -14,27 +14,146 @@
  * limitations under the License.
  */
 
-use crate::regexp::Feature;
+#[cfg(feature = "coloring")]
+use crate::char::ColorTheme;
+use crate::regexp::{
+    AlternationOrder, EscapeStyle, ExactRepetitionStyle, Feature, GroupPolicy, InputTrim,
+    Normalization, RegExpError, RegExpFlavor,
+};
+use std::collections::{BTreeMap, BTreeSet};
+use std::str::FromStr;
 
+/// This struct holds the settings for regular expression generation.
+///
+/// [`RegExpConfig::new()`](#method.new) returns the default settings.
+/// Customized settings are made with [`RegExpBuilder`](./struct.RegExpBuilder.html)
+/// and can be retrieved by [`RegExpBuilder::config()`](./struct.RegExpBuilder.html#method.config).
 #[derive(Clone, Debug, Hash, Ord, PartialOrd, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct RegExpConfig {
     pub(crate) conversion_features: Vec<Feature>,
     pub(crate) minimum_repetitions: u32,
     pub(crate) minimum_substring_length: u32,
+    pub(crate) repetition_merge_gap_tolerance: u32,
+    pub(crate) is_repetition_unbounded: bool,
+    pub(crate) exact_repetition_style: ExactRepetitionStyle,
+    pub(crate) wildcard_threshold: usize,
+    pub(crate) is_dotall_enabled: bool,
+    pub(crate) is_multiline: bool,
+    pub(crate) is_decimal_number_detected: bool,
+    pub(crate) is_numeric_detection_enabled: bool,
+    pub(crate) is_length_quantification_enabled: bool,
+    pub(crate) is_run_detection_enabled: bool,
+    pub(crate) is_hex_generalization_enabled: bool,
+    pub(crate) is_optional_sign_detected: bool,
+    pub(crate) is_prefix_factoring_enabled: bool,
+    pub(crate) is_suffix_factoring_enabled: bool,
+    pub(crate) is_optional_char_detection_enabled: bool,
+    pub(crate) alternation_order: AlternationOrder,
+    pub(crate) is_frequency_ordered: bool,
+    pub(crate) is_input_order_preserved: bool,
+    pub(crate) is_longest_first: bool,
+    pub(crate) is_dedup_enabled: bool,
+    pub(crate) is_blank_line_skipped: bool,
+    pub(crate) flavor: RegExpFlavor,
+    pub(crate) is_char_class_annotated: bool,
     pub(crate) is_non_ascii_char_escaped: bool,
     pub(crate) is_astral_code_point_converted_to_surrogate: bool,
+    pub(crate) escape_style: EscapeStyle,
+    pub(crate) normalization: Normalization,
+    pub(crate) input_trim: InputTrim,
+    pub(crate) custom_escapes: BTreeMap<char, String>,
+    pub(crate) is_prefix_matching_enabled: bool,
+    pub(crate) is_slash_escaped: bool,
+    pub(crate) is_strict_escaping: bool,
+    pub(crate) is_ascii_only: bool,
+    pub(crate) literal_delimiter: Option<char>,
+    pub(crate) is_byte_mode: bool,
     pub(crate) is_output_colorized: bool,
+    #[cfg(feature = "coloring")]
+    pub(crate) color_theme: ColorTheme,
+    pub(crate) is_truecolor_disabled: bool,
+    pub(crate) group_policy: Option<GroupPolicy>,
+    pub(crate) is_atomic_groups_enabled: bool,
+    pub(crate) is_literal_quoting_enabled: bool,
+    pub(crate) max_output_length: Option<usize>,
+    pub(crate) is_top_level_group_suppressed: bool,
+    pub(crate) is_enclosing_char_detected: bool,
+    pub(crate) is_quantifier_shorthand_enabled: bool,
+    pub(crate) is_range_compression_enabled: bool,
+    pub(crate) minimum_range_length: u32,
+    pub(crate) is_alternation_to_class_enabled: bool,
+    pub(crate) alternation_to_class_threshold: usize,
+    pub(crate) noise_chars: BTreeSet<char>,
+    pub(crate) is_delimiter_detection_enabled: bool,
+    pub(crate) delimiter_candidates: Vec<char>,
+    pub(crate) optional_bracket_pairs: Vec<(char, char)>,
+    pub(crate) is_bracket_balance_ensured: bool,
 }
 
 impl RegExpConfig {
-    pub(crate) fn new() -> Self {
+    /// Creates the default settings for regular expression generation.
+    pub fn new() -> Self {
         Self {
             conversion_features: vec![],
             minimum_repetitions: 1,
             minimum_substring_length: 1,
+            repetition_merge_gap_tolerance: 0,
+            is_repetition_unbounded: false,
+            exact_repetition_style: ExactRepetitionStyle::Braces,
+            wildcard_threshold: usize::MAX,
+            is_dotall_enabled: false,
+            is_multiline: false,
+            is_decimal_number_detected: false,
+            is_numeric_detection_enabled: false,
+            is_length_quantification_enabled: false,
+            is_run_detection_enabled: false,
+            is_hex_generalization_enabled: false,
+            is_optional_sign_detected: false,
+            is_prefix_factoring_enabled: false,
+            is_suffix_factoring_enabled: false,
+            is_optional_char_detection_enabled: false,
+            alternation_order: AlternationOrder::ByLength,
+            is_frequency_ordered: false,
+            is_input_order_preserved: false,
+            is_longest_first: false,
+            is_dedup_enabled: true,
+            is_blank_line_skipped: false,
+            flavor: RegExpFlavor::Standard,
+            is_char_class_annotated: false,
             is_non_ascii_char_escaped: false,
             is_astral_code_point_converted_to_surrogate: false,
+            escape_style: EscapeStyle::UnicodeBraces,
+            normalization: Normalization::None,
+            input_trim: InputTrim::None,
+            custom_escapes: BTreeMap::new(),
+            is_prefix_matching_enabled: false,
+            is_slash_escaped: false,
+            is_strict_escaping: false,
+            is_ascii_only: false,
+            literal_delimiter: None,
+            is_byte_mode: false,
             is_output_colorized: false,
+            #[cfg(feature = "coloring")]
+            color_theme: ColorTheme::new(),
+            is_truecolor_disabled: false,
+            group_policy: None,
+            is_atomic_groups_enabled: false,
+            is_literal_quoting_enabled: false,
+            max_output_length: None,
+            is_top_level_group_suppressed: false,
+            is_enclosing_char_detected: false,
+            is_quantifier_shorthand_enabled: true,
+            is_range_compression_enabled: true,
+            minimum_range_length: 3,
+            is_alternation_to_class_enabled: true,
+            alternation_to_class_threshold: 2,
+            noise_chars: BTreeSet::new(),
+            is_delimiter_detection_enabled: false,
+            delimiter_candidates: vec![',', ';', '|', ' '],
+            optional_bracket_pairs: vec![],
+            is_bracket_balance_ensured: false,
         }
     }
 
@@ -75,7 +194,141 @@ impl RegExpConfig {
         self.conversion_features.contains(&Feature::CapturingGroup)
     }
 
+    pub(crate) fn is_alternation_captured(&self) -> bool {
+        self.group_policy
+            .as_ref()
+            .map_or(self.is_capturing_group_enabled(), |it| {
+                it.is_alternation_captured
+            })
+    }
+
+    pub(crate) fn is_concatenation_captured(&self) -> bool {
+        self.group_policy
+            .as_ref()
+            .map_or(self.is_capturing_group_enabled(), |it| {
+                it.is_concatenation_captured
+            })
+    }
+
+    pub(crate) fn is_repetition_captured(&self) -> bool {
+        self.group_policy
+            .as_ref()
+            .map_or(self.is_capturing_group_enabled(), |it| {
+                it.is_repetition_captured
+            })
+    }
+
+    pub(crate) fn is_repetition_expanded(&self, min: u32, max: u32) -> bool {
+        min == max && min > 1 && self.exact_repetition_style == ExactRepetitionStyle::Expanded
+    }
+
+    pub(crate) fn is_atomic_group_used(&self) -> bool {
+        self.is_atomic_groups_enabled && self.flavor.is_atomic_group_supported()
+    }
+
+    pub(crate) fn is_enclosing_char_detection_used(&self) -> bool {
+        self.is_enclosing_char_detected && self.flavor.is_backreference_supported()
+    }
+
+    pub(crate) fn is_literal_quoting_used(&self) -> bool {
+        self.is_literal_quoting_enabled && self.flavor.is_literal_quoting_supported()
+    }
+
+    /// Returns `true` if the given character has to be escaped because it delimits
+    /// the regular expression literal, either as the forward slash or as the literal delimiter.
+    pub(crate) fn is_escaped_delimiter(&self, c: char) -> bool {
+        (c == '/' && self.is_slash_escaped) || self.literal_delimiter == Some(c)
+    }
+
+    pub(crate) fn check_atomic_groups(&self) -> Result<(), RegExpError> {
+        if self.is_atomic_groups_enabled
+            && (self.is_alternation_captured() || self.is_repetition_captured())
+        {
+            Err(RegExpError::AtomicCapturingGroup)
+        } else {
+            Ok(())
+        }
+    }
+
     pub(crate) fn is_char_class_feature_enabled(&self) -> bool {
         self.conversion_features.iter().any(|it| it.is_char_class())
     }
 }
+
+/// Parses a compact string of single-character flags, such as `"dgi"`, into settings.
+/// The flags correspond to the short options of the command-line tool.
+/// All settings not mentioned keep their default values.
+///
+/// | Flag | Setting |
+/// |------|---------|
+/// | `d`  | [`Feature::Digit`](./enum.Feature.html#variant.Digit) |
+/// | `D`  | [`Feature::NonDigit`](./enum.Feature.html#variant.NonDigit) |
+/// | `s`  | [`Feature::Space`](./enum.Feature.html#variant.Space) |
+/// | `S`  | [`Feature::NonSpace`](./enum.Feature.html#variant.NonSpace) |
+/// | `w`  | [`Feature::Word`](./enum.Feature.html#variant.Word) |
+/// | `W`  | [`Feature::NonWord`](./enum.Feature.html#variant.NonWord) |
+/// | `r`  | [`Feature::Repetition`](./enum.Feature.html#variant.Repetition) |
+/// | `i`  | [`Feature::CaseInsensitivity`](./enum.Feature.html#variant.CaseInsensitivity) |
+/// | `g`  | [`Feature::CapturingGroup`](./enum.Feature.html#variant.CapturingGroup) |
+/// | `e`  | escaping of non-ascii characters |
+/// | `u`  | escaping of non-ascii characters with surrogate pairs for astral code points |
+/// | `m`  | multiline mode with the flag `(?m)` |
+/// | `c`  | syntax highlighting |
+///
+/// Returns [`RegExpError::UnknownFlag`](./enum.RegExpError.html#variant.UnknownFlag)
+/// for any other character.
+///
+/// ```
+/// use grex::{generate, RegExpConfig};
+///
+/// let config = "dg".parse::<RegExpConfig>().unwrap();
+/// assert_eq!(generate(&["a", "1"], &config), "^(\\d|a)$");
+/// ```
+impl FromStr for RegExpConfig {
+    type Err = RegExpError;
+
+    fn from_str(flags: &str) -> Result<Self, Self::Err> {
+        let mut config = RegExpConfig::new();
+        for flag in flags.chars() {
+            let feature = match flag {
+                'd' => Feature::Digit,
+                'D' => Feature::NonDigit,
+                's' => Feature::Space,
+                'S' => Feature::NonSpace,
+                'w' => Feature::Word,
+                'W' => Feature::NonWord,
+                'r' => Feature::Repetition,
+                'i' => Feature::CaseInsensitivity,
+                'g' => Feature::CapturingGroup,
+                'e' => {
+                    config.is_non_ascii_char_escaped = true;
+                    continue;
+                }
+                'u' => {
+                    config.is_non_ascii_char_escaped = true;
+                    config.is_astral_code_point_converted_to_surrogate = true;
+                    continue;
+                }
+                'm' => {
+                    config.is_multiline = true;
+                    continue;
+                }
+                'c' => {
+                    config.is_output_colorized = cfg!(feature = "coloring");
+                    continue;
+                }
+                _ => return Err(RegExpError::UnknownFlag(flag)),
+            };
+            if !config.conversion_features.contains(&feature) {
+                config.conversion_features.push(feature);
+            }
+        }
+        Ok(config)
+    }
+}
+
+impl Default for RegExpConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
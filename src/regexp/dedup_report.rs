@@ -0,0 +1,39 @@
+/*
+ * Copyright © 2019-2020 Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+/// This struct reports the duplicate test cases which have been removed
+/// before generating a [`RegExp`](./struct.RegExp.html).
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct DedupReport {
+    removed: Vec<String>,
+}
+
+impl DedupReport {
+    pub(crate) fn new(removed: Vec<String>) -> Self {
+        Self { removed }
+    }
+
+    /// Returns the removed duplicates in the order of their occurrence.
+    /// A test case is listed once per additional occurrence.
+    pub fn removed(&self) -> &[String] {
+        &self.removed
+    }
+
+    /// Returns `true` if no duplicates have been removed.
+    pub fn is_empty(&self) -> bool {
+        self.removed.is_empty()
+    }
+}
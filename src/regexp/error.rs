@@ -0,0 +1,72 @@
+/*
+ * Copyright © 2019-2020 Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::error::Error;
+use std::fmt::{Display, Formatter, Result};
+
+/// This enum lists the errors which can occur during regular expression generation.
+/// They are returned by [`RegExpBuilder::try_build`](./struct.RegExpBuilder.html#method.try_build)
+/// and [`try_generate`](./fn.try_generate.html).
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum RegExpError {
+    /// Ascii-only output has been requested, but the test cases contain non-ascii characters
+    /// and their escaping has been disabled.
+    NonAsciiCharsNotEscaped,
+
+    /// Ascii-only output has been requested, but the regular expression contains the given
+    /// non-ascii character which cannot be escaped, such as a non-ascii literal delimiter.
+    NonAsciiChar(char),
+
+    /// A flags string parsed into a [`RegExpConfig`](./struct.RegExpConfig.html)
+    /// contains the given character which is not a known flag.
+    UnknownFlag(char),
+
+    /// Atomic groups have been requested for alternations or repetitions
+    /// which are configured as capturing groups at the same time.
+    AtomicCapturingGroup,
+
+    /// The regular expression is longer than the given maximum number of characters
+    /// which has been set with
+    /// [`RegExpBuilder::with_max_output_length`](./struct.RegExpBuilder.html#method.with_max_output_length).
+    OutputTooLong(usize),
+}
+
+impl Display for RegExpError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            RegExpError::NonAsciiCharsNotEscaped => write!(
+                f,
+                "Ascii-only output requires the escaping of non-ascii characters"
+            ),
+            RegExpError::NonAsciiChar(c) => write!(
+                f,
+                "Ascii-only output is violated by the non-ascii character '{}'",
+                c
+            ),
+            RegExpError::UnknownFlag(c) => write!(f, "'{}' is not a known flag", c),
+            RegExpError::AtomicCapturingGroup => {
+                write!(f, "Atomic groups cannot be combined with capturing groups")
+            }
+            RegExpError::OutputTooLong(max_length) => write!(
+                f,
+                "The regular expression exceeds the maximum length of {} characters",
+                max_length
+            ),
+        }
+    }
+}
+
+impl Error for RegExpError {}
@@ -0,0 +1,48 @@
+/*
+ * Copyright © 2019-2020 Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+/// This enum specifies the notation of escape sequences for non-ascii characters
+/// which can be passed to method
+/// [`RegExpBuilder.with_escape_style`](./struct.RegExpBuilder.html#method.with_escape_style).
+#[derive(Clone, Debug, Hash, Ord, PartialOrd, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum EscapeStyle {
+    /// Escape sequences with braces, e.g. `\u{e9}` for `é` and `\u{1f4a9}` for `💩`.
+    ///
+    /// This is the default style.
+    UnicodeBraces,
+
+    /// Escape sequences with exactly four hexadecimal digits, e.g. `\u00e9` for `é`.
+    /// Astral code points do not fit into four digits, so they are written with braces,
+    /// e.g. `\u{1f4a9}`, unless they are converted to surrogate pairs such as `\ud83d\udca9`.
+    FourDigitU,
+
+    /// Escape sequences with exactly two hexadecimal digits for code points up to `U+00FF`,
+    /// e.g. `\xe9` for `é`. Larger code points are written with braces, e.g. `\x{1f4a9}`.
+    HexByte,
+
+    /// Escape sequences with braces and at least four hexadecimal digits for all code points,
+    /// e.g. `\x{00e9}` for `é` and `\x{1f4a9}` for `💩`. This uniform notation is accepted
+    /// by PCRE and RE2 among others.
+    HexBraces,
+
+    /// Caret notation for the control characters `U+0001` to `U+001A`, e.g. `\cA` for `U+0001`.
+    /// The characters `\t`, `\n` and `\r` keep their conventional escape sequences.
+    /// All other characters are escaped in the same way as with
+    /// [`UnicodeBraces`](#variant.UnicodeBraces).
+    ControlChar,
+}
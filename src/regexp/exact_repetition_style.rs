@@ -0,0 +1,33 @@
+/*
+ * Copyright © 2019-2020 Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+/// This enum specifies how repetitions with an exact count are written. It can be passed to method
+/// [`RegExpBuilder.with_exact_repetition_style`](./struct.RegExpBuilder.html#method.with_exact_repetition_style).
+/// Repetitions with a range of counts are always written with braces, e.g. `a{2,3}`.
+#[derive(Clone, Debug, Hash, Ord, PartialOrd, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum ExactRepetitionStyle {
+    /// The repeated expression is followed by the count in braces, e.g. `a{3}` or `(?:ab){2}`.
+    ///
+    /// This is the default style.
+    Braces,
+
+    /// The repeated expression is spelled out as often as it is repeated, e.g. `aaa` or `abab`.
+    /// Expressions containing a capturing group are still written with braces, e.g. `(ab|c){2}`,
+    /// because every copy of the group would be numbered separately.
+    Expanded,
+}
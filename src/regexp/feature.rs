@@ -17,6 +17,8 @@
 /// This enum specifies the supported conversion features which can be passed to method
 /// [`RegExpBuilder.with_conversion_of`](./struct.RegExpBuilder.html#method.with_conversion_of).
 #[derive(Clone, Debug, Hash, Ord, PartialOrd, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum Feature {
     /// This feature converts any Unicode decimal digit to character class `\d`.
     ///
@@ -89,9 +91,6 @@ pub enum Feature {
 
 impl Feature {
     pub(crate) fn is_char_class(&self) -> bool {
-        match self {
-            Feature::Repetition => false,
-            _ => true,
-        }
+        !matches!(self, Feature::Repetition)
     }
 }
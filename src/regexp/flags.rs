@@ -0,0 +1,77 @@
+/*
+ * Copyright © 2019-2020 Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use std::ops::BitOr;
+
+/// This struct holds the flags of a regular expression which are returned separately
+/// from the pattern by [`RegExp::to_pattern_and_flags`](./struct.RegExp.html#method.to_pattern_and_flags).
+///
+/// Flags can be combined with the `|` operator and tested with [`contains`](#method.contains).
+#[derive(Clone, Copy, Debug, Default, Hash, Ord, PartialOrd, Eq, PartialEq)]
+pub struct RegexFlags {
+    bits: u8,
+}
+
+impl RegexFlags {
+    /// Matching is case-insensitive, equivalent to the inline flag `(?i)`.
+    pub const CASE_INSENSITIVE: Self = Self { bits: 0b0001 };
+
+    /// Unicode support is disabled, equivalent to the inline flag `(?-u)`.
+    /// This is the case for expressions built from bytes.
+    pub const UNICODE_DISABLED: Self = Self { bits: 0b0010 };
+
+    /// The wildcard `.` matches newlines as well, equivalent to the inline flag `(?s)`.
+    /// This is only set if a wildcard is part of an expression built in dotall mode.
+    pub const DOT_MATCHES_NEW_LINE: Self = Self { bits: 0b0100 };
+
+    /// The anchors `^` and `$` match at the beginning and end of each line,
+    /// equivalent to the inline flag `(?m)`.
+    pub const MULTI_LINE: Self = Self { bits: 0b1000 };
+
+    /// Returns a value without any flags set.
+    pub fn empty() -> Self {
+        Self { bits: 0 }
+    }
+
+    /// Returns the raw bits of the flags.
+    pub fn bits(&self) -> u8 {
+        self.bits
+    }
+
+    /// Returns `true` if no flags are set.
+    pub fn is_empty(&self) -> bool {
+        self.bits == 0
+    }
+
+    /// Returns `true` if all flags of `other` are set.
+    pub fn contains(&self, other: Self) -> bool {
+        self.bits & other.bits == other.bits
+    }
+
+    pub(crate) fn insert(&mut self, other: Self) {
+        self.bits |= other.bits;
+    }
+}
+
+impl BitOr for RegexFlags {
+    type Output = Self;
+
+    fn bitor(self, other: Self) -> Self {
+        Self {
+            bits: self.bits | other.bits,
+        }
+    }
+}
@@ -0,0 +1,74 @@
+/*
+ * Copyright © 2019-2020 Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+/// This enum specifies the regular expression flavor which can be passed to method
+/// [`RegExpBuilder.with_flavor`](./struct.RegExpBuilder.html#method.with_flavor).
+#[derive(Clone, Debug, Hash, Ord, PartialOrd, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum RegExpFlavor {
+    /// Perl-compatible regular expressions which operate on Unicode strings,
+    /// such as the ones of the [*regex*](https://crates.io/crates/regex) crate's `Regex` type.
+    ///
+    /// This is the default flavor.
+    Standard,
+
+    /// Regular expressions for the [*regex*](https://crates.io/crates/regex) crate's
+    /// `regex::bytes::Regex` type. Non-ascii characters are converted to byte escapes
+    /// of their UTF-8 encoding, e.g. `é` becomes `(?-u:\xc3\xa9)`. The escapes are
+    /// enclosed in groups which disable Unicode mode because `\xHH` would denote
+    /// a code point instead of a byte otherwise.
+    RustBytes,
+
+    /// Regular expressions for backtracking engines such as PCRE, Java or .NET.
+    /// They are created like the ones of the [`Standard`](#variant.Standard) flavor,
+    /// but support atomic groups. Literal quoting with `\Q...\E` is supported by PCRE
+    /// and Java only, .NET does not understand it.
+    Pcre,
+
+    /// Regular expressions for Ruby's Onigmo engine. They are created like the ones of the
+    /// [`Standard`](#variant.Standard) flavor, but support atomic groups, and the class
+    /// of all hex digits `[0-9A-Fa-f]` is written as the shorthand `\h`, its complement as `\H`.
+    ///
+    /// In Ruby, `^` and `$` always match at line boundaries, so the input is anchored with
+    /// `\A` and `\z` instead. Only in multiline mode are `^` and `$` used, without any flag.
+    /// The flag `(?m)` makes the dot match newlines in Ruby, so it replaces the flag `(?s)`.
+    ///
+    /// Note that shorthand classes such as `\d` and `\w` only match ascii characters in Ruby.
+    Ruby,
+}
+
+impl RegExpFlavor {
+    pub(crate) fn is_atomic_group_supported(&self) -> bool {
+        self == &RegExpFlavor::Pcre || self == &RegExpFlavor::Ruby
+    }
+
+    pub(crate) fn is_hex_digit_shorthand_supported(&self) -> bool {
+        self == &RegExpFlavor::Ruby
+    }
+
+    pub(crate) fn is_multiline_by_default(&self) -> bool {
+        self == &RegExpFlavor::Ruby
+    }
+
+    pub(crate) fn is_backreference_supported(&self) -> bool {
+        self == &RegExpFlavor::Pcre || self == &RegExpFlavor::Ruby
+    }
+
+    pub(crate) fn is_literal_quoting_supported(&self) -> bool {
+        self == &RegExpFlavor::Pcre
+    }
+}
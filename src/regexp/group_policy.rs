@@ -0,0 +1,57 @@
+/*
+ * Copyright © 2019-2020 Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+/// This struct specifies per construct whether groups are capturing or non-capturing.
+/// It can be passed to method
+/// [`RegExpBuilder.with_group_policy`](./struct.RegExpBuilder.html#method.with_group_policy)
+/// and takes precedence over [`Feature::CapturingGroup`](./enum.Feature.html#variant.CapturingGroup).
+///
+/// [`GroupPolicy::new()`](#method.new) creates non-capturing groups for all constructs.
+/// Each construct can be switched to capturing groups individually.
+#[derive(Clone, Debug, Default, Hash, Ord, PartialOrd, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct GroupPolicy {
+    pub(crate) is_alternation_captured: bool,
+    pub(crate) is_concatenation_captured: bool,
+    pub(crate) is_repetition_captured: bool,
+}
+
+impl GroupPolicy {
+    /// Creates a policy with non-capturing groups for all constructs.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates capturing groups around alternations, e.g. `^(a|bc)$`.
+    pub fn with_capturing_alternations(&mut self) -> &mut Self {
+        self.is_alternation_captured = true;
+        self
+    }
+
+    /// Creates capturing groups around alternations which are part of
+    /// a concatenation, e.g. `^x(a|bc)$`.
+    pub fn with_capturing_concatenations(&mut self) -> &mut Self {
+        self.is_concatenation_captured = true;
+        self
+    }
+
+    /// Creates capturing groups around repeated expressions, e.g. `^(ab){2}$`.
+    pub fn with_capturing_repetitions(&mut self) -> &mut Self {
+        self.is_repetition_captured = true;
+        self
+    }
+}
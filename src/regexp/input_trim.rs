@@ -0,0 +1,53 @@
+/*
+ * Copyright © 2019-2020 Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+/// This enum specifies which whitespace is removed from the test cases before regular
+/// expression generation. It can be passed to method
+/// [`RegExpBuilder.with_input_trim`](./struct.RegExpBuilder.html#method.with_input_trim).
+#[derive(Clone, Debug, Hash, Ord, PartialOrd, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum InputTrim {
+    /// The test cases are processed as they are.
+    ///
+    /// This is the default.
+    None,
+
+    /// Whitespace at the end of the test cases is removed.
+    Trailing,
+
+    /// Whitespace at the beginning and at the end of the test cases is removed.
+    Both,
+}
+
+impl InputTrim {
+    /// Removes the whitespace from the given value. In byte mode, only ascii whitespace
+    /// is removed as the other characters stand for single bytes.
+    pub(crate) fn trim<'a>(&self, value: &'a str, is_byte_mode: bool) -> &'a str {
+        let is_whitespace = |c: char| {
+            if is_byte_mode {
+                c.is_ascii_whitespace()
+            } else {
+                c.is_whitespace()
+            }
+        };
+        match self {
+            InputTrim::None => value,
+            InputTrim::Trailing => value.trim_end_matches(is_whitespace),
+            InputTrim::Both => value.trim_matches(is_whitespace),
+        }
+    }
+}
@@ -14,22 +14,45 @@
  * limitations under the License.
  */
 
+mod alternation_order;
 mod builder;
 mod config;
+mod dedup_report;
+mod error;
+mod escape_style;
+mod exact_repetition_style;
 mod feature;
+mod flags;
+mod flavor;
+mod group_policy;
+mod input_trim;
+mod normalization;
 
 #[allow(clippy::module_inception)]
 mod regexp;
+mod stats;
 
+pub use alternation_order::AlternationOrder;
 pub use builder::RegExpBuilder;
 pub use config::RegExpConfig;
+pub use dedup_report::DedupReport;
+pub use error::RegExpError;
+pub use escape_style::EscapeStyle;
+pub use exact_repetition_style::ExactRepetitionStyle;
 pub use feature::Feature;
-pub use regexp::RegExp;
+pub use flags::RegexFlags;
+pub use flavor::RegExpFlavor;
+pub use group_policy::GroupPolicy;
+pub use input_trim::InputTrim;
+pub use normalization::Normalization;
+pub use regexp::{generate, try_generate, RegExp};
+pub use stats::RegExpStats;
 
 #[cfg(test)]
 mod tests {
     use crate::regexp::Feature;
     use crate::regexp::RegExpBuilder;
+    use crate::regexp::{generate, RegExpConfig};
 
     #[test]
     #[should_panic(expected = "No test cases have been provided for regular expression generation")]
@@ -37,6 +60,18 @@ mod tests {
         RegExpBuilder::from(&Vec::<String>::new());
     }
 
+    #[test]
+    #[should_panic(expected = "No test cases have been provided for regular expression generation")]
+    fn generate_panics_without_test_cases() {
+        generate(&Vec::<String>::new(), &RegExpConfig::new());
+    }
+
+    #[test]
+    #[should_panic(expected = "No test cases have been provided for regular expression generation")]
+    fn regexp_builder_panics_without_byte_test_cases() {
+        RegExpBuilder::from_bytes(&Vec::<Vec<u8>>::new());
+    }
+
     #[test]
     #[should_panic(
         expected = "No conversion features have been provided for regular expression generation"
@@ -62,4 +97,54 @@ mod tests {
     fn regexp_builder_panics_if_minimum_substring_length_is_zero() {
         RegExpBuilder::from(&["abc"]).with_minimum_substring_length(0);
     }
+
+    #[test]
+    #[should_panic(expected = "Minimum range length must be at least 2")]
+    fn regexp_builder_panics_if_minimum_range_length_is_less_than_two() {
+        RegExpBuilder::from(&["abc"]).with_minimum_range_length(1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Alternation to class threshold must be at least 2")]
+    fn regexp_builder_panics_if_alternation_to_class_threshold_is_less_than_two() {
+        RegExpBuilder::from(&["abc"]).with_alternation_to_class_threshold(1);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "No bracket pairs have been provided for regular expression generation"
+    )]
+    fn regexp_builder_panics_if_bracket_pairs_are_empty() {
+        RegExpBuilder::from(&["abc"]).with_optional_surrounding_brackets(&[], false);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "No noise characters have been provided for regular expression generation"
+    )]
+    fn regexp_builder_panics_if_noise_chars_are_empty() {
+        RegExpBuilder::from(&["abc"]).with_noise_chars(&[]);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "No delimiter candidates have been provided for regular expression generation"
+    )]
+    fn regexp_builder_panics_if_delimiter_candidates_are_empty() {
+        RegExpBuilder::from(&["abc"]).with_delimiter_candidates(&[]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Maximum output length must not be zero")]
+    fn regexp_builder_panics_if_max_output_length_is_zero() {
+        RegExpBuilder::from(&["abc"]).with_max_output_length(0);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "The literal delimiter must not be a letter, a digit, a whitespace character or a backslash"
+    )]
+    fn regexp_builder_panics_if_literal_delimiter_is_alphanumeric() {
+        RegExpBuilder::from(&["abc"]).with_literal_delimiter('d');
+    }
 }
@@ -0,0 +1,48 @@
+/*
+ * Copyright © 2019-2020 Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use unicode_normalization::UnicodeNormalization;
+
+/// This enum specifies the Unicode normalization form which the test cases are converted to
+/// before regular expression generation. It can be passed to method
+/// [`RegExpBuilder.with_normalization`](./struct.RegExpBuilder.html#method.with_normalization).
+#[derive(Clone, Debug, Hash, Ord, PartialOrd, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Normalization {
+    /// The test cases are processed as they are.
+    ///
+    /// This is the default.
+    None,
+
+    /// Normalization Form C, which composes characters with their combining marks where possible,
+    /// e.g. `e` followed by `U+0301 COMBINING ACUTE ACCENT` becomes `é`.
+    Nfc,
+
+    /// Normalization Form D, which decomposes characters into base characters and
+    /// combining marks, e.g. `é` becomes `e` followed by `U+0301 COMBINING ACUTE ACCENT`.
+    Nfd,
+}
+
+impl Normalization {
+    pub(crate) fn normalize(&self, value: &str) -> String {
+        match self {
+            Normalization::None => value.to_string(),
+            Normalization::Nfc => value.nfc().collect(),
+            Normalization::Nfd => value.nfd().collect(),
+        }
+    }
+}
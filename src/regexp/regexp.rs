@@ -15,130 +15,1401 @@
  */
 
 use crate::ast::Expression;
-use crate::char::{ColorizableString, GraphemeCluster};
+use crate::char::{
+    count_capturing_groups, without_colorization, ColoredString, ColorizableString, Grapheme,
+    GraphemeCluster, UNBOUNDED_REPETITIONS,
+};
 use crate::fsm::DFA;
 use crate::regexp::config::RegExpConfig;
-use colored::ColoredString;
+use crate::regexp::{
+    AlternationOrder, DedupReport, InputTrim, Normalization, RegExpError, RegExpStats, RegexFlags,
+};
 use itertools::Itertools;
-use std::cmp::Ordering;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
 use std::fmt::{Display, Formatter, Result};
+use std::fs::File;
+use std::hash::Hash;
+use std::io::{self, BufRead, BufReader, ErrorKind};
+use std::iter::once;
+use std::path::Path;
+use std::sync::Arc;
+use unicode_segmentation::UnicodeSegmentation;
 
+/// This struct represents a regular expression which has been generated
+/// by [`RegExpBuilder`](./struct.RegExpBuilder.html) from user-provided test cases.
+///
+/// It holds the [syntax tree](./ast/index.html) of the expression together with the settings
+/// it has been generated with. The `Display` implementation renders the expression,
+/// including syntax highlighting if it has been enabled.
+///
+/// ```
+/// use grex::{RegExp, RegExpConfig};
+///
+/// let regexp = RegExp::new(&["abc", "abd"], &RegExpConfig::new());
+/// assert_eq!(regexp.to_string(), "^ab[cd]$");
+/// assert!(regexp.verify(&["abc", "abd"]));
+/// assert_eq!(regexp.capturing_group_count(), 0);
+/// ```
 pub struct RegExp {
     ast: Expression,
-    config: RegExpConfig,
+    enclosing_chars: Option<Expression>,
+    dedup_report: DedupReport,
+    char_class_annotations: Vec<BTreeMap<String, Vec<usize>>>,
+    config: Arc<RegExpConfig>,
 }
 
 impl RegExp {
+    /// Generates a regular expression from the given test cases with the given settings.
+    /// This is the same as [`generate`](./fn.generate.html) but returns the `RegExp` itself
+    /// instead of its string representation.
+    ///
+    /// ⚠ Panics if `test_cases` is empty.
+    pub fn new<T: AsRef<str>>(test_cases: &[T], config: &RegExpConfig) -> Self {
+        if test_cases.is_empty() {
+            panic!("No test cases have been provided for regular expression generation");
+        }
+        let mut test_cases = test_cases
+            .iter()
+            .map(|it| it.as_ref().to_string())
+            .collect_vec();
+        Self::from(&mut test_cases, config)
+    }
+
+    /// Generates a regular expression from test cases which have already been segmented,
+    /// bypassing the grapheme segmentation of *grex*. Each inner vector holds the graphemes of
+    /// one test case in order, so that custom segmentations such as the one of ICU or
+    /// domain-specific tokens can be used. An empty inner vector denotes the empty test case.
+    ///
+    /// ```
+    /// use grex::{RegExp, RegExpConfig};
+    ///
+    /// let config = RegExpConfig::new();
+    /// let inputs = vec![
+    ///     vec!["ch".to_string(), "a".to_string()],
+    ///     vec!["c".to_string(), "a".to_string()],
+    /// ];
+    /// assert_eq!(RegExp::from_graphemes(inputs, &config).to_string(), "^(?:c|ch)a$");
+    /// assert_eq!(RegExp::new(&["cha", "ca"], &config).to_string(), "^ch?a$");
+    /// ```
+    ///
+    /// Every grapheme is matched and repeated as a whole. The settings which inspect the test
+    /// cases as plain strings are ignored, namely the detection of enclosing characters, noise
+    /// characters, number signs, decimal numbers, numbers, hexadecimal numbers and delimiters
+    /// as well as length quantification. Test cases are compared by their joined graphemes
+    /// for ordering and in the [`DedupReport`](./struct.DedupReport.html).
+    ///
+    /// ⚠ Panics if:
+    /// - `inputs` is empty
+    /// - any of the graphemes is an empty string
+    pub fn from_graphemes(inputs: Vec<Vec<String>>, config: &RegExpConfig) -> Self {
+        if inputs.is_empty() {
+            panic!("No test cases have been provided for regular expression generation");
+        }
+        if inputs.iter().flatten().any(|it| it.is_empty()) {
+            panic!("Empty graphemes are not allowed for regular expression generation");
+        }
+
+        let config = &Arc::new(config.clone());
+        let mut inputs = inputs;
+        if config.input_trim != InputTrim::None {
+            let is_blank = |grapheme: &String| {
+                config
+                    .input_trim
+                    .trim(grapheme, config.is_byte_mode)
+                    .is_empty()
+            };
+            for graphemes in inputs.iter_mut() {
+                while graphemes.last().is_some_and(is_blank) {
+                    graphemes.pop();
+                }
+                if config.input_trim == InputTrim::Both {
+                    let blank_count = graphemes.iter().take_while(|it| is_blank(it)).count();
+                    graphemes.drain(..blank_count);
+                }
+            }
+        }
+        Self::from_test_cases(&mut inputs, config)
+    }
+
+    /// Generates a regular expression from the lines of the given reader,
+    /// one test case per line. Lines may be ended with either a newline (`\n`) or
+    /// a carriage return with a line feed (`\r\n`). The final line ending is optional.
+    ///
+    /// Blank lines are treated as empty test cases unless
+    /// [`RegExpBuilder::with_skipping_of_blank_lines`](./struct.RegExpBuilder.html#method.with_skipping_of_blank_lines)
+    /// has been set in `config`.
+    ///
+    /// Returns an error if reading fails, if the data is not valid UTF-8
+    /// or if there are no test cases left to generate an expression from.
+    pub fn from_reader<R: BufRead>(reader: R, config: &RegExpConfig) -> io::Result<Self> {
+        let mut test_cases = vec![];
+        for line in reader.lines() {
+            let line = line?;
+            if !(config.is_blank_line_skipped && line.trim().is_empty()) {
+                test_cases.push(line);
+            }
+        }
+        if test_cases.is_empty() {
+            return Err(io::Error::new(
+                ErrorKind::InvalidInput,
+                "No test cases have been provided for regular expression generation",
+            ));
+        }
+        Ok(Self::from(&mut test_cases, config))
+    }
+
+    /// Generates a regular expression from the lines of the file at the given path.
+    /// This is a convenience wrapper around [`RegExp::from_reader`](#method.from_reader).
+    pub fn from_file<P: AsRef<Path>>(path: P, config: &RegExpConfig) -> io::Result<Self> {
+        let file = File::open(path)?;
+        Self::from_reader(BufReader::new(file), config)
+    }
+
+    /// Generates a regular expression from a JSON array of strings such as `["abc","abd"]`.
+    ///
+    /// Returns an error if `json` is not an array, if any of its elements
+    /// is not a string or if the array is empty.
+    #[cfg(feature = "serde")]
+    pub fn from_json(
+        json: &str,
+        config: &RegExpConfig,
+    ) -> std::result::Result<Self, serde_json::Error> {
+        let mut test_cases = serde_json::from_str::<Vec<String>>(json)?;
+        if test_cases.is_empty() {
+            return Err(serde::de::Error::custom(
+                "No test cases have been provided for regular expression generation",
+            ));
+        }
+        Ok(Self::from(&mut test_cases, config))
+    }
+
     pub(crate) fn from(test_cases: &mut Vec<String>, config: &RegExpConfig) -> Self {
+        let config = &Arc::new(config.clone());
+        if config.input_trim != InputTrim::None {
+            Self::trim(test_cases, config);
+        }
+        if config.normalization != Normalization::None && !config.is_byte_mode {
+            Self::normalize(test_cases, config);
+        }
+        Self::from_test_cases(test_cases, config)
+    }
+
+    /// Generates the regular expression from test cases which have already been trimmed
+    /// and normalized. The steps which inspect the test cases as plain strings are skipped
+    /// if the test cases have been segmented by the caller.
+    fn from_test_cases<T: TestCase>(test_cases: &mut Vec<T>, config: &Arc<RegExpConfig>) -> Self {
+        let original_test_cases = if config.is_char_class_annotated {
+            test_cases.iter().map(|it| it.joined()).collect_vec()
+        } else {
+            vec![]
+        };
+        // Duplicates are only removed by sorting if their frequencies are needed.
+        let dedup_report = match (config.is_dedup_enabled, config.is_frequency_ordered) {
+            (true, false) => Self::dedup(test_cases),
+            (true, true) => Self::dedup(&mut test_cases.clone()),
+            (false, _) => DedupReport::default(),
+        };
         if config.is_case_insensitive_matching() {
-            Self::convert_to_lowercase(test_cases);
+            *test_cases = test_cases
+                .iter()
+                .map(|it| it.to_lowercase(config))
+                .collect_vec();
+        }
+        let mut enclosing_chars = None;
+        let mut is_sign_optional = false;
+        if let Some(test_cases) = T::as_strings_mut(test_cases) {
+            if config.is_enclosing_char_detection_used() {
+                enclosing_chars = Self::strip_enclosing_chars(test_cases, config);
+            }
+            if !config.noise_chars.is_empty() {
+                Self::remove_test_cases_covered_by_noise(test_cases, config);
+            }
+            is_sign_optional =
+                config.is_optional_sign_detected && Self::strip_number_signs(test_cases);
+        }
+        let frequencies = if config.is_frequency_ordered {
+            Self::count_frequencies(test_cases)
+        } else {
+            vec![]
+        };
+        if config.is_prefix_matching_enabled {
+            *test_cases = test_cases
+                .iter()
+                .flat_map(|it| it.prefixes(config))
+                .collect_vec();
+        }
+        let unsorted_test_cases = if Self::is_input_order_restored(config) {
+            test_cases.iter().map(|it| it.joined()).collect_vec()
+        } else {
+            vec![]
+        };
+        if config.is_input_order_preserved {
+            Self::dedup(test_cases);
+        } else {
+            Self::sort(test_cases);
+        }
+        let ast = T::expression(test_cases, config);
+        let input_ordered_test_cases = if unsorted_test_cases.is_empty() {
+            test_cases.iter().map(|it| it.joined()).collect_vec()
+        } else {
+            unsorted_test_cases
+        };
+        let mut ast = Self::simplify(ast, &input_ordered_test_cases, &frequencies, config);
+        if is_sign_optional {
+            ast = Expression::prepend_optional_sign(ast, config);
+        }
+        if !config.optional_bracket_pairs.is_empty() {
+            ast = Expression::surround_with_optional_brackets(ast, config);
         }
-        Self::sort(test_cases);
-        let grapheme_clusters = Self::grapheme_clusters(&test_cases, config);
-        let dfa = DFA::from(grapheme_clusters, config);
-        let ast = Expression::from(dfa, config);
+        if config.is_longest_first {
+            ast = ast.order_prefixes_last();
+        }
+        let char_class_annotations = if config.is_char_class_annotated {
+            Self::annotate_char_classes(&original_test_cases, &ast, &enclosing_chars, config)
+        } else {
+            vec![]
+        };
         Self {
             ast,
+            enclosing_chars,
+            dedup_report,
+            char_class_annotations,
             config: config.clone(),
         }
     }
 
-    fn convert_to_lowercase(test_cases: &mut Vec<String>) {
-        std::mem::replace(
-            test_cases,
-            test_cases.iter().map(|it| it.to_lowercase()).collect_vec(),
-        );
+    /// Returns `true` if the test cases are sorted but their original order has to be restored.
+    fn is_input_order_restored(config: &RegExpConfig) -> bool {
+        config.alternation_order == AlternationOrder::Input && !config.is_input_order_preserved
     }
 
-    fn sort(test_cases: &mut Vec<String>) {
+    /// Applies the transformations of the syntax tree which are independent of
+    /// how the test cases have been segmented.
+    fn simplify(
+        mut ast: Expression,
+        test_cases: &[String],
+        frequencies: &[(Vec<char>, usize)],
+        config: &Arc<RegExpConfig>,
+    ) -> Expression {
+        if config.is_optional_char_detection_enabled {
+            ast = ast.make_inserted_graphemes_optional();
+        }
+        if config.is_prefix_factoring_enabled || config.is_suffix_factoring_enabled {
+            ast = ast.factor_common_affixes();
+        }
+        if config.is_alternation_to_class_enabled {
+            ast = ast.convert_single_char_alternations();
+        }
+        ast = ast.merge_adjacent_repetitions();
+        if config.is_input_order_preserved || config.alternation_order == AlternationOrder::Input {
+            let test_cases = test_cases
+                .iter()
+                .map(|it| it.chars().collect_vec())
+                .collect_vec();
+            ast = ast.order_alternations_by_input(&test_cases);
+        }
+        if config.is_frequency_ordered {
+            ast = ast.order_alternations_by_frequency(frequencies);
+        }
+        ast
+    }
+
+    /// Returns the number of distinct strings matched by this regular expression
+    /// or `None` if this number is unbounded or too large to be represented by `u128`.
+    ///
+    /// Shorthand character classes such as `\d` contribute the number of Unicode characters
+    /// they match. The `(?i)` flag of case-insensitive regular expressions is not taken into account.
+    pub fn match_count(&self) -> Option<u128> {
+        match &self.enclosing_chars {
+            Some(enclosing_chars) => self
+                .ast
+                .match_count()?
+                .checked_mul(enclosing_chars.match_count()?),
+            None => self.ast.match_count(),
+        }
+    }
+
+    /// Returns the plain regular expression without any ANSI escape sequences,
+    /// even if syntax highlighting has been enabled for this regular expression.
+    /// The returned string can always be fed into the [*regex*](https://crates.io/crates/regex) crate.
+    ///
+    /// In contrast, the `Display` implementation honors the syntax highlighting setting
+    /// which is useful for printing the regular expression to the console.
+    pub fn to_regex_string(&self) -> String {
+        without_colorization(|| self.to_string())
+    }
+
+    /// Returns the plain regular expression without inline flags and the flags separately.
+    /// This is useful for regular expression engines which expect the flags as a separate
+    /// argument, such as [`RegexBuilder`](https://docs.rs/regex/latest/regex/struct.RegexBuilder.html)
+    /// of the *regex* crate. Like [`to_regex_string`](#method.to_regex_string), the pattern
+    /// never contains any ANSI escape sequences and it is not wrapped by a literal delimiter.
+    pub fn to_pattern_and_flags(&self) -> (String, RegexFlags) {
+        (
+            without_colorization(|| self.format_pattern(false)),
+            self.flags(),
+        )
+    }
+
+    pub(crate) fn check_ascii_only(&self) -> std::result::Result<(), RegExpError> {
+        if !self.config.is_ascii_only {
+            return Ok(());
+        }
+        match self.to_regex_string().chars().find(|c| !c.is_ascii()) {
+            None => Ok(()),
+            Some(_) if !self.config.is_non_ascii_char_escaped => {
+                Err(RegExpError::NonAsciiCharsNotEscaped)
+            }
+            Some(c) => Err(RegExpError::NonAsciiChar(c)),
+        }
+    }
+
+    pub(crate) fn check_output_length(&self) -> std::result::Result<(), RegExpError> {
+        match self.config.max_output_length {
+            Some(max_length) if self.to_regex_string().chars().count() > max_length => {
+                Err(RegExpError::OutputTooLong(max_length))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns `true` if this regular expression matches each of the given test cases
+    /// as a whole. This is guaranteed for the test cases the expression has been generated from
+    /// as long as they have not been trimmed or normalized, so this method is useful to check
+    /// other strings or a modified [`ast`](#method.ast).
+    ///
+    /// Case-insensitive expressions are matched against the test cases in lowercase.
+    /// Otherwise, the test cases are matched as given. They are neither trimmed nor normalized,
+    /// so the original test cases are rejected if they were changed by
+    /// [`with_input_trim`](./struct.RegExpBuilder.html#method.with_input_trim) or
+    /// [`with_normalization`](./struct.RegExpBuilder.html#method.with_normalization).
+    pub fn verify<T: AsRef<str>>(&self, test_cases: &[T]) -> bool {
+        test_cases.iter().all(|test_case| {
+            let test_case = test_case.as_ref();
+            let chars = if self.config.is_case_insensitive_matching() {
+                to_lowercase(test_case, &self.config).chars().collect_vec()
+            } else {
+                test_case.chars().collect_vec()
+            };
+
+            match &self.enclosing_chars {
+                Some(enclosing_chars) => {
+                    chars.len() >= 2
+                        && chars.first() == chars.last()
+                        && enclosing_chars.is_match(&chars[..1])
+                        && self.ast.is_match(&chars[1..chars.len() - 1])
+                }
+                None => self.ast.is_match(&chars),
+            }
+        })
+    }
+
+    /// Returns the number of capturing groups in this regular expression.
+    /// This is useful for indexing the groups of a match.
+    ///
+    /// Capturing groups are only created if
+    /// [`Feature::CapturingGroup`](./enum.Feature.html#variant.CapturingGroup) is enabled
+    /// or if the enclosing characters of the test cases are detected.
+    pub fn capturing_group_count(&self) -> usize {
+        count_capturing_groups(&self.to_regex_string())
+    }
+
+    /// Returns metrics about this regular expression which are computed by walking its
+    /// syntax tree, including the enclosing characters if they have been detected.
+    ///
+    /// ```
+    /// use grex::RegExpBuilder;
+    ///
+    /// let regexp = RegExpBuilder::from(&["abc", "def"]).build_regexp();
+    /// let stats = regexp.stats();
+    /// assert_eq!(stats.distinct_grapheme_count(), 6);
+    /// assert_eq!(stats.alternation_branch_count(), 2);
+    /// assert_eq!(stats.pattern_length(), 13);
+    /// assert!(!stats.has_repetitions());
+    /// ```
+    pub fn stats(&self) -> RegExpStats {
+        let mut expressions = vec![&self.ast];
+        expressions.extend(&self.enclosing_chars);
+        RegExpStats::new(&expressions, self.to_regex_string().chars().count())
+    }
+
+    /// Describes this regular expression in plain English which is useful for teaching
+    /// and debugging, e.g. `matches 'ab' followed by one of [c, d]` for `^ab[cd]$`.
+    ///
+    /// ```
+    /// use grex::RegExpBuilder;
+    ///
+    /// let regexp = RegExpBuilder::from(&["abc", "abd"]).build_regexp();
+    /// assert_eq!(regexp.explain(), "matches 'ab' followed by one of [c, d]");
+    /// ```
+    pub fn explain(&self) -> String {
+        let mut explanation = match &self.enclosing_chars {
+            Some(enclosing_chars) => format!(
+                "matches {} followed by {} followed by the same text as at the beginning",
+                enclosing_chars.explain(),
+                self.ast.explain()
+            ),
+            None => format!("matches {}", self.ast.explain()),
+        };
+        if self.config.is_case_insensitive_matching() {
+            explanation.push_str(", ignoring case");
+        }
+        explanation
+    }
+
+    /// Returns a multi-line representation of the syntax tree of this regular expression
+    /// with one indented line per node. Unlike the `Display` implementation, the result is
+    /// not a valid regular expression itself. It is meant for inspecting why a certain
+    /// expression has been produced.
+    ///
+    /// ```
+    /// use grex::RegExpBuilder;
+    ///
+    /// let regexp = RegExpBuilder::from(&["abc", "abd"]).build_regexp();
+    /// assert_eq!(
+    ///     regexp.to_pretty_string(),
+    ///     "Concatenation\n  Literal ab\n  CharacterClass [cd]"
+    /// );
+    /// ```
+    pub fn to_pretty_string(&self) -> String {
+        without_colorization(|| {
+            let mut lines = vec![];
+            if self.config.is_case_insensitive_matching() {
+                lines.push("Flag (?i)".to_string());
+            }
+            if self.flags().contains(RegexFlags::MULTI_LINE) {
+                lines.push("Flag (?m)".to_string());
+            }
+            if self.flags().contains(RegexFlags::DOT_MATCHES_NEW_LINE) {
+                lines.push("Flag (?s)".to_string());
+            }
+            if let Some(enclosing_chars) = &self.enclosing_chars {
+                lines.push("EnclosingChars".to_string());
+                enclosing_chars.pretty_print(1, &mut lines);
+            }
+            self.ast.pretty_print(0, &mut lines);
+            lines.join("\n")
+        })
+    }
+
+    /// Returns every branch of the top-level alternation of this regular expression as
+    /// a regular expression of its own, which is useful for systems that cannot handle
+    /// large alternations. The branches are formatted with the same settings as the whole
+    /// expression, including anchors, flags and enclosing characters. If the expression
+    /// is not an alternation at the top level, it is returned as the only branch.
+    ///
+    /// ```
+    /// use grex::RegExpBuilder;
+    ///
+    /// let regexp = RegExpBuilder::from(&["abc", "def"]).build_regexp();
+    /// assert_eq!(regexp.branches(), vec!["^abc$", "^def$"]);
+    /// ```
+    pub fn branches(&self) -> Vec<String> {
+        match &self.ast {
+            Expression::Alternation(options, _) => options
+                .iter()
+                .map(|option| {
+                    Self {
+                        ast: option.clone(),
+                        enclosing_chars: self.enclosing_chars.clone(),
+                        dedup_report: DedupReport::default(),
+                        char_class_annotations: vec![],
+                        config: self.config.clone(),
+                    }
+                    .to_string()
+                })
+                .collect_vec(),
+            _ => vec![self.to_string()],
+        }
+    }
+
+    /// Returns the syntax tree of this regular expression without anchors, flags and
+    /// enclosing characters. See the [`ast`](./ast/index.html) module for how to modify it.
+    pub fn ast(&self) -> &Expression {
+        &self.ast
+    }
+
+    /// Returns the report about the duplicate test cases which have been removed
+    /// before generating this regular expression. Test cases are compared exactly,
+    /// so `a` and `A` are not considered duplicates, even for case-insensitive matching.
+    pub fn dedup_report(&self) -> &DedupReport {
+        &self.dedup_report
+    }
+
+    /// Returns one annotation for each character class in this regular expression,
+    /// in the order in which the classes appear. An annotation maps the members of its class
+    /// to the indices of the test cases whose match goes through the class with the respective
+    /// member. The list is empty unless
+    /// [`RegExpBuilder::with_char_class_annotations`](./struct.RegExpBuilder.html#method.with_char_class_annotations)
+    /// has been called.
+    pub fn char_class_annotations(&self) -> &[BTreeMap<String, Vec<usize>>] {
+        &self.char_class_annotations
+    }
+
+    fn annotate_char_classes(
+        test_cases: &[String],
+        ast: &Expression,
+        enclosing_chars: &Option<Expression>,
+        config: &Arc<RegExpConfig>,
+    ) -> Vec<BTreeMap<String, Vec<usize>>> {
+        let test_cases = test_cases
+            .iter()
+            .map(|test_case| {
+                let test_case = if config.is_case_insensitive_matching() {
+                    to_lowercase(test_case, config)
+                } else {
+                    test_case.clone()
+                };
+                test_case.chars().collect_vec()
+            })
+            .collect_vec();
+        let mut annotations = vec![];
+
+        // The enclosing chars are the first and the last char of every test case,
+        // but only the first one is matched by the character class.
+        let inner_test_cases = match enclosing_chars {
+            Some(enclosing_chars) => {
+                let matched_members = test_cases
+                    .iter()
+                    .map(|chars| {
+                        enclosing_chars.matched_class_members(chars.get(..1).unwrap_or(&[]))
+                    })
+                    .collect_vec();
+                annotations.extend(Self::annotate_char_classes_of(
+                    enclosing_chars,
+                    &matched_members,
+                ));
+                test_cases
+                    .iter()
+                    .map(|chars| chars.get(1..chars.len().saturating_sub(1)).unwrap_or(&[]))
+                    .collect_vec()
+            }
+            None => test_cases.iter().map(|chars| &chars[..]).collect_vec(),
+        };
+        let matched_members = inner_test_cases
+            .iter()
+            .map(|chars| ast.matched_class_members(chars))
+            .collect_vec();
+        annotations.extend(Self::annotate_char_classes_of(ast, &matched_members));
+        annotations
+    }
+
+    fn annotate_char_classes_of(
+        expr: &Expression,
+        matched_members: &[BTreeSet<(Vec<usize>, char)>],
+    ) -> Vec<BTreeMap<String, Vec<usize>>> {
+        expr.character_classes()
+            .into_iter()
+            .map(|(path, members)| {
+                members
+                    .iter()
+                    .map(|&member| {
+                        let indices = matched_members
+                            .iter()
+                            .enumerate()
+                            .filter(|(_, it)| it.contains(&(path.clone(), member)))
+                            .map(|(index, _)| index)
+                            .collect_vec();
+                        (member.to_string(), indices)
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn dedup<T: TestCase>(test_cases: &mut Vec<T>) -> DedupReport {
+        let mut seen_test_cases = HashSet::new();
+        let mut removed_test_cases = vec![];
+
+        test_cases.retain(|it| {
+            if seen_test_cases.insert(it.clone()) {
+                true
+            } else {
+                removed_test_cases.push(it.joined());
+                false
+            }
+        });
+
+        DedupReport::new(removed_test_cases)
+    }
+
+    fn trim(test_cases: &mut Vec<String>, config: &Arc<RegExpConfig>) {
+        *test_cases = test_cases
+            .iter()
+            .map(|it| config.input_trim.trim(it, config.is_byte_mode).to_string())
+            .collect_vec();
+    }
+
+    fn normalize(test_cases: &mut Vec<String>, config: &Arc<RegExpConfig>) {
+        *test_cases = test_cases
+            .iter()
+            .map(|it| config.normalization.normalize(it))
+            .collect_vec();
+    }
+
+    fn strip_enclosing_chars(
+        test_cases: &mut Vec<String>,
+        config: &Arc<RegExpConfig>,
+    ) -> Option<Expression> {
+        let mut enclosing_chars = BTreeSet::new();
+
+        for test_case in test_cases.iter() {
+            let mut chars = test_case.chars();
+            match (chars.next(), chars.next_back()) {
+                (Some(first_char), Some(last_char)) if first_char == last_char => {
+                    enclosing_chars.insert(first_char);
+                }
+                _ => return None,
+            }
+        }
+
+        if enclosing_chars.len() < 2 {
+            return None;
+        }
+
+        *test_cases = test_cases
+            .iter()
+            .map(|it| {
+                let mut chars = it.chars();
+                chars.next();
+                chars.next_back();
+                chars.as_str().to_string()
+            })
+            .collect_vec();
+
+        Some(Expression::CharacterClass(enclosing_chars, config.clone()))
+    }
+
+    fn strip_number_signs(test_cases: &mut Vec<String>) -> bool {
+        let is_number = |value: &str| {
+            let mut parts = value.splitn(2, '.');
+            parts.all(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_digit()))
+        };
+        let unsigned_numbers = test_cases
+            .iter()
+            .map(|it| it.strip_prefix(|c| c == '+' || c == '-'))
+            .collect_vec();
+        let is_any_number_signed = unsigned_numbers.iter().any(|it| it.is_some());
+        let are_all_numbers = test_cases
+            .iter()
+            .zip(unsigned_numbers.iter())
+            .all(|(test_case, unsigned_number)| is_number(unsigned_number.unwrap_or(test_case)));
+
+        if !is_any_number_signed || !are_all_numbers {
+            return false;
+        }
+
+        *test_cases = test_cases
+            .iter()
+            .zip(unsigned_numbers.iter())
+            .map(|(test_case, unsigned_number)| unsigned_number.unwrap_or(test_case).to_string())
+            .collect_vec();
+        true
+    }
+
+    fn remove_test_cases_covered_by_noise(
+        test_cases: &mut Vec<String>,
+        config: &Arc<RegExpConfig>,
+    ) {
+        let noise_chars = &config.noise_chars;
+        let signal = |test_case: &String| {
+            test_case
+                .chars()
+                .filter(|c| !noise_chars.contains(c))
+                .collect::<String>()
+        };
+        let noisy_signals = test_cases
+            .iter()
+            .filter(|it| it.chars().any(|c| noise_chars.contains(&c)))
+            .map(signal)
+            .collect::<HashSet<_>>();
+
+        test_cases.retain(|it| !noisy_signals.contains(it));
+    }
+
+    fn count_frequencies<T: TestCase>(test_cases: &[T]) -> Vec<(Vec<char>, usize)> {
+        let mut counts = BTreeMap::<String, usize>::new();
+        for test_case in test_cases {
+            *counts.entry(test_case.joined()).or_default() += 1;
+        }
+        counts
+            .into_iter()
+            .map(|(test_case, count)| (test_case.chars().collect_vec(), count))
+            .collect_vec()
+    }
+
+    fn sort<T: TestCase>(test_cases: &mut Vec<T>) {
         test_cases.sort();
         test_cases.dedup();
-        test_cases.sort_by(|a, b| match a.len().cmp(&b.len()) {
-            Ordering::Equal => a.cmp(&b),
-            other => other,
+        test_cases.sort_by_cached_key(|it| {
+            let test_case = it.joined();
+            (test_case.len(), test_case)
         });
     }
 
-    fn grapheme_clusters(test_cases: &[String], config: &RegExpConfig) -> Vec<GraphemeCluster> {
-        let mut clusters = test_cases
+    fn decimal_number_cluster(
+        test_cases: &[String],
+        config: &Arc<RegExpConfig>,
+    ) -> Option<GraphemeCluster> {
+        if !config.is_decimal_number_detected || test_cases.is_empty() {
+            return None;
+        }
+
+        let is_digits = |part: &str| !part.is_empty() && part.chars().all(|c| c.is_ascii_digit());
+        let mut integer_digit_counts = BTreeSet::new();
+        let mut fraction_digit_counts = BTreeSet::new();
+
+        for test_case in test_cases {
+            let (integer_part, fraction_part) = test_case.split_once('.')?;
+            if !is_digits(integer_part) || !is_digits(fraction_part) {
+                return None;
+            }
+            integer_digit_counts.insert(integer_part.len() as u32);
+            fraction_digit_counts.insert(fraction_part.len() as u32);
+        }
+
+        let digits = |counts: &BTreeSet<u32>| {
+            Grapheme::new(
+                vec!["\\d".to_string()],
+                *counts.iter().next().unwrap(),
+                *counts.iter().next_back().unwrap(),
+                config,
+            )
+        };
+
+        Some(GraphemeCluster::from_graphemes(
+            vec![
+                digits(&integer_digit_counts),
+                Grapheme::from(".", config),
+                digits(&fraction_digit_counts),
+            ],
+            config,
+        ))
+    }
+
+    fn numeric_cluster(
+        test_cases: &[String],
+        config: &Arc<RegExpConfig>,
+    ) -> Option<GraphemeCluster> {
+        if !config.is_numeric_detection_enabled || test_cases.is_empty() {
+            return None;
+        }
+
+        let mut digit_counts = BTreeSet::new();
+
+        for test_case in test_cases {
+            if test_case.is_empty() || !test_case.chars().all(|c| c.is_ascii_digit()) {
+                return None;
+            }
+            digit_counts.insert(test_case.len() as u32);
+        }
+
+        let min = *digit_counts.iter().next().unwrap();
+        let max = if config.is_repetition_unbounded {
+            UNBOUNDED_REPETITIONS
+        } else {
+            *digit_counts.iter().next_back().unwrap()
+        };
+
+        Some(GraphemeCluster::from_graphemes(
+            vec![Grapheme::new(vec!["\\d".to_string()], min, max, config)],
+            config,
+        ))
+    }
+
+    fn expression(test_cases: &[String], config: &Arc<RegExpConfig>) -> Expression {
+        match Self::hex_char_class(test_cases, config)
+            .or_else(|| Self::length_quantified_char_class(test_cases, config))
+        {
+            Some(expr) => expr,
+            None => {
+                let grapheme_clusters = match Self::decimal_number_cluster(test_cases, config)
+                    .or_else(|| Self::numeric_cluster(test_cases, config))
+                    .or_else(|| Self::grapheme_run_cluster(test_cases, config))
+                {
+                    Some(cluster) => vec![cluster],
+                    None => Self::grapheme_clusters(test_cases, config),
+                };
+                let dfa = DFA::from(grapheme_clusters, config);
+                Expression::from(dfa, config)
+            }
+        }
+    }
+
+    fn delimited_expression(
+        test_cases: &[String],
+        config: &Arc<RegExpConfig>,
+    ) -> Option<Expression> {
+        if !config.is_delimiter_detection_enabled {
+            return None;
+        }
+
+        let delimiter = Self::detect_delimiter(test_cases, &config.delimiter_candidates)?;
+        let mut fields = test_cases
             .iter()
-            .map(|it| GraphemeCluster::from(it, config))
+            .flat_map(|it| it.split(delimiter))
+            .map(|it| it.to_string())
             .collect_vec();
+        Self::sort(&mut fields);
 
-        if config.is_char_class_feature_enabled() {
-            for cluster in clusters.iter_mut() {
-                cluster.convert_to_char_classes();
+        let field = Self::expression(&fields, config);
+        let expr = Expression::new_delimited(field, delimiter, config);
+
+        let is_verified = test_cases
+            .iter()
+            .all(|it| expr.is_match(&it.chars().collect_vec()));
+        if is_verified {
+            Some(expr)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the candidate which separates the most fields, provided that it occurs
+    /// in at least one test case and none of the test cases contains an empty field.
+    /// Ties are resolved in favor of the earlier candidate.
+    fn detect_delimiter(test_cases: &[String], candidates: &[char]) -> Option<char> {
+        let mut detected_delimiter = None;
+        let mut max_count = 0;
+
+        for &candidate in candidates {
+            let is_valid = test_cases
+                .iter()
+                .all(|it| it.split(candidate).all(|field| !field.is_empty()));
+            let count = test_cases
+                .iter()
+                .map(|it| it.matches(candidate).count())
+                .sum::<usize>();
+            if is_valid && count > max_count {
+                detected_delimiter = Some(candidate);
+                max_count = count;
             }
         }
+        detected_delimiter
+    }
 
-        if config.is_repetition_converted() {
-            for cluster in clusters.iter_mut() {
-                cluster.convert_repetitions();
+    /// Returns a cluster of a single quantified grapheme if every test case is a run
+    /// of the same grapheme, so that `aaa` and `aaaaa` result in `a{3,5}`.
+    fn grapheme_run_cluster(
+        test_cases: &[String],
+        config: &Arc<RegExpConfig>,
+    ) -> Option<GraphemeCluster> {
+        if !config.is_run_detection_enabled || test_cases.is_empty() {
+            return None;
+        }
+
+        let mut value = None;
+        let mut lengths = BTreeSet::new();
+
+        for test_case in test_cases {
+            // The conversion features are applied first, so that `111` becomes a run of `\d`
+            // with digit conversion and a single grapheme `1{3}` with repetition conversion.
+            let cluster = Self::grapheme_cluster(test_case, config);
+            if cluster.is_empty() {
+                return None;
+            }
+            let mut length = 0;
+            for grapheme in cluster.graphemes() {
+                if grapheme.has_repetitions() || grapheme.minimum() != grapheme.maximum() {
+                    return None;
+                }
+                match &value {
+                    Some(it) if it != &grapheme.value() => return None,
+                    Some(_) => {}
+                    None => value = Some(grapheme.value()),
+                }
+                length += grapheme.minimum();
+            }
+            lengths.insert(length);
+        }
+
+        Some(GraphemeCluster::from_graphemes(
+            vec![Grapheme::new(
+                vec![value?],
+                *lengths.iter().next().unwrap(),
+                *lengths.iter().next_back().unwrap(),
+                config,
+            )],
+            config,
+        ))
+    }
+
+    fn hex_char_class(test_cases: &[String], config: &Arc<RegExpConfig>) -> Option<Expression> {
+        if !config.is_hex_generalization_enabled || test_cases.is_empty() {
+            return None;
+        }
+
+        let mut char_set: BTreeSet<char> = ('0'..='9').collect();
+        let mut lengths = BTreeSet::new();
+
+        for test_case in test_cases {
+            if test_case.is_empty() || !test_case.chars().all(|c| c.is_ascii_hexdigit()) {
+                return None;
+            }
+            if test_case.chars().any(|c| c.is_ascii_lowercase()) {
+                char_set.extend('a'..='f');
+            }
+            if test_case.chars().any(|c| c.is_ascii_uppercase()) {
+                char_set.extend('A'..='F');
+            }
+            lengths.insert(test_case.len() as u32);
+        }
+
+        if char_set.len() == 10 {
+            return None;
+        }
+
+        Some(Expression::new_length_quantified_char_class(
+            char_set,
+            *lengths.iter().next().unwrap(),
+            *lengths.iter().next_back().unwrap(),
+            config,
+        ))
+    }
+
+    fn length_quantified_char_class(
+        test_cases: &[String],
+        config: &Arc<RegExpConfig>,
+    ) -> Option<Expression> {
+        if !config.is_length_quantification_enabled || test_cases.is_empty() {
+            return None;
+        }
+
+        let mut char_set = BTreeSet::new();
+        let mut lengths = BTreeSet::new();
+
+        for test_case in test_cases {
+            let cluster = GraphemeCluster::from(test_case, config);
+            for grapheme in cluster.graphemes() {
+                let mut chars = grapheme.value().chars().collect_vec();
+                if chars.len() != 1 {
+                    return None;
+                }
+                char_set.insert(chars.remove(0));
             }
+            lengths.insert(cluster.size() as u32);
+        }
+
+        if lengths.len() < 2 {
+            return None;
+        }
+
+        // The characters must form a single range, such as `[a-d]`. Otherwise any set of
+        // characters would be regarded as a common class, however unrelated they are.
+        let first_char = *char_set.iter().next().unwrap();
+        let last_char = *char_set.iter().next_back().unwrap();
+        if (first_char..=last_char).nth(char_set.len()).is_some() {
+            return None;
+        }
+
+        Some(Expression::new_length_quantified_char_class(
+            char_set,
+            *lengths.iter().next().unwrap(),
+            *lengths.iter().next_back().unwrap(),
+            config,
+        ))
+    }
+
+    /// Creates the grapheme clusters of all test cases. As every test case is processed
+    /// independently, this is done in parallel if the `parallel` feature is enabled.
+    /// The order of the clusters is the order of the test cases in either case.
+    fn grapheme_clusters(
+        test_cases: &[String],
+        config: &Arc<RegExpConfig>,
+    ) -> Vec<GraphemeCluster> {
+        #[cfg(feature = "parallel")]
+        let test_cases = test_cases.par_iter();
+        #[cfg(not(feature = "parallel"))]
+        let test_cases = test_cases.iter();
+
+        test_cases
+            .map(|it| Self::grapheme_cluster(it, config))
+            .collect()
+    }
+
+    fn grapheme_cluster(test_case: &str, config: &Arc<RegExpConfig>) -> GraphemeCluster {
+        let mut cluster = GraphemeCluster::from(test_case, config);
+
+        if config.is_char_class_feature_enabled() {
+            cluster.convert_to_char_classes();
+        }
+
+        if config.is_repetition_converted() {
+            cluster.convert_repetitions();
         }
 
-        clusters
+        if !config.noise_chars.is_empty() {
+            cluster.make_noise_chars_optional();
+        }
+
+        cluster
+    }
+}
+
+/// Generates a regular expression from the given test cases using the given settings.
+///
+/// This is a shorthand for [`RegExpBuilder`](./struct.RegExpBuilder.html) which is useful
+/// if the settings are shared between several invocations.
+///
+/// ```
+/// use grex::{generate, RegExpConfig};
+///
+/// let regexp = generate(&["abc", "abd"], &RegExpConfig::new());
+/// assert_eq!(regexp, "^ab[cd]$");
+/// ```
+///
+/// ⚠ Panics if:
+/// - `test_cases` is empty
+/// - ascii-only output has been requested but cannot be guaranteed
+/// - the regular expression exceeds the maximum output length
+pub fn generate<T: AsRef<str>>(test_cases: &[T], config: &RegExpConfig) -> String {
+    try_generate(test_cases, config).unwrap_or_else(|error| panic!("{}", error))
+}
+
+/// Generates a regular expression like [`generate`](./fn.generate.html) but returns an error
+/// instead of panicking if ascii-only output has been requested but cannot be guaranteed
+/// or if the regular expression exceeds the maximum output length.
+///
+/// ⚠ Panics if `test_cases` is empty.
+pub fn try_generate<T: AsRef<str>>(
+    test_cases: &[T],
+    config: &RegExpConfig,
+) -> std::result::Result<String, RegExpError> {
+    if test_cases.is_empty() {
+        panic!("No test cases have been provided for regular expression generation");
     }
+    config.check_atomic_groups()?;
+    let mut test_cases = test_cases
+        .iter()
+        .map(|it| it.as_ref().to_string())
+        .collect_vec();
+    let regexp = RegExp::from(&mut test_cases, config);
+    regexp.check_ascii_only()?;
+    regexp.check_output_length()?;
+    Ok(regexp.to_string())
 }
 
 impl Display for RegExp {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
-        let (flag, left_anchor, left_parenthesis, right_parenthesis, right_anchor) =
-            to_colorized_string(
-                vec![
-                    if self.config.is_case_insensitive_matching() {
-                        ColorizableString::IgnoreCaseFlag
-                    } else {
-                        ColorizableString::EmptyString
-                    },
-                    ColorizableString::Caret,
-                    if self.config.is_capturing_group_enabled() {
-                        ColorizableString::CapturingLeftParenthesis
-                    } else {
-                        ColorizableString::NonCapturingLeftParenthesis
-                    },
-                    ColorizableString::RightParenthesis,
-                    ColorizableString::DollarSign,
-                ],
-                &self.config,
-            );
+        match self.config.literal_delimiter {
+            Some(delimiter) => {
+                let mut flags = String::new();
+                if self.flags().contains(RegexFlags::CASE_INSENSITIVE) {
+                    flags.push('i');
+                }
+                let is_multiline_by_default = self.config.flavor.is_multiline_by_default();
+                if self.flags().contains(RegexFlags::MULTI_LINE) && !is_multiline_by_default {
+                    flags.push('m');
+                }
+                if self.flags().contains(RegexFlags::DOT_MATCHES_NEW_LINE) {
+                    flags.push(if is_multiline_by_default { 'm' } else { 's' });
+                }
+                // There is no flag for disabling Unicode mode behind the closing delimiter.
+                let unicode_flag = if self.flags().contains(RegexFlags::UNICODE_DISABLED) {
+                    ColorizableString::UnicodeDisabledFlag
+                } else {
+                    ColorizableString::EmptyString
+                }
+                .to_colorized_string(&self.config);
+                write!(
+                    f,
+                    "{}{}{}{}{}",
+                    delimiter,
+                    unicode_flag,
+                    self.format_pattern(false),
+                    delimiter,
+                    flags
+                )
+            }
+            None => write!(f, "{}", self.format_pattern(true)),
+        }
+    }
+}
 
-        match self.ast {
-            Expression::Alternation(_, _) => write!(
-                f,
-                "{}{}{}{}{}{}",
-                flag,
-                left_anchor,
-                left_parenthesis,
-                self.ast.to_string(),
-                right_parenthesis,
-                right_anchor
-            ),
-            _ => write!(
-                f,
+impl RegExp {
+    fn flags(&self) -> RegexFlags {
+        let mut flags = RegexFlags::empty();
+        if self.config.is_case_insensitive_matching() {
+            flags.insert(RegexFlags::CASE_INSENSITIVE);
+        }
+        if self.config.is_byte_mode {
+            flags.insert(RegexFlags::UNICODE_DISABLED);
+        }
+        let contains_wildcard = self.ast.contains_wildcard()
+            || self
+                .enclosing_chars
+                .as_ref()
+                .is_some_and(|it| it.contains_wildcard());
+        if self.config.is_multiline {
+            flags.insert(RegexFlags::MULTI_LINE);
+        }
+        if self.config.is_dotall_enabled && contains_wildcard {
+            flags.insert(RegexFlags::DOT_MATCHES_NEW_LINE);
+        }
+        flags
+    }
+
+    fn format_pattern(&self, is_flag_inlined: bool) -> String {
+        let is_multiline_by_default = self.config.flavor.is_multiline_by_default();
+        let is_input_anchored = is_multiline_by_default && !self.config.is_multiline;
+        let (
+            unicode_flag,
+            case_flag,
+            left_anchor,
+            left_parenthesis,
+            right_parenthesis,
+            right_anchor,
+        ) = to_colorized_string(
+            vec![
+                if is_flag_inlined && self.flags().contains(RegexFlags::UNICODE_DISABLED) {
+                    ColorizableString::UnicodeDisabledFlag
+                } else {
+                    ColorizableString::EmptyString
+                },
+                if is_flag_inlined && self.flags().contains(RegexFlags::CASE_INSENSITIVE) {
+                    ColorizableString::IgnoreCaseFlag
+                } else {
+                    ColorizableString::EmptyString
+                },
+                if is_input_anchored {
+                    ColorizableString::InputStartAnchor
+                } else {
+                    ColorizableString::Caret
+                },
+                if self.config.is_alternation_captured() {
+                    ColorizableString::CapturingLeftParenthesis
+                } else if self.config.is_atomic_group_used() {
+                    ColorizableString::AtomicLeftParenthesis
+                } else {
+                    ColorizableString::NonCapturingLeftParenthesis
+                },
+                ColorizableString::RightParenthesis,
+                if is_input_anchored {
+                    ColorizableString::InputEndAnchor
+                } else {
+                    ColorizableString::DollarSign
+                },
+            ],
+            &self.config,
+        );
+        let multiline_flag = if is_flag_inlined
+            && self.flags().contains(RegexFlags::MULTI_LINE)
+            && !is_multiline_by_default
+        {
+            ColorizableString::MultiLineFlag
+        } else {
+            ColorizableString::EmptyString
+        }
+        .to_colorized_string(&self.config);
+        let dotall_flag =
+            if is_flag_inlined && self.flags().contains(RegexFlags::DOT_MATCHES_NEW_LINE) {
+                // Ruby's flag `(?m)` makes the dot match newlines.
+                if is_multiline_by_default {
+                    ColorizableString::MultiLineFlag
+                } else {
+                    ColorizableString::DotAllFlag
+                }
+            } else {
+                ColorizableString::EmptyString
+            }
+            .to_colorized_string(&self.config);
+
+        if let Some(enclosing_chars) = &self.enclosing_chars {
+            let capturing_parenthesis =
+                ColorizableString::CapturingLeftParenthesis.to_colorized_string(&self.config);
+            let backreference =
+                ColorizableString::Other("\\1".to_string()).to_colorized_string(&self.config);
+            let ast = match self.ast {
+                Expression::Alternation(_, _) => {
+                    format!("{}{}{}", left_parenthesis, self.ast, right_parenthesis)
+                }
+                _ => self.ast.to_string(),
+            };
+            let flag = format!(
                 "{}{}{}{}",
+                unicode_flag, case_flag, multiline_flag, dotall_flag
+            );
+            return format!(
+                "{}{}{}{}{}{}{}{}",
                 flag,
                 left_anchor,
-                self.ast.to_string(),
+                capturing_parenthesis,
+                enclosing_chars,
+                right_parenthesis,
+                ast,
+                backreference,
                 right_anchor
+            );
+        }
+
+        let flag = format!(
+            "{}{}{}{}",
+            unicode_flag, case_flag, multiline_flag, dotall_flag
+        );
+        match &self.ast {
+            Expression::Alternation(options, _) if self.is_top_level_group_suppressed() => {
+                let pipe = ColorizableString::Pipe.to_colorized_string(&self.config);
+                let branches = options
+                    .iter()
+                    .map(|option| match option {
+                        Expression::Alternation(_, _) => format!(
+                            "{}{}{}{}{}",
+                            left_anchor, left_parenthesis, option, right_parenthesis, right_anchor
+                        ),
+                        _ => format!("{}{}{}", left_anchor, option, right_anchor),
+                    })
+                    .join(&pipe.to_string());
+                format!("{}{}", flag, branches)
+            }
+            Expression::Alternation(_, _) => format!(
+                "{}{}{}{}{}{}",
+                flag, left_anchor, left_parenthesis, self.ast, right_parenthesis, right_anchor
             ),
+            _ => format!("{}{}{}{}", flag, left_anchor, self.ast, right_anchor),
+        }
+    }
+
+    fn is_top_level_group_suppressed(&self) -> bool {
+        self.config.is_top_level_group_suppressed
+            && !self.config.is_alternation_captured()
+            && !self.config.is_atomic_group_used()
+    }
+}
+
+/// A test case as it passes through the steps of regular expression generation,
+/// either as a plain string or as the graphemes it has been segmented into by the caller.
+trait TestCase: Clone + Eq + Hash + Ord {
+    fn joined(&self) -> String;
+
+    fn to_lowercase(&self, config: &Arc<RegExpConfig>) -> Self;
+
+    /// Returns all non-empty prefixes of this test case, ending with the test case itself.
+    fn prefixes(&self, config: &Arc<RegExpConfig>) -> Vec<Self>;
+
+    /// Returns the test cases as plain strings for the steps which inspect them as such,
+    /// or `None` if they have been segmented and these steps are skipped.
+    fn as_strings_mut(test_cases: &mut Vec<Self>) -> Option<&mut Vec<String>>;
+
+    fn expression(test_cases: &[Self], config: &Arc<RegExpConfig>) -> Expression;
+}
+
+impl TestCase for String {
+    fn joined(&self) -> String {
+        self.clone()
+    }
+
+    fn to_lowercase(&self, config: &Arc<RegExpConfig>) -> Self {
+        to_lowercase(self, config)
+    }
+
+    /// Prefixes are built grapheme by grapheme,
+    /// so a character is never separated from its combining marks.
+    fn prefixes(&self, config: &Arc<RegExpConfig>) -> Vec<Self> {
+        let prefix_ends = if config.is_byte_mode {
+            self.char_indices().map(|(idx, _)| idx).collect_vec()
+        } else {
+            self.grapheme_indices(true)
+                .map(|(idx, _)| idx)
+                .collect_vec()
+        };
+        prefix_ends
+            .into_iter()
+            .skip(1)
+            .chain(once(self.len()))
+            .map(|idx| self[..idx].to_string())
+            .collect_vec()
+    }
+
+    fn as_strings_mut(test_cases: &mut Vec<Self>) -> Option<&mut Vec<String>> {
+        Some(test_cases)
+    }
+
+    fn expression(test_cases: &[Self], config: &Arc<RegExpConfig>) -> Expression {
+        RegExp::delimited_expression(test_cases, config)
+            .unwrap_or_else(|| RegExp::expression(test_cases, config))
+    }
+}
+
+impl TestCase for Vec<String> {
+    fn joined(&self) -> String {
+        self.concat()
+    }
+
+    fn to_lowercase(&self, config: &Arc<RegExpConfig>) -> Self {
+        self.iter()
+            .map(|grapheme| to_lowercase(grapheme, config))
+            .collect_vec()
+    }
+
+    fn prefixes(&self, _config: &Arc<RegExpConfig>) -> Vec<Self> {
+        if self.is_empty() {
+            return vec![vec![]];
         }
+        (1..=self.len())
+            .map(|length| self[..length].to_vec())
+            .collect_vec()
+    }
+
+    fn as_strings_mut(_test_cases: &mut Vec<Self>) -> Option<&mut Vec<String>> {
+        None
+    }
+
+    fn expression(test_cases: &[Self], config: &Arc<RegExpConfig>) -> Expression {
+        let grapheme_clusters = test_cases
+            .iter()
+            .map(|graphemes| {
+                let mut cluster = GraphemeCluster::from_graphemes(
+                    graphemes
+                        .iter()
+                        .map(|it| Grapheme::from(it, config))
+                        .collect_vec(),
+                    config,
+                );
+                if config.is_char_class_feature_enabled() {
+                    cluster.convert_to_char_classes();
+                }
+                if config.is_repetition_converted() {
+                    cluster.convert_repetitions();
+                }
+                cluster
+            })
+            .collect_vec();
+        Expression::from(DFA::from(grapheme_clusters, config), config)
     }
 }
 
+fn to_lowercase(s: &str, config: &Arc<RegExpConfig>) -> String {
+    if config.is_byte_mode {
+        return s.to_ascii_lowercase();
+    }
+    s.chars()
+        .flat_map(|c| {
+            let lowercase = c.to_lowercase();
+            if lowercase.len() == 1 {
+                lowercase.collect_vec()
+            } else {
+                vec![c]
+            }
+        })
+        .collect()
+}
+
 fn to_colorized_string(
     strings: Vec<ColorizableString>,
-    config: &RegExpConfig,
+    config: &Arc<RegExpConfig>,
 ) -> (
     ColoredString,
     ColoredString,
     ColoredString,
     ColoredString,
     ColoredString,
+    ColoredString,
 ) {
     let v = strings
         .iter()
-        .map(|it| it.to_colorized_string(config.is_output_colorized))
+        .map(|it| it.to_colorized_string(config))
         .collect_vec();
 
     (
@@ -147,5 +1418,6 @@ fn to_colorized_string(
         v[2].clone(),
         v[3].clone(),
         v[4].clone(),
+        v[5].clone(),
     )
 }
@@ -0,0 +1,110 @@
+/*
+ * Copyright © 2019-2020 Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::ast::Expression;
+use crate::char::Grapheme;
+use std::collections::BTreeSet;
+
+/// This struct contains metrics about a [`RegExp`](./struct.RegExp.html) which help
+/// to tune the settings of [`RegExpBuilder`](./struct.RegExpBuilder.html), e.g. to decide
+/// whether the alternations are large enough to be worth factoring.
+/// It is returned by [`RegExp::stats`](./struct.RegExp.html#method.stats).
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct RegExpStats {
+    distinct_grapheme_count: usize,
+    alternation_branch_count: usize,
+    pattern_length: usize,
+    has_repetitions: bool,
+}
+
+impl RegExpStats {
+    pub(crate) fn new(expressions: &[&Expression], pattern_length: usize) -> Self {
+        let mut graphemes = BTreeSet::new();
+        let mut stats = Self {
+            pattern_length,
+            ..Self::default()
+        };
+        for expr in expressions {
+            stats.collect(expr, &mut graphemes);
+        }
+        stats.distinct_grapheme_count = graphemes.len();
+        stats
+    }
+
+    /// Returns the number of distinct graphemes within the literals of the regular expression.
+    /// Shorthand classes such as `\d` created by the conversion features count as graphemes,
+    /// whereas members of character classes are not taken into account.
+    pub fn distinct_grapheme_count(&self) -> usize {
+        self.distinct_grapheme_count
+    }
+
+    /// Returns the number of branches of all alternations in the regular expression,
+    /// so that `^(?:a|b(?:c|d))$` has four branches. Character classes are not counted
+    /// as alternations.
+    pub fn alternation_branch_count(&self) -> usize {
+        self.alternation_branch_count
+    }
+
+    /// Returns the number of characters of the regular expression,
+    /// excluding any ANSI escape sequences added by syntax highlighting.
+    pub fn pattern_length(&self) -> usize {
+        self.pattern_length
+    }
+
+    /// Returns `true` if the regular expression contains any quantified repetition,
+    /// such as `a{2}` or `(?:ab)+`. Optional parts marked with `?` count as repetitions as well.
+    pub fn has_repetitions(&self) -> bool {
+        self.has_repetitions
+    }
+
+    fn collect(&mut self, expr: &Expression, graphemes: &mut BTreeSet<String>) {
+        match expr {
+            Expression::Alternation(options, _) => {
+                self.alternation_branch_count += options.len();
+                for option in options {
+                    self.collect(option, graphemes);
+                }
+            }
+            Expression::CharacterClass(_, _) | Expression::CharacterRange(_, _) => {}
+            Expression::Concatenation(expr1, expr2, _) => {
+                self.collect(expr1, graphemes);
+                self.collect(expr2, graphemes);
+            }
+            Expression::Literal(cluster, _) => {
+                for grapheme in cluster.graphemes() {
+                    self.collect_grapheme(grapheme, graphemes);
+                }
+            }
+            Expression::Repetition(expr, _, _) => {
+                self.has_repetitions = true;
+                self.collect(expr, graphemes);
+            }
+        }
+    }
+
+    fn collect_grapheme(&mut self, grapheme: &Grapheme, graphemes: &mut BTreeSet<String>) {
+        if grapheme.minimum() != 1 || grapheme.maximum() != 1 {
+            self.has_repetitions = true;
+        }
+        if grapheme.has_repetitions() {
+            for repeated_grapheme in grapheme.repetitions.iter() {
+                self.collect_grapheme(repeated_grapheme, graphemes);
+            }
+        } else {
+            graphemes.extend(grapheme.chars().iter().cloned());
+        }
+    }
+}
@@ -15,9 +15,11 @@
  */
 
 mod decimal;
+mod emoji;
 mod space;
 mod word;
 
 pub use decimal::DECIMAL_NUMBER;
+pub use emoji::EXTENDED_PICTOGRAPHIC;
 pub use space::WHITE_SPACE;
 pub use word::WORD;
@@ -0,0 +1,75 @@
+/*
+ * Copyright © 2019-2020 Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+use crate::regexp::{try_generate, RegExpConfig};
+use wasm_bindgen::prelude::*;
+
+/// Generates a regular expression from a JavaScript array of strings.
+///
+/// `config` is either `undefined`, `null` or an object with the same fields as the
+/// serialized [`RegExpConfig`](./struct.RegExpConfig.html). Missing fields take their
+/// default values. Colorized output is always disabled as it requires a terminal.
+///
+/// Throws a JavaScript error if the arguments cannot be converted,
+/// if no test cases are given or if the expression cannot be generated.
+#[wasm_bindgen(js_name = generate)]
+pub fn generate_wasm(inputs: JsValue, config: JsValue) -> String {
+    let test_cases: Vec<String> = serde_wasm_bindgen::from_value(inputs)
+        .unwrap_or_else(|error| wasm_bindgen::throw_str(&error.to_string()));
+    let mut config: RegExpConfig = if config.is_undefined() || config.is_null() {
+        RegExpConfig::new()
+    } else {
+        serde_wasm_bindgen::from_value(config)
+            .unwrap_or_else(|error| wasm_bindgen::throw_str(&error.to_string()))
+    };
+    config.is_output_colorized = false;
+
+    if test_cases.is_empty() {
+        wasm_bindgen::throw_str(
+            "No test cases have been provided for regular expression generation",
+        );
+    }
+
+    try_generate(&test_cases, &config)
+        .unwrap_or_else(|error| wasm_bindgen::throw_str(&error.to_string()))
+}
+
+#[cfg(all(test, target_arch = "wasm32"))]
+mod tests {
+    use super::*;
+    use crate::regexp::Feature;
+    use wasm_bindgen_test::*;
+
+    fn to_js_array(test_cases: &[&str]) -> JsValue {
+        serde_wasm_bindgen::to_value(test_cases).unwrap()
+    }
+
+    #[wasm_bindgen_test]
+    fn generate_wasm_with_default_config() {
+        let regexp = generate_wasm(to_js_array(&["a", "aa", "aaa"]), JsValue::UNDEFINED);
+        assert_eq!(regexp, "^a(?:aa?)?$");
+    }
+
+    #[wasm_bindgen_test]
+    fn generate_wasm_ignores_colorized_output() {
+        let mut config = RegExpConfig::new();
+        config.conversion_features = vec![Feature::Digit];
+        config.is_output_colorized = true;
+        let config = serde_wasm_bindgen::to_value(&config).unwrap();
+        let regexp = generate_wasm(to_js_array(&["1", "23"]), config);
+        assert_eq!(regexp, "^\\d(?:\\d)?$");
+    }
+}
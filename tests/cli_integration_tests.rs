@@ -31,7 +31,7 @@ mod no_conversion {
         #[test]
         fn succeeds() {
             let mut grex = init_command();
-            grex.args(&[TEST_CASE]);
+            grex.args([TEST_CASE]);
             grex.assert()
                 .success()
                 .stdout(predicate::eq("^I   ♥♥♥ 36 and ٣ and y̆y̆ and 💩💩\\.$\n"));
@@ -40,7 +40,7 @@ mod no_conversion {
         #[test]
         fn succeeds_with_ignore_case_option() {
             let mut grex = init_command();
-            grex.args(&["--ignore-case", "Ä@Ö€Ü", "ä@ö€ü", "Ä@ö€Ü", "ä@Ö€ü"]);
+            grex.args(["--ignore-case", "Ä@Ö€Ü", "ä@ö€ü", "Ä@ö€Ü", "ä@Ö€ü"]);
             grex.assert()
                 .success()
                 .stdout(predicate::eq("(?i)^ä@ö€ü$\n"));
@@ -49,7 +49,7 @@ mod no_conversion {
         #[test]
         fn succeeds_with_leading_hyphen() {
             let mut grex = init_command();
-            grex.args(&["-a", "b", "c"]);
+            grex.args(["-a", "b", "c"]);
             grex.assert()
                 .success()
                 .stdout(predicate::eq("^(?:\\-a|[bc])$\n"));
@@ -58,7 +58,7 @@ mod no_conversion {
         #[test]
         fn succeeds_with_escape_option() {
             let mut grex = init_command();
-            grex.args(&["--escape", TEST_CASE]);
+            grex.args(["--escape", TEST_CASE]);
             grex.assert().success().stdout(predicate::eq(
                 "^I   \\u{2665}\\u{2665}\\u{2665} 36 and \\u{663} and y\\u{306}y\\u{306} and \\u{1f4a9}\\u{1f4a9}\\.$\n",
             ));
@@ -67,7 +67,7 @@ mod no_conversion {
         #[test]
         fn succeeds_with_escape_and_surrogate_option() {
             let mut grex = init_command();
-            grex.args(&["--escape", "--with-surrogates", TEST_CASE]);
+            grex.args(["--escape", "--with-surrogates", TEST_CASE]);
             grex.assert().success().stdout(predicate::eq(
                 "^I   \\u{2665}\\u{2665}\\u{2665} 36 and \\u{663} and y\\u{306}y\\u{306} and \\u{d83d}\\u{dca9}\\u{d83d}\\u{dca9}\\.$\n",
             ));
@@ -80,16 +80,16 @@ mod no_conversion {
             writeln!(file, "a\nb\\n\n\nc\näöü\n♥");
 
             let mut grex = init_command();
-            grex.args(&["-f", file.path().to_str().unwrap()]);
+            grex.args(["-f", file.path().to_str().unwrap()]);
             grex.assert()
                 .success()
-                .stdout(predicate::eq("^(?:b\\\\n|äöü|[ac♥])$\n"));
+                .stdout(predicate::eq("^(?:b\\\\n|äöü|[ac♥])?$\n"));
         }
 
         #[test]
         fn fails_with_surrogate_but_without_escape_option() {
             let mut grex = init_command();
-            grex.args(&["--with-surrogates", TEST_CASE]);
+            grex.args(["--with-surrogates", TEST_CASE]);
             grex.assert().failure().stderr(predicate::str::contains(
                 "required arguments were not provided",
             ));
@@ -115,7 +115,7 @@ mod no_conversion {
         #[test]
         fn fails_when_file_does_not_exist() {
             let mut grex = init_command();
-            grex.args(&["-f", "/path/to/non-existing/file"]);
+            grex.args(["-f", "/path/to/non-existing/file"]);
             grex.assert()
                 .success()
                 .stdout(predicate::str::is_empty())
@@ -127,8 +127,8 @@ mod no_conversion {
         #[test]
         fn fails_with_both_direct_and_file_input() {
             let mut grex = init_command();
-            grex.args(&[TEST_CASE]);
-            grex.args(&["-f", "/path/to/some/file"]);
+            grex.args([TEST_CASE]);
+            grex.args(["-f", "/path/to/some/file"]);
             grex.assert().failure().stderr(predicate::str::contains(
                 "argument '--file <FILE>' cannot be used with 'input'",
             ));
@@ -141,7 +141,7 @@ mod no_conversion {
         #[test]
         fn succeeds() {
             let mut grex = init_command();
-            grex.args(&["--repetitions", TEST_CASE]);
+            grex.args(["--repetitions", TEST_CASE]);
             grex.assert().success().stdout(predicate::eq(
                 "^I {3}♥{3} 36 and ٣ and (?:y̆){2} and 💩{2}\\.$\n",
             ));
@@ -150,7 +150,7 @@ mod no_conversion {
         #[test]
         fn succeeds_with_ignore_case_option() {
             let mut grex = init_command();
-            grex.args(&["--repetitions", "--ignore-case", "ÄÖÜäöü@Ö€", "äöüÄöÜ@ö€"]);
+            grex.args(["--repetitions", "--ignore-case", "ÄÖÜäöü@Ö€", "äöüÄöÜ@ö€"]);
             grex.assert()
                 .success()
                 .stdout(predicate::eq("(?i)^(?:äöü){2}@ö€$\n"));
@@ -159,7 +159,7 @@ mod no_conversion {
         #[test]
         fn succeeds_with_escape_option() {
             let mut grex = init_command();
-            grex.args(&["--repetitions", "--escape", TEST_CASE]);
+            grex.args(["--repetitions", "--escape", TEST_CASE]);
             grex.assert().success().stdout(predicate::eq(
                 "^I {3}\\u{2665}{3} 36 and \\u{663} and (?:y\\u{306}){2} and \\u{1f4a9}{2}\\.$\n",
             ));
@@ -168,7 +168,7 @@ mod no_conversion {
         #[test]
         fn succeeds_with_escape_and_surrogate_option() {
             let mut grex = init_command();
-            grex.args(&["--repetitions", "--escape", "--with-surrogates", TEST_CASE]);
+            grex.args(["--repetitions", "--escape", "--with-surrogates", TEST_CASE]);
             grex.assert().success().stdout(predicate::eq(
                 "^I {3}\\u{2665}{3} 36 and \\u{663} and (?:y\\u{306}){2} and (?:\\u{d83d}\\u{dca9}){2}\\.$\n",
             ));
@@ -177,7 +177,7 @@ mod no_conversion {
         #[test]
         fn succeeds_with_increased_minimum_repetitions() {
             let mut grex = init_command();
-            grex.args(&["--repetitions", "--min-repetitions", "2", TEST_CASE]);
+            grex.args(["--repetitions", "--min-repetitions", "2", TEST_CASE]);
             grex.assert()
                 .success()
                 .stdout(predicate::eq("^I {3}♥{3} 36 and ٣ and y̆y̆ and 💩💩\\.$\n"));
@@ -186,7 +186,7 @@ mod no_conversion {
         #[test]
         fn succeeds_with_increased_minimum_substring_length() {
             let mut grex = init_command();
-            grex.args(&["--repetitions", "--min-substring-length", "2", TEST_CASE]);
+            grex.args(["--repetitions", "--min-substring-length", "2", TEST_CASE]);
             grex.assert().success().stdout(predicate::eq(
                 "^I   ♥♥♥ 36 and ٣ and (?:y̆){2} and 💩💩\\.$\n",
             ));
@@ -195,7 +195,7 @@ mod no_conversion {
         #[test]
         fn fails_with_minimum_repetitions_equal_to_zero() {
             let mut grex = init_command();
-            grex.args(&["--min-repetitions", "0", TEST_CASE]);
+            grex.args(["--min-repetitions", "0", TEST_CASE]);
             grex.assert()
                 .failure()
                 .stderr(predicate::str::contains("Value must not be zero"));
@@ -204,7 +204,7 @@ mod no_conversion {
         #[test]
         fn fails_with_minimum_repetitions_equal_to_invalid_value() {
             let mut grex = init_command();
-            grex.args(&["--min-repetitions", "§!$", TEST_CASE]);
+            grex.args(["--min-repetitions", "§!$", TEST_CASE]);
             grex.assert().failure().stderr(predicate::str::contains(
                 "Value is not a valid unsigned integer",
             ));
@@ -213,7 +213,7 @@ mod no_conversion {
         #[test]
         fn fails_with_minimum_substring_length_equal_to_zero() {
             let mut grex = init_command();
-            grex.args(&["--min-substring-length", "0", TEST_CASE]);
+            grex.args(["--min-substring-length", "0", TEST_CASE]);
             grex.assert()
                 .failure()
                 .stderr(predicate::str::contains("Value must not be zero"));
@@ -222,7 +222,7 @@ mod no_conversion {
         #[test]
         fn fails_with_minimum_substring_length_equal_to_invalid_value() {
             let mut grex = init_command();
-            grex.args(&["--min-substring-length", "§!$", TEST_CASE]);
+            grex.args(["--min-substring-length", "§!$", TEST_CASE]);
             grex.assert().failure().stderr(predicate::str::contains(
                 "Value is not a valid unsigned integer",
             ));
@@ -239,7 +239,7 @@ mod digit_conversion {
         #[test]
         fn succeeds() {
             let mut grex = init_command();
-            grex.args(&["--digits", TEST_CASE]);
+            grex.args(["--digits", TEST_CASE]);
             grex.assert().success().stdout(predicate::eq(
                 "^I   ♥♥♥ \\d\\d and \\d and y̆y̆ and 💩💩\\.$\n",
             ));
@@ -248,7 +248,7 @@ mod digit_conversion {
         #[test]
         fn succeeds_with_escape_option() {
             let mut grex = init_command();
-            grex.args(&["--digits", "--escape", TEST_CASE]);
+            grex.args(["--digits", "--escape", TEST_CASE]);
             grex.assert().success().stdout(predicate::eq(
                 "^I   \\u{2665}\\u{2665}\\u{2665} \\d\\d and \\d and y\\u{306}y\\u{306} and \\u{1f4a9}\\u{1f4a9}\\.$\n",
             ));
@@ -257,7 +257,7 @@ mod digit_conversion {
         #[test]
         fn succeeds_with_escape_and_surrogate_option() {
             let mut grex = init_command();
-            grex.args(&["--digits", "--escape", "--with-surrogates", TEST_CASE]);
+            grex.args(["--digits", "--escape", "--with-surrogates", TEST_CASE]);
             grex.assert().success().stdout(predicate::eq(
                 "^I   \\u{2665}\\u{2665}\\u{2665} \\d\\d and \\d and y\\u{306}y\\u{306} and \\u{d83d}\\u{dca9}\\u{d83d}\\u{dca9}\\.$\n"
             ));
@@ -266,7 +266,7 @@ mod digit_conversion {
         #[test]
         fn succeeds_with_capturing_groups_option() {
             let mut grex = init_command();
-            grex.args(&["--capture-groups", "abc", "def"]);
+            grex.args(["--capture-groups", "abc", "def"]);
             grex.assert()
                 .success()
                 .stdout(predicate::eq("^(abc|def)$\n"));
@@ -279,7 +279,7 @@ mod digit_conversion {
         #[test]
         fn succeeds() {
             let mut grex = init_command();
-            grex.args(&["--repetitions", "--digits", TEST_CASE]);
+            grex.args(["--repetitions", "--digits", TEST_CASE]);
             grex.assert().success().stdout(predicate::eq(
                 "^I {3}♥{3} \\d(?:\\d and ){2}(?:y̆){2} and 💩{2}\\.$\n",
             ));
@@ -288,7 +288,7 @@ mod digit_conversion {
         #[test]
         fn succeeds_with_escape_option() {
             let mut grex = init_command();
-            grex.args(&["--repetitions", "--digits", "--escape", TEST_CASE]);
+            grex.args(["--repetitions", "--digits", "--escape", TEST_CASE]);
             grex.assert().success().stdout(predicate::eq(
                 "^I {3}\\u{2665}{3} \\d(?:\\d and ){2}(?:y\\u{306}){2} and \\u{1f4a9}{2}\\.$\n",
             ));
@@ -297,7 +297,7 @@ mod digit_conversion {
         #[test]
         fn succeeds_with_escape_and_surrogate_option() {
             let mut grex = init_command();
-            grex.args(&[
+            grex.args([
                 "--repetitions",
                 "--digits",
                 "--escape",
@@ -312,7 +312,7 @@ mod digit_conversion {
         #[test]
         fn succeeds_with_increased_minimum_repetitions() {
             let mut grex = init_command();
-            grex.args(&[
+            grex.args([
                 "--repetitions",
                 "--min-repetitions",
                 "2",
@@ -327,7 +327,7 @@ mod digit_conversion {
         #[test]
         fn succeeds_with_increased_minimum_substring_length() {
             let mut grex = init_command();
-            grex.args(&[
+            grex.args([
                 "--repetitions",
                 "--min-substring-length",
                 "2",
@@ -350,7 +350,7 @@ mod space_conversion {
         #[test]
         fn succeeds() {
             let mut grex = init_command();
-            grex.args(&["--spaces", TEST_CASE]);
+            grex.args(["--spaces", TEST_CASE]);
             grex.assert().success().stdout(predicate::eq(
                 "^I\\s\\s\\s♥♥♥\\s36\\sand\\s٣\\sand\\sy̆y̆\\sand\\s💩💩\\.$\n",
             ));
@@ -359,7 +359,7 @@ mod space_conversion {
         #[test]
         fn succeeds_with_escape_option() {
             let mut grex = init_command();
-            grex.args(&["--spaces", "--escape", TEST_CASE]);
+            grex.args(["--spaces", "--escape", TEST_CASE]);
             grex.assert().success().stdout(predicate::eq(
                 "^I\\s\\s\\s\\u{2665}\\u{2665}\\u{2665}\\s36\\sand\\s\\u{663}\\sand\\sy\\u{306}y\\u{306}\\sand\\s\\u{1f4a9}\\u{1f4a9}\\.$\n"
             ));
@@ -368,7 +368,7 @@ mod space_conversion {
         #[test]
         fn succeeds_with_escape_and_surrogate_option() {
             let mut grex = init_command();
-            grex.args(&["--spaces", "--escape", "--with-surrogates", TEST_CASE]);
+            grex.args(["--spaces", "--escape", "--with-surrogates", TEST_CASE]);
             grex.assert().success().stdout(predicate::eq(
                 "^I\\s\\s\\s\\u{2665}\\u{2665}\\u{2665}\\s36\\sand\\s\\u{663}\\sand\\sy\\u{306}y\\u{306}\\sand\\s\\u{d83d}\\u{dca9}\\u{d83d}\\u{dca9}\\.$\n"
             ));
@@ -381,7 +381,7 @@ mod space_conversion {
         #[test]
         fn succeeds() {
             let mut grex = init_command();
-            grex.args(&["--repetitions", "--spaces", TEST_CASE]);
+            grex.args(["--repetitions", "--spaces", TEST_CASE]);
             grex.assert().success().stdout(predicate::eq(
                 "^I\\s{3}♥{3}\\s36\\sand\\s٣\\sand\\s(?:y̆){2}\\sand\\s💩{2}\\.$\n",
             ));
@@ -390,7 +390,7 @@ mod space_conversion {
         #[test]
         fn succeeds_with_escape_option() {
             let mut grex = init_command();
-            grex.args(&["--repetitions", "--spaces", "--escape", TEST_CASE]);
+            grex.args(["--repetitions", "--spaces", "--escape", TEST_CASE]);
             grex.assert().success().stdout(predicate::eq(
                 "^I\\s{3}\\u{2665}{3}\\s36\\sand\\s\\u{663}\\sand\\s(?:y\\u{306}){2}\\sand\\s\\u{1f4a9}{2}\\.$\n",
             ));
@@ -399,7 +399,7 @@ mod space_conversion {
         #[test]
         fn succeeds_with_escape_and_surrogate_option() {
             let mut grex = init_command();
-            grex.args(&[
+            grex.args([
                 "--repetitions",
                 "--spaces",
                 "--escape",
@@ -422,7 +422,7 @@ mod word_conversion {
         #[test]
         fn succeeds() {
             let mut grex = init_command();
-            grex.args(&["--words", TEST_CASE]);
+            grex.args(["--words", TEST_CASE]);
             grex.assert().success().stdout(predicate::eq(
                 "^\\w   ♥♥♥ \\w\\w \\w\\w\\w \\w \\w\\w\\w \\w\\w\\w\\w \\w\\w\\w 💩💩\\.$\n",
             ));
@@ -431,7 +431,7 @@ mod word_conversion {
         #[test]
         fn succeeds_with_escape_option() {
             let mut grex = init_command();
-            grex.args(&["--words", "--escape", TEST_CASE]);
+            grex.args(["--words", "--escape", TEST_CASE]);
             grex.assert().success().stdout(predicate::eq(
                 "^\\w   \\u{2665}\\u{2665}\\u{2665} \\w\\w \\w\\w\\w \\w \\w\\w\\w \\w\\w\\w\\w \\w\\w\\w \\u{1f4a9}\\u{1f4a9}\\.$\n"
             ));
@@ -440,7 +440,7 @@ mod word_conversion {
         #[test]
         fn succeeds_with_escape_and_surrogate_option() {
             let mut grex = init_command();
-            grex.args(&["--words", "--escape", "--with-surrogates", TEST_CASE]);
+            grex.args(["--words", "--escape", "--with-surrogates", TEST_CASE]);
             grex.assert().success().stdout(predicate::eq(
                 "^\\w   \\u{2665}\\u{2665}\\u{2665} \\w\\w \\w\\w\\w \\w \\w\\w\\w \\w\\w\\w\\w \\w\\w\\w \\u{d83d}\\u{dca9}\\u{d83d}\\u{dca9}\\.$\n"
             ));
@@ -453,7 +453,7 @@ mod word_conversion {
         #[test]
         fn succeeds() {
             let mut grex = init_command();
-            grex.args(&["--repetitions", "--words", TEST_CASE]);
+            grex.args(["--repetitions", "--words", TEST_CASE]);
             grex.assert().success().stdout(predicate::eq(
                 "^\\w {3}♥{3} \\w{2} \\w{3} \\w \\w{3} \\w{4} \\w{3} 💩{2}\\.$\n",
             ));
@@ -462,7 +462,7 @@ mod word_conversion {
         #[test]
         fn succeeds_with_escape_option() {
             let mut grex = init_command();
-            grex.args(&["--repetitions", "--words", "--escape", TEST_CASE]);
+            grex.args(["--repetitions", "--words", "--escape", TEST_CASE]);
             grex.assert().success().stdout(predicate::eq(
                 "^\\w {3}\\u{2665}{3} \\w{2} \\w{3} \\w \\w{3} \\w{4} \\w{3} \\u{1f4a9}{2}\\.$\n",
             ));
@@ -471,7 +471,7 @@ mod word_conversion {
         #[test]
         fn succeeds_with_escape_and_surrogate_option() {
             let mut grex = init_command();
-            grex.args(&[
+            grex.args([
                 "--repetitions",
                 "--words",
                 "--escape",
@@ -494,7 +494,7 @@ mod digit_space_conversion {
         #[test]
         fn succeeds() {
             let mut grex = init_command();
-            grex.args(&["--digits", "--spaces", TEST_CASE]);
+            grex.args(["--digits", "--spaces", TEST_CASE]);
             grex.assert().success().stdout(predicate::eq(
                 "^I\\s\\s\\s♥♥♥\\s\\d\\d\\sand\\s\\d\\sand\\sy̆y̆\\sand\\s💩💩\\.$\n",
             ));
@@ -503,7 +503,7 @@ mod digit_space_conversion {
         #[test]
         fn succeeds_with_escape_option() {
             let mut grex = init_command();
-            grex.args(&["--digits", "--spaces", "--escape", TEST_CASE]);
+            grex.args(["--digits", "--spaces", "--escape", TEST_CASE]);
             grex.assert().success().stdout(predicate::eq(
                 "^I\\s\\s\\s\\u{2665}\\u{2665}\\u{2665}\\s\\d\\d\\sand\\s\\d\\sand\\sy\\u{306}y\\u{306}\\sand\\s\\u{1f4a9}\\u{1f4a9}\\.$\n"
             ));
@@ -512,7 +512,7 @@ mod digit_space_conversion {
         #[test]
         fn succeeds_with_escape_and_surrogate_option() {
             let mut grex = init_command();
-            grex.args(&[
+            grex.args([
                 "--digits",
                 "--spaces",
                 "--escape",
@@ -531,7 +531,7 @@ mod digit_space_conversion {
         #[test]
         fn succeeds() {
             let mut grex = init_command();
-            grex.args(&["--repetitions", "--digits", "--spaces", TEST_CASE]);
+            grex.args(["--repetitions", "--digits", "--spaces", TEST_CASE]);
             grex.assert().success().stdout(predicate::eq(
                 "^I\\s{3}♥{3}\\s\\d(?:\\d\\sand\\s){2}(?:y̆){2}\\sand\\s💩{2}\\.$\n",
             ));
@@ -540,7 +540,7 @@ mod digit_space_conversion {
         #[test]
         fn succeeds_with_escape_option() {
             let mut grex = init_command();
-            grex.args(&[
+            grex.args([
                 "--repetitions",
                 "--digits",
                 "--spaces",
@@ -555,7 +555,7 @@ mod digit_space_conversion {
         #[test]
         fn succeeds_with_escape_and_surrogate_option() {
             let mut grex = init_command();
-            grex.args(&[
+            grex.args([
                 "--repetitions",
                 "--digits",
                 "--spaces",
@@ -579,7 +579,7 @@ mod digit_word_conversion {
         #[test]
         fn succeeds() {
             let mut grex = init_command();
-            grex.args(&["--digits", "--words", TEST_CASE]);
+            grex.args(["--digits", "--words", TEST_CASE]);
             grex.assert().success().stdout(predicate::eq(
                 "^\\w   ♥♥♥ \\d\\d \\w\\w\\w \\d \\w\\w\\w \\w\\w\\w\\w \\w\\w\\w 💩💩\\.$\n",
             ));
@@ -588,7 +588,7 @@ mod digit_word_conversion {
         #[test]
         fn succeeds_with_escape_option() {
             let mut grex = init_command();
-            grex.args(&["--digits", "--words", "--escape", TEST_CASE]);
+            grex.args(["--digits", "--words", "--escape", TEST_CASE]);
             grex.assert().success().stdout(predicate::eq(
                 "^\\w   \\u{2665}\\u{2665}\\u{2665} \\d\\d \\w\\w\\w \\d \\w\\w\\w \\w\\w\\w\\w \\w\\w\\w \\u{1f4a9}\\u{1f4a9}\\.$\n"
             ));
@@ -597,7 +597,7 @@ mod digit_word_conversion {
         #[test]
         fn succeeds_with_escape_and_surrogate_option() {
             let mut grex = init_command();
-            grex.args(&[
+            grex.args([
                 "--digits",
                 "--words",
                 "--escape",
@@ -616,7 +616,7 @@ mod digit_word_conversion {
         #[test]
         fn succeeds() {
             let mut grex = init_command();
-            grex.args(&["--repetitions", "--digits", "--words", TEST_CASE]);
+            grex.args(["--repetitions", "--digits", "--words", TEST_CASE]);
             grex.assert().success().stdout(predicate::eq(
                 "^\\w {3}♥{3} \\d(?:\\d \\w{3} ){2}\\w{4} \\w{3} 💩{2}\\.$\n",
             ));
@@ -625,7 +625,7 @@ mod digit_word_conversion {
         #[test]
         fn succeeds_with_escape_option() {
             let mut grex = init_command();
-            grex.args(&[
+            grex.args([
                 "--repetitions",
                 "--digits",
                 "--words",
@@ -640,7 +640,7 @@ mod digit_word_conversion {
         #[test]
         fn succeeds_with_escape_and_surrogate_option() {
             let mut grex = init_command();
-            grex.args(&[
+            grex.args([
                 "--repetitions",
                 "--digits",
                 "--words",
@@ -664,7 +664,7 @@ mod space_word_conversion {
         #[test]
         fn succeeds() {
             let mut grex = init_command();
-            grex.args(&["--words", "--spaces", TEST_CASE]);
+            grex.args(["--words", "--spaces", TEST_CASE]);
             grex.assert().success().stdout(predicate::eq(
                 "^\\w\\s\\s\\s♥♥♥\\s\\w\\w\\s\\w\\w\\w\\s\\w\\s\\w\\w\\w\\s\\w\\w\\w\\w\\s\\w\\w\\w\\s💩💩\\.$\n",
             ));
@@ -673,7 +673,7 @@ mod space_word_conversion {
         #[test]
         fn succeeds_with_escape_option() {
             let mut grex = init_command();
-            grex.args(&["--words", "--spaces", "--escape", TEST_CASE]);
+            grex.args(["--words", "--spaces", "--escape", TEST_CASE]);
             grex.assert().success().stdout(predicate::eq(
                 "^\\w\\s\\s\\s\\u{2665}\\u{2665}\\u{2665}\\s\\w\\w\\s\\w\\w\\w\\s\\w\\s\\w\\w\\w\\s\\w\\w\\w\\w\\s\\w\\w\\w\\s\\u{1f4a9}\\u{1f4a9}\\.$\n"
             ));
@@ -682,7 +682,7 @@ mod space_word_conversion {
         #[test]
         fn succeeds_with_escape_and_surrogate_option() {
             let mut grex = init_command();
-            grex.args(&[
+            grex.args([
                 "--words",
                 "--spaces",
                 "--escape",
@@ -701,7 +701,7 @@ mod space_word_conversion {
         #[test]
         fn succeeds() {
             let mut grex = init_command();
-            grex.args(&["--repetitions", "--words", "--spaces", TEST_CASE]);
+            grex.args(["--repetitions", "--words", "--spaces", TEST_CASE]);
             grex.assert().success().stdout(predicate::eq(
                 "^\\w\\s{3}♥{3}\\s\\w{2}\\s\\w{3}\\s\\w\\s\\w{3}\\s\\w{4}\\s\\w{3}\\s💩{2}\\.$\n",
             ));
@@ -710,7 +710,7 @@ mod space_word_conversion {
         #[test]
         fn succeeds_with_escape_option() {
             let mut grex = init_command();
-            grex.args(&[
+            grex.args([
                 "--repetitions",
                 "--words",
                 "--spaces",
@@ -725,7 +725,7 @@ mod space_word_conversion {
         #[test]
         fn succeeds_with_escape_and_surrogate_option() {
             let mut grex = init_command();
-            grex.args(&[
+            grex.args([
                 "--repetitions",
                 "--words",
                 "--spaces",
@@ -749,7 +749,7 @@ mod digit_space_word_conversion {
         #[test]
         fn succeeds() {
             let mut grex = init_command();
-            grex.args(&["--digits", "--words", "--spaces", TEST_CASE]);
+            grex.args(["--digits", "--words", "--spaces", TEST_CASE]);
             grex.assert().success().stdout(predicate::eq(
                 "^\\w\\s\\s\\s♥♥♥\\s\\d\\d\\s\\w\\w\\w\\s\\d\\s\\w\\w\\w\\s\\w\\w\\w\\w\\s\\w\\w\\w\\s💩💩\\.$\n",
             ));
@@ -758,7 +758,7 @@ mod digit_space_word_conversion {
         #[test]
         fn succeeds_with_escape_option() {
             let mut grex = init_command();
-            grex.args(&["--digits", "--words", "--spaces", "--escape", TEST_CASE]);
+            grex.args(["--digits", "--words", "--spaces", "--escape", TEST_CASE]);
             grex.assert().success().stdout(predicate::eq(
                 "^\\w\\s\\s\\s\\u{2665}\\u{2665}\\u{2665}\\s\\d\\d\\s\\w\\w\\w\\s\\d\\s\\w\\w\\w\\s\\w\\w\\w\\w\\s\\w\\w\\w\\s\\u{1f4a9}\\u{1f4a9}\\.$\n"
             ));
@@ -767,7 +767,7 @@ mod digit_space_word_conversion {
         #[test]
         fn succeeds_with_escape_and_surrogate_option() {
             let mut grex = init_command();
-            grex.args(&[
+            grex.args([
                 "--digits",
                 "--words",
                 "--spaces",
@@ -787,7 +787,7 @@ mod digit_space_word_conversion {
         #[test]
         fn succeeds() {
             let mut grex = init_command();
-            grex.args(&[
+            grex.args([
                 "--repetitions",
                 "--digits",
                 "--words",
@@ -802,7 +802,7 @@ mod digit_space_word_conversion {
         #[test]
         fn succeeds_with_escape_option() {
             let mut grex = init_command();
-            grex.args(&[
+            grex.args([
                 "--repetitions",
                 "--digits",
                 "--words",
@@ -818,7 +818,7 @@ mod digit_space_word_conversion {
         #[test]
         fn succeeds_with_escape_and_surrogate_option() {
             let mut grex = init_command();
-            grex.args(&[
+            grex.args([
                 "--repetitions",
                 "--digits",
                 "--words",
@@ -843,7 +843,7 @@ mod non_digit_conversion {
         #[test]
         fn succeeds() {
             let mut grex = init_command();
-            grex.args(&["--non-digits", TEST_CASE]);
+            grex.args(["--non-digits", TEST_CASE]);
             grex.assert().success().stdout(predicate::eq(
                 "^\\D\\D\\D\\D\\D\\D\\D\\D36\\D\\D\\D\\D\\D٣\\D\\D\\D\\D\\D\\D\\D\\D\\D\\D\\D\\D\\D\\D\\D\\D\\D$\n",
             ));
@@ -852,7 +852,7 @@ mod non_digit_conversion {
         #[test]
         fn succeeds_with_escape_option() {
             let mut grex = init_command();
-            grex.args(&["--non-digits", "--escape", TEST_CASE]);
+            grex.args(["--non-digits", "--escape", TEST_CASE]);
             grex.assert().success().stdout(predicate::eq(
                 "^\\D\\D\\D\\D\\D\\D\\D\\D36\\D\\D\\D\\D\\D\\u{663}\\D\\D\\D\\D\\D\\D\\D\\D\\D\\D\\D\\D\\D\\D\\D\\D\\D$\n",
             ));
@@ -861,7 +861,7 @@ mod non_digit_conversion {
         #[test]
         fn succeeds_with_escape_and_surrogate_option() {
             let mut grex = init_command();
-            grex.args(&["--non-digits", "--escape", "--with-surrogates", TEST_CASE]);
+            grex.args(["--non-digits", "--escape", "--with-surrogates", TEST_CASE]);
             grex.assert().success().stdout(predicate::eq(
                 "^\\D\\D\\D\\D\\D\\D\\D\\D36\\D\\D\\D\\D\\D\\u{663}\\D\\D\\D\\D\\D\\D\\D\\D\\D\\D\\D\\D\\D\\D\\D\\D\\D$\n",
             ));
@@ -874,7 +874,7 @@ mod non_digit_conversion {
         #[test]
         fn succeeds() {
             let mut grex = init_command();
-            grex.args(&["--repetitions", "--non-digits", TEST_CASE]);
+            grex.args(["--repetitions", "--non-digits", TEST_CASE]);
             grex.assert()
                 .success()
                 .stdout(predicate::eq("^\\D{8}36\\D{5}٣\\D{17}$\n"));
@@ -883,7 +883,7 @@ mod non_digit_conversion {
         #[test]
         fn succeeds_with_escape_option() {
             let mut grex = init_command();
-            grex.args(&["--repetitions", "--non-digits", "--escape", TEST_CASE]);
+            grex.args(["--repetitions", "--non-digits", "--escape", TEST_CASE]);
             grex.assert()
                 .success()
                 .stdout(predicate::eq("^\\D{8}36\\D{5}\\u{663}\\D{17}$\n"));
@@ -892,7 +892,7 @@ mod non_digit_conversion {
         #[test]
         fn succeeds_with_escape_and_surrogate_option() {
             let mut grex = init_command();
-            grex.args(&[
+            grex.args([
                 "--repetitions",
                 "--non-digits",
                 "--escape",
@@ -915,7 +915,7 @@ mod non_space_conversion {
         #[test]
         fn succeeds() {
             let mut grex = init_command();
-            grex.args(&["--non-spaces", TEST_CASE]);
+            grex.args(["--non-spaces", TEST_CASE]);
             grex.assert().success().stdout(predicate::eq(
                 "^\\S   \\S\\S\\S \\S\\S \\S\\S\\S \\S \\S\\S\\S \\S\\S\\S\\S \\S\\S\\S \\S\\S\\S$\n",
             ));
@@ -924,7 +924,7 @@ mod non_space_conversion {
         #[test]
         fn succeeds_with_escape_option() {
             let mut grex = init_command();
-            grex.args(&["--non-spaces", "--escape", TEST_CASE]);
+            grex.args(["--non-spaces", "--escape", TEST_CASE]);
             grex.assert().success().stdout(predicate::eq(
                 "^\\S   \\S\\S\\S \\S\\S \\S\\S\\S \\S \\S\\S\\S \\S\\S\\S\\S \\S\\S\\S \\S\\S\\S$\n",
             ));
@@ -933,7 +933,7 @@ mod non_space_conversion {
         #[test]
         fn succeeds_with_escape_and_surrogate_option() {
             let mut grex = init_command();
-            grex.args(&["--non-spaces", "--escape", "--with-surrogates", TEST_CASE]);
+            grex.args(["--non-spaces", "--escape", "--with-surrogates", TEST_CASE]);
             grex.assert().success().stdout(predicate::eq(
                 "^\\S   \\S\\S\\S \\S\\S \\S\\S\\S \\S \\S\\S\\S \\S\\S\\S\\S \\S\\S\\S \\S\\S\\S$\n",
             ));
@@ -946,7 +946,7 @@ mod non_space_conversion {
         #[test]
         fn succeeds() {
             let mut grex = init_command();
-            grex.args(&["--repetitions", "--non-spaces", TEST_CASE]);
+            grex.args(["--repetitions", "--non-spaces", TEST_CASE]);
             grex.assert().success().stdout(predicate::eq(
                 "^\\S {3}\\S{3} \\S{2} \\S{3} \\S \\S{3} \\S{4} \\S{3} \\S{3}$\n",
             ));
@@ -955,7 +955,7 @@ mod non_space_conversion {
         #[test]
         fn succeeds_with_escape_option() {
             let mut grex = init_command();
-            grex.args(&["--repetitions", "--non-spaces", "--escape", TEST_CASE]);
+            grex.args(["--repetitions", "--non-spaces", "--escape", TEST_CASE]);
             grex.assert().success().stdout(predicate::eq(
                 "^\\S {3}\\S{3} \\S{2} \\S{3} \\S \\S{3} \\S{4} \\S{3} \\S{3}$\n",
             ));
@@ -964,7 +964,7 @@ mod non_space_conversion {
         #[test]
         fn succeeds_with_escape_and_surrogate_option() {
             let mut grex = init_command();
-            grex.args(&[
+            grex.args([
                 "--repetitions",
                 "--non-spaces",
                 "--escape",
@@ -987,7 +987,7 @@ mod non_word_conversion {
         #[test]
         fn succeeds() {
             let mut grex = init_command();
-            grex.args(&["--non-words", TEST_CASE]);
+            grex.args(["--non-words", TEST_CASE]);
             grex.assert().success().stdout(predicate::eq(
                 "^I\\W\\W\\W\\W\\W\\W\\W36\\Wand\\W٣\\Wand\\Wy̆y̆\\Wand\\W\\W\\W\\W$\n",
             ));
@@ -996,7 +996,7 @@ mod non_word_conversion {
         #[test]
         fn succeeds_with_escape_option() {
             let mut grex = init_command();
-            grex.args(&["--non-words", "--escape", TEST_CASE]);
+            grex.args(["--non-words", "--escape", TEST_CASE]);
             grex.assert().success().stdout(predicate::eq(
                 "^I\\W\\W\\W\\W\\W\\W\\W36\\Wand\\W\\u{663}\\Wand\\Wy\\u{306}y\\u{306}\\Wand\\W\\W\\W\\W$\n",
             ));
@@ -1005,7 +1005,7 @@ mod non_word_conversion {
         #[test]
         fn succeeds_with_escape_and_surrogate_option() {
             let mut grex = init_command();
-            grex.args(&["--non-words", "--escape", "--with-surrogates", TEST_CASE]);
+            grex.args(["--non-words", "--escape", "--with-surrogates", TEST_CASE]);
             grex.assert().success().stdout(predicate::eq(
                 "^I\\W\\W\\W\\W\\W\\W\\W36\\Wand\\W\\u{663}\\Wand\\Wy\\u{306}y\\u{306}\\Wand\\W\\W\\W\\W$\n",
             ));
@@ -1018,7 +1018,7 @@ mod non_word_conversion {
         #[test]
         fn succeeds() {
             let mut grex = init_command();
-            grex.args(&["--repetitions", "--non-words", TEST_CASE]);
+            grex.args(["--repetitions", "--non-words", TEST_CASE]);
             grex.assert().success().stdout(predicate::eq(
                 "^I\\W{7}36\\Wand\\W٣\\Wand\\W(?:y̆){2}\\Wand\\W{4}$\n",
             ));
@@ -1027,7 +1027,7 @@ mod non_word_conversion {
         #[test]
         fn succeeds_with_escape_option() {
             let mut grex = init_command();
-            grex.args(&["--repetitions", "--non-words", "--escape", TEST_CASE]);
+            grex.args(["--repetitions", "--non-words", "--escape", TEST_CASE]);
             grex.assert().success().stdout(predicate::eq(
                 "^I\\W{7}36\\Wand\\W\\u{663}\\Wand\\W(?:y\\u{306}){2}\\Wand\\W{4}$\n",
             ));
@@ -1036,7 +1036,7 @@ mod non_word_conversion {
         #[test]
         fn succeeds_with_escape_and_surrogate_option() {
             let mut grex = init_command();
-            grex.args(&[
+            grex.args([
                 "--repetitions",
                 "--non-words",
                 "--escape",
@@ -1059,7 +1059,7 @@ mod non_digit_non_space_conversion {
         #[test]
         fn succeeds() {
             let mut grex = init_command();
-            grex.args(&["--non-digits", "--non-spaces", TEST_CASE]);
+            grex.args(["--non-digits", "--non-spaces", TEST_CASE]);
             grex.assert().success().stdout(predicate::eq(
                 "^\\D\\D\\D\\D\\D\\D\\D\\D\\S\\S\\D\\D\\D\\D\\D\\S\\D\\D\\D\\D\\D\\D\\D\\D\\D\\D\\D\\D\\D\\D\\D\\D\\D$\n",
             ));
@@ -1068,7 +1068,7 @@ mod non_digit_non_space_conversion {
         #[test]
         fn succeeds_with_escape_option() {
             let mut grex = init_command();
-            grex.args(&["--non-digits", "--non-spaces", "--escape", TEST_CASE]);
+            grex.args(["--non-digits", "--non-spaces", "--escape", TEST_CASE]);
             grex.assert().success().stdout(predicate::eq(
                 "^\\D\\D\\D\\D\\D\\D\\D\\D\\S\\S\\D\\D\\D\\D\\D\\S\\D\\D\\D\\D\\D\\D\\D\\D\\D\\D\\D\\D\\D\\D\\D\\D\\D$\n",
             ));
@@ -1077,7 +1077,7 @@ mod non_digit_non_space_conversion {
         #[test]
         fn succeeds_with_escape_and_surrogate_option() {
             let mut grex = init_command();
-            grex.args(&[
+            grex.args([
                 "--non-digits",
                 "--non-spaces",
                 "--escape",
@@ -1096,7 +1096,7 @@ mod non_digit_non_space_conversion {
         #[test]
         fn succeeds() {
             let mut grex = init_command();
-            grex.args(&["--repetitions", "--non-digits", "--non-spaces", TEST_CASE]);
+            grex.args(["--repetitions", "--non-digits", "--non-spaces", TEST_CASE]);
             grex.assert()
                 .success()
                 .stdout(predicate::eq("^\\D{8}\\S{2}\\D{5}\\S\\D{17}$\n"));
@@ -1105,7 +1105,7 @@ mod non_digit_non_space_conversion {
         #[test]
         fn succeeds_with_escape_option() {
             let mut grex = init_command();
-            grex.args(&[
+            grex.args([
                 "--repetitions",
                 "--non-digits",
                 "--non-spaces",
@@ -1120,7 +1120,7 @@ mod non_digit_non_space_conversion {
         #[test]
         fn succeeds_with_escape_and_surrogate_option() {
             let mut grex = init_command();
-            grex.args(&[
+            grex.args([
                 "--repetitions",
                 "--non-digits",
                 "--non-spaces",
@@ -1144,7 +1144,7 @@ mod non_digit_non_word_conversion {
         #[test]
         fn succeeds() {
             let mut grex = init_command();
-            grex.args(&["--non-digits", "--non-words", TEST_CASE]);
+            grex.args(["--non-digits", "--non-words", TEST_CASE]);
             grex.assert().success().stdout(predicate::eq(
                 "^\\D\\D\\D\\D\\D\\D\\D\\D36\\D\\D\\D\\D\\D٣\\D\\D\\D\\D\\D\\D\\D\\D\\D\\D\\D\\D\\D\\D\\D\\D\\D$\n",
             ));
@@ -1153,7 +1153,7 @@ mod non_digit_non_word_conversion {
         #[test]
         fn succeeds_with_escape_option() {
             let mut grex = init_command();
-            grex.args(&["--non-digits", "--non-words", "--escape", TEST_CASE]);
+            grex.args(["--non-digits", "--non-words", "--escape", TEST_CASE]);
             grex.assert().success().stdout(predicate::eq(
                 "^\\D\\D\\D\\D\\D\\D\\D\\D36\\D\\D\\D\\D\\D\\u{663}\\D\\D\\D\\D\\D\\D\\D\\D\\D\\D\\D\\D\\D\\D\\D\\D\\D$\n",
             ));
@@ -1162,7 +1162,7 @@ mod non_digit_non_word_conversion {
         #[test]
         fn succeeds_with_escape_and_surrogate_option() {
             let mut grex = init_command();
-            grex.args(&[
+            grex.args([
                 "--non-digits",
                 "--non-words",
                 "--escape",
@@ -1181,7 +1181,7 @@ mod non_digit_non_word_conversion {
         #[test]
         fn succeeds() {
             let mut grex = init_command();
-            grex.args(&["--repetitions", "--non-digits", "--non-words", TEST_CASE]);
+            grex.args(["--repetitions", "--non-digits", "--non-words", TEST_CASE]);
             grex.assert()
                 .success()
                 .stdout(predicate::eq("^\\D{8}36\\D{5}٣\\D{17}$\n"));
@@ -1190,7 +1190,7 @@ mod non_digit_non_word_conversion {
         #[test]
         fn succeeds_with_escape_option() {
             let mut grex = init_command();
-            grex.args(&[
+            grex.args([
                 "--repetitions",
                 "--non-digits",
                 "--non-words",
@@ -1205,7 +1205,7 @@ mod non_digit_non_word_conversion {
         #[test]
         fn succeeds_with_escape_and_surrogate_option() {
             let mut grex = init_command();
-            grex.args(&[
+            grex.args([
                 "--repetitions",
                 "--non-digits",
                 "--non-words",
@@ -1229,7 +1229,7 @@ mod non_space_non_word_conversion {
         #[test]
         fn succeeds() {
             let mut grex = init_command();
-            grex.args(&["--non-spaces", "--non-words", TEST_CASE]);
+            grex.args(["--non-spaces", "--non-words", TEST_CASE]);
             grex.assert().success().stdout(predicate::eq(
                 "^\\S\\W\\W\\W\\W\\W\\W\\W\\S\\S\\W\\S\\S\\S\\W\\S\\W\\S\\S\\S\\W\\S\\S\\S\\S\\W\\S\\S\\S\\W\\W\\W\\W$\n",
             ));
@@ -1238,7 +1238,7 @@ mod non_space_non_word_conversion {
         #[test]
         fn succeeds_with_escape_option() {
             let mut grex = init_command();
-            grex.args(&["--non-spaces", "--non-words", "--escape", TEST_CASE]);
+            grex.args(["--non-spaces", "--non-words", "--escape", TEST_CASE]);
             grex.assert().success().stdout(predicate::eq(
                 "^\\S\\W\\W\\W\\W\\W\\W\\W\\S\\S\\W\\S\\S\\S\\W\\S\\W\\S\\S\\S\\W\\S\\S\\S\\S\\W\\S\\S\\S\\W\\W\\W\\W$\n",
             ));
@@ -1247,7 +1247,7 @@ mod non_space_non_word_conversion {
         #[test]
         fn succeeds_with_escape_and_surrogate_option() {
             let mut grex = init_command();
-            grex.args(&[
+            grex.args([
                 "--non-spaces",
                 "--non-words",
                 "--escape",
@@ -1266,7 +1266,7 @@ mod non_space_non_word_conversion {
         #[test]
         fn succeeds() {
             let mut grex = init_command();
-            grex.args(&["--repetitions", "--non-spaces", "--non-words", TEST_CASE]);
+            grex.args(["--repetitions", "--non-spaces", "--non-words", TEST_CASE]);
             grex.assert().success().stdout(predicate::eq(
                 "^\\S\\W{7}\\S{2}\\W\\S{3}\\W\\S\\W\\S{3}\\W\\S{4}\\W\\S{3}\\W{4}$\n",
             ));
@@ -1275,7 +1275,7 @@ mod non_space_non_word_conversion {
         #[test]
         fn succeeds_with_escape_option() {
             let mut grex = init_command();
-            grex.args(&[
+            grex.args([
                 "--repetitions",
                 "--non-spaces",
                 "--non-words",
@@ -1290,7 +1290,7 @@ mod non_space_non_word_conversion {
         #[test]
         fn succeeds_with_escape_and_surrogate_option() {
             let mut grex = init_command();
-            grex.args(&[
+            grex.args([
                 "--repetitions",
                 "--non-spaces",
                 "--non-words",
@@ -1314,7 +1314,7 @@ mod non_digit_non_space_non_word_conversion {
         #[test]
         fn succeeds() {
             let mut grex = init_command();
-            grex.args(&["--non-digits", "--non-spaces", "--non-words", TEST_CASE]);
+            grex.args(["--non-digits", "--non-spaces", "--non-words", TEST_CASE]);
             grex.assert().success().stdout(predicate::eq(
                 "^\\D\\D\\D\\D\\D\\D\\D\\D\\S\\S\\D\\D\\D\\D\\D\\S\\D\\D\\D\\D\\D\\D\\D\\D\\D\\D\\D\\D\\D\\D\\D\\D\\D$\n",
             ));
@@ -1323,7 +1323,7 @@ mod non_digit_non_space_non_word_conversion {
         #[test]
         fn succeeds_with_escape_option() {
             let mut grex = init_command();
-            grex.args(&[
+            grex.args([
                 "--non-digits",
                 "--non-spaces",
                 "--non-words",
@@ -1338,7 +1338,7 @@ mod non_digit_non_space_non_word_conversion {
         #[test]
         fn succeeds_with_escape_and_surrogate_option() {
             let mut grex = init_command();
-            grex.args(&[
+            grex.args([
                 "--non-digits",
                 "--non-spaces",
                 "--non-words",
@@ -1358,7 +1358,7 @@ mod non_digit_non_space_non_word_conversion {
         #[test]
         fn succeeds() {
             let mut grex = init_command();
-            grex.args(&[
+            grex.args([
                 "--repetitions",
                 "--non-digits",
                 "--non-spaces",
@@ -1373,7 +1373,7 @@ mod non_digit_non_space_non_word_conversion {
         #[test]
         fn succeeds_with_escape_option() {
             let mut grex = init_command();
-            grex.args(&[
+            grex.args([
                 "--repetitions",
                 "--non-digits",
                 "--non-spaces",
@@ -1389,7 +1389,7 @@ mod non_digit_non_space_non_word_conversion {
         #[test]
         fn succeeds_with_escape_and_surrogate_option() {
             let mut grex = init_command();
-            grex.args(&[
+            grex.args([
                 "--repetitions",
                 "--non-digits",
                 "--non-spaces",
@@ -1414,7 +1414,7 @@ mod digit_non_digit_conversion {
         #[test]
         fn succeeds() {
             let mut grex = init_command();
-            grex.args(&["--digits", "--non-digits", TEST_CASE]);
+            grex.args(["--digits", "--non-digits", TEST_CASE]);
             grex.assert().success().stdout(predicate::eq(
                 "^\\D\\D\\D\\D\\D\\D\\D\\D\\d\\d\\D\\D\\D\\D\\D\\d\\D\\D\\D\\D\\D\\D\\D\\D\\D\\D\\D\\D\\D\\D\\D\\D\\D$\n",
             ));
@@ -1423,7 +1423,7 @@ mod digit_non_digit_conversion {
         #[test]
         fn succeeds_with_escape_option() {
             let mut grex = init_command();
-            grex.args(&["--digits", "--non-digits", "--escape", TEST_CASE]);
+            grex.args(["--digits", "--non-digits", "--escape", TEST_CASE]);
             grex.assert().success().stdout(predicate::eq(
                 "^\\D\\D\\D\\D\\D\\D\\D\\D\\d\\d\\D\\D\\D\\D\\D\\d\\D\\D\\D\\D\\D\\D\\D\\D\\D\\D\\D\\D\\D\\D\\D\\D\\D$\n",
             ));
@@ -1432,7 +1432,7 @@ mod digit_non_digit_conversion {
         #[test]
         fn succeeds_with_escape_and_surrogate_option() {
             let mut grex = init_command();
-            grex.args(&[
+            grex.args([
                 "--digits",
                 "--non-digits",
                 "--escape",
@@ -1451,7 +1451,7 @@ mod digit_non_digit_conversion {
         #[test]
         fn succeeds() {
             let mut grex = init_command();
-            grex.args(&["--repetitions", "--digits", "--non-digits", TEST_CASE]);
+            grex.args(["--repetitions", "--digits", "--non-digits", TEST_CASE]);
             grex.assert()
                 .success()
                 .stdout(predicate::eq("^\\D{8}\\d{2}\\D{5}\\d\\D{17}$\n"));
@@ -1460,7 +1460,7 @@ mod digit_non_digit_conversion {
         #[test]
         fn succeeds_with_escape_option() {
             let mut grex = init_command();
-            grex.args(&[
+            grex.args([
                 "--repetitions",
                 "--digits",
                 "--non-digits",
@@ -1475,7 +1475,7 @@ mod digit_non_digit_conversion {
         #[test]
         fn succeeds_with_escape_and_surrogate_option() {
             let mut grex = init_command();
-            grex.args(&[
+            grex.args([
                 "--repetitions",
                 "--digits",
                 "--non-digits",
@@ -1499,7 +1499,7 @@ mod space_non_space_conversion {
         #[test]
         fn succeeds() {
             let mut grex = init_command();
-            grex.args(&["--spaces", "--non-spaces", TEST_CASE]);
+            grex.args(["--spaces", "--non-spaces", TEST_CASE]);
             grex.assert().success().stdout(predicate::eq(
                 "^\\S\\s\\s\\s\\S\\S\\S\\s\\S\\S\\s\\S\\S\\S\\s\\S\\s\\S\\S\\S\\s\\S\\S\\S\\S\\s\\S\\S\\S\\s\\S\\S\\S$\n",
             ));
@@ -1508,7 +1508,7 @@ mod space_non_space_conversion {
         #[test]
         fn succeeds_with_escape_option() {
             let mut grex = init_command();
-            grex.args(&["--spaces", "--non-spaces", "--escape", TEST_CASE]);
+            grex.args(["--spaces", "--non-spaces", "--escape", TEST_CASE]);
             grex.assert().success().stdout(predicate::eq(
                 "^\\S\\s\\s\\s\\S\\S\\S\\s\\S\\S\\s\\S\\S\\S\\s\\S\\s\\S\\S\\S\\s\\S\\S\\S\\S\\s\\S\\S\\S\\s\\S\\S\\S$\n",
             ));
@@ -1517,7 +1517,7 @@ mod space_non_space_conversion {
         #[test]
         fn succeeds_with_escape_and_surrogate_option() {
             let mut grex = init_command();
-            grex.args(&[
+            grex.args([
                 "--spaces",
                 "--non-spaces",
                 "--escape",
@@ -1536,7 +1536,7 @@ mod space_non_space_conversion {
         #[test]
         fn succeeds() {
             let mut grex = init_command();
-            grex.args(&["--repetitions", "--spaces", "--non-spaces", TEST_CASE]);
+            grex.args(["--repetitions", "--spaces", "--non-spaces", TEST_CASE]);
             grex.assert().success().stdout(predicate::eq(
                 "^\\S\\s{3}\\S{3}\\s\\S{2}\\s\\S{3}\\s\\S\\s\\S{3}\\s\\S{4}\\s\\S{3}\\s\\S{3}$\n",
             ));
@@ -1545,7 +1545,7 @@ mod space_non_space_conversion {
         #[test]
         fn succeeds_with_escape_option() {
             let mut grex = init_command();
-            grex.args(&[
+            grex.args([
                 "--repetitions",
                 "--spaces",
                 "--non-spaces",
@@ -1560,7 +1560,7 @@ mod space_non_space_conversion {
         #[test]
         fn succeeds_with_escape_and_surrogate_option() {
             let mut grex = init_command();
-            grex.args(&[
+            grex.args([
                 "--repetitions",
                 "--spaces",
                 "--non-spaces",
@@ -1584,7 +1584,7 @@ mod word_non_word_conversion {
         #[test]
         fn succeeds() {
             let mut grex = init_command();
-            grex.args(&["--words", "--non-words", TEST_CASE]);
+            grex.args(["--words", "--non-words", TEST_CASE]);
             grex.assert().success().stdout(predicate::eq(
                 "^\\w\\W\\W\\W\\W\\W\\W\\W\\w\\w\\W\\w\\w\\w\\W\\w\\W\\w\\w\\w\\W\\w\\w\\w\\w\\W\\w\\w\\w\\W\\W\\W\\W$\n",
             ));
@@ -1593,7 +1593,7 @@ mod word_non_word_conversion {
         #[test]
         fn succeeds_with_escape_option() {
             let mut grex = init_command();
-            grex.args(&["--words", "--non-words", "--escape", TEST_CASE]);
+            grex.args(["--words", "--non-words", "--escape", TEST_CASE]);
             grex.assert().success().stdout(predicate::eq(
                 "^\\w\\W\\W\\W\\W\\W\\W\\W\\w\\w\\W\\w\\w\\w\\W\\w\\W\\w\\w\\w\\W\\w\\w\\w\\w\\W\\w\\w\\w\\W\\W\\W\\W$\n",
             ));
@@ -1602,7 +1602,7 @@ mod word_non_word_conversion {
         #[test]
         fn succeeds_with_escape_and_surrogate_option() {
             let mut grex = init_command();
-            grex.args(&[
+            grex.args([
                 "--words",
                 "--non-words",
                 "--escape",
@@ -1621,7 +1621,7 @@ mod word_non_word_conversion {
         #[test]
         fn succeeds() {
             let mut grex = init_command();
-            grex.args(&["--repetitions", "--words", "--non-words", TEST_CASE]);
+            grex.args(["--repetitions", "--words", "--non-words", TEST_CASE]);
             grex.assert().success().stdout(predicate::eq(
                 "^\\w\\W{7}\\w{2}\\W\\w{3}\\W\\w\\W\\w{3}\\W\\w{4}\\W\\w{3}\\W{4}$\n",
             ));
@@ -1630,7 +1630,7 @@ mod word_non_word_conversion {
         #[test]
         fn succeeds_with_escape_option() {
             let mut grex = init_command();
-            grex.args(&[
+            grex.args([
                 "--repetitions",
                 "--words",
                 "--non-words",
@@ -1645,7 +1645,7 @@ mod word_non_word_conversion {
         #[test]
         fn succeeds_with_escape_and_surrogate_option() {
             let mut grex = init_command();
-            grex.args(&[
+            grex.args([
                 "--repetitions",
                 "--words",
                 "--non-words",
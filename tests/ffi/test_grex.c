@@ -0,0 +1,72 @@
+/*
+ * Copyright © 2019-2020 Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+/*
+ * Build and run from the repository root:
+ *
+ *   cargo build --release --features ffi
+ *   cc -Iinclude tests/ffi/test_grex.c target/release/libgrex.a -lpthread -ldl -lm -o target/test_grex
+ *   ./target/test_grex
+ */
+
+#include <grex.h>
+#include <stdio.h>
+#include <string.h>
+
+static int failures = 0;
+
+static void expect_regexp(const char *const *inputs, size_t n, const GrexConfig *cfg, const char *expected) {
+    char *out = NULL;
+    int code = grex_generate(inputs, n, cfg, &out);
+    if (code != GREX_OK || out == NULL || strcmp(out, expected) != 0) {
+        fprintf(stderr, "expected %s, got code %d and %s\n", expected, code, out ? out : "(null)");
+        failures++;
+    }
+    grex_free(out);
+}
+
+static void expect_error(const char *const *inputs, size_t n, int expected) {
+    char *out = NULL;
+    int code = grex_generate(inputs, n, NULL, &out);
+    if (code != expected || out != NULL) {
+        fprintf(stderr, "expected error code %d, got %d\n", expected, code);
+        failures++;
+    }
+}
+
+int main(void) {
+    const char *inputs[] = {"a", "aa", "aaa"};
+    expect_regexp(inputs, 3, NULL, "^a(?:aa?)?$");
+
+    const char *digits[] = {"1", "23"};
+    GrexConfig cfg = {0};
+    cfg.is_digit_converted = true;
+    expect_regexp(digits, 2, &cfg, "^\\d(?:\\d)?$");
+
+    const char *invalid[] = {"\xff"};
+    expect_error(invalid, 1, GREX_INVALID_UTF8);
+    expect_error(inputs, 0, GREX_NO_INPUTS);
+
+    const char *null_input[] = {NULL};
+    expect_error(null_input, 1, GREX_NULL_POINTER);
+
+    grex_free(NULL);
+
+    if (failures == 0) {
+        printf("all tests passed\n");
+    }
+    return failures == 0 ? 0 : 1;
+}
@@ -0,0 +1,88 @@
+/*
+ * Copyright © 2019-2020 Peter M. Stahl pemistahl@gmail.com
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ * http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+#![cfg(feature = "ffi")]
+
+use grex::ffi::{
+    grex_free, grex_generate, GrexConfig, GREX_INVALID_UTF8, GREX_NO_INPUTS, GREX_NULL_POINTER,
+    GREX_OK,
+};
+use std::ffi::{CStr, CString};
+use std::os::raw::c_char;
+use std::ptr;
+
+fn generate(test_cases: &[&[u8]], cfg: *const GrexConfig) -> (i32, Option<String>) {
+    let test_cases = test_cases
+        .iter()
+        .map(|it| CString::new(*it).unwrap())
+        .collect::<Vec<_>>();
+    let inputs = test_cases.iter().map(|it| it.as_ptr()).collect::<Vec<_>>();
+    let mut out: *mut c_char = ptr::null_mut();
+    unsafe {
+        let code = grex_generate(inputs.as_ptr(), inputs.len(), cfg, &mut out);
+        if out.is_null() {
+            return (code, None);
+        }
+        let regexp = CStr::from_ptr(out).to_str().unwrap().to_string();
+        grex_free(out);
+        (code, Some(regexp))
+    }
+}
+
+#[test]
+fn succeeds_with_default_config() {
+    assert_eq!(
+        generate(&[b"a", b"aa", b"aaa"], ptr::null()),
+        (GREX_OK, Some("^a(?:aa?)?$".to_string()))
+    );
+}
+
+#[test]
+fn succeeds_with_custom_config() {
+    let cfg = GrexConfig {
+        is_digit_converted: true,
+        is_non_ascii_char_escaped: true,
+        ..GrexConfig::default()
+    };
+    assert_eq!(
+        generate(&[b"1", "\u{e9}".as_bytes()], &cfg),
+        (GREX_OK, Some("^(?:\\d|\\u{e9})$".to_string()))
+    );
+}
+
+#[test]
+fn fails_with_invalid_input() {
+    assert_eq!(generate(&[b"\xff"], ptr::null()), (GREX_INVALID_UTF8, None));
+    assert_eq!(generate(&[], ptr::null()), (GREX_NO_INPUTS, None));
+}
+
+#[test]
+fn fails_with_null_pointers() {
+    let inputs = [ptr::null::<c_char>()];
+    let mut out: *mut c_char = ptr::null_mut();
+    unsafe {
+        assert_eq!(
+            grex_generate(inputs.as_ptr(), 1, ptr::null(), &mut out),
+            GREX_NULL_POINTER
+        );
+        assert!(out.is_null());
+        assert_eq!(
+            grex_generate(inputs.as_ptr(), 1, ptr::null(), ptr::null_mut()),
+            GREX_NULL_POINTER
+        );
+        grex_free(ptr::null_mut());
+    }
+}
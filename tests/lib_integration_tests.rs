@@ -14,7 +14,12 @@
  * limitations under the License.
  */
 
-use grex::{Feature, RegExpBuilder};
+use grex::{
+    generate, AlternationOrder, EscapeStyle, ExactRepetitionStyle, Feature, GroupPolicy, InputTrim,
+    Normalization, RegExpBuilder, RegExpConfig, RegExpError, RegExpFlavor, RegexFlags,
+};
+#[cfg(feature = "coloring")]
+use grex::{Color, ColorTheme};
 use regex::Regex;
 use rstest::rstest;
 use std::io::Write;
@@ -28,6 +33,11 @@ mod no_conversion {
 
         #[rstest(test_cases, expected_output,
             case(vec![""], "^$"),
+            case(vec!["", "a"], "^a?$"),
+            case(vec!["", "ab"], "^(?:ab)?$"),
+            case(vec!["", "a", "b"], "^[ab]?$"),
+            case(vec!["", "ab", "cd"], "^(?:ab|cd)?$"),
+            case(vec!["", "a", "aa"], "^(?:aa?)?$"),
             case(vec![" "], "^ $"),
             case(vec!["   "], "^   $"),
             case(vec!["["], "^\\[$"),
@@ -98,6 +108,7 @@ mod no_conversion {
         #[rstest(test_cases, expected_output,
             case(vec!["ABC", "abc", "AbC", "aBc"], "(?i)^abc$"),
             case(vec!["Ä@Ö€Ü", "ä@ö€ü", "Ä@ö€Ü", "ä@Ö€ü"], "(?i)^ä@ö€ü$"),
+            case(vec!["İstanbul"], "(?i)^İstanbul$"),
         )]
         fn succeeds_with_ignore_case_option(test_cases: Vec<&str>, expected_output: &str) {
             let regexp = RegExpBuilder::from(&test_cases)
@@ -109,7 +120,7 @@ mod no_conversion {
 
         #[rstest(test_cases, expected_output,
             case(vec!["My ♥ and 💩 is yours."], "^My \\u{2665} and \\u{1f4a9} is yours\\.$"),
-            case(vec!["My ♥ is yours.", "My 💩 is yours."], "^My (?:\\u{2665}|\\u{1f4a9}) is yours\\.$"),
+            case(vec!["My ♥ is yours.", "My 💩 is yours."], "^My [\\u{2665}\\u{1f4a9}] is yours\\.$"),
             case(
                 vec!["I   ♥♥♥ 36 and ٣ and y̆y̆ and 💩💩."],
                 "^I   \\u{2665}\\u{2665}\\u{2665} 36 and \\u{663} and y\\u{306}y\\u{306} and \\u{1f4a9}\\u{1f4a9}\\.$"
@@ -126,6 +137,7 @@ mod no_conversion {
         #[rstest(test_cases, expected_output,
             case(vec!["My ♥ and 💩 is yours."], "^My \\u{2665} and \\u{d83d}\\u{dca9} is yours\\.$"),
             case(vec!["My ♥ is yours.", "My 💩 is yours."], "^My (?:\\u{2665}|\\u{d83d}\\u{dca9}) is yours\\.$"),
+            case(vec!["\u{10ffff}"], "^\\u{dbff}\\u{dfff}$"),
             case(
                 vec!["I   ♥♥♥ 36 and ٣ and y̆y̆ and 💩💩."],
                 "^I   \\u{2665}\\u{2665}\\u{2665} 36 and \\u{663} and y\\u{306}y\\u{306} and \\u{d83d}\\u{dca9}\\u{d83d}\\u{dca9}\\.$"
@@ -189,6 +201,9 @@ mod no_conversion {
             case(vec!["abababa"], "^a(?:ba){3}$"),
             case(vec!["aababab"], "^a(?:ab){3}$"),
             case(vec!["abababaa"], "^(?:ab){3}a{2}$"),
+            case(vec!["abab", "ababab"], "^(?:ab){2,3}$"),
+            case(vec!["xabab", "xababab", "xabababab"], "^x(?:ab){2,4}$"),
+            case(vec!["abab", "ababab", "cdcd"], "^(?:(?:ab){2,3}|(?:cd){2})$"),
             case(vec!["aaaaaabbbbb"], "^a{6}b{5}$"),
             case(vec!["aabaababab"], "^(?:a{2}b){2}abab$"), // goal: ^(a{2}b){2}(ab){2}$
             case(vec!["aaaaaaabbbbbba"], "^a{7}b{6}a$"),
@@ -220,6 +235,7 @@ mod no_conversion {
             case(vec!["I \\u{2665} cake"], "^I \\\\u\\{26{2}5\\} cake$"),
             case(vec!["I \\u{2665}\\u{2665} cake"], "^I (?:\\\\u\\{26{2}5\\}){2} cake$"),
             case(vec!["I \\u2665\\u2665 cake"], "^I (?:\\\\u26{2}5){2} cake$"),
+            case(vec!["\\\\\\"], "^\\\\{3}$"),
             case(vec!["My ♥♥♥ is yours.", "My 💩💩 is yours."], "^My (?:💩{2}|♥{3}) is yours\\.$"),
             case(vec!["I   ♥♥♥ 36 and ٣ and y̆y̆ and 💩💩."], "^I {3}♥{3} 36 and ٣ and (?:y̆){2} and 💩{2}\\.$")
         )]
@@ -245,11 +261,15 @@ mod no_conversion {
 
         #[rstest(test_cases, expected_output,
             case(vec!["My ♥♥♥ and 💩💩 is yours."], "^My \\u{2665}{3} and \\u{1f4a9}{2} is yours\\.$"),
+            case(vec!["\\\\\\ 💩💩"], "^\\\\{3} \\u{1f4a9}{2}$"),
             case(vec!["My ♥♥♥ is yours.", "My 💩💩 is yours."], "^My (?:\\u{1f4a9}{2}|\\u{2665}{3}) is yours\\.$"),
             case(
                 vec!["I   ♥♥♥ 36 and ٣ and y̆y̆ and 💩💩."],
                 "^I {3}\\u{2665}{3} 36 and \\u{663} and (?:y\\u{306}){2} and \\u{1f4a9}{2}\\.$"
-            )
+            ),
+            case(vec!["e\u{301}e\u{301}e\u{301}"], "^(?:e\\u{301}){3}$"),
+            case(vec!["e\u{301}\u{301}e\u{301}\u{301}e"], "^(?:e\\u{301}\\u{301}){2}e$"),
+            case(vec!["ae\u{301}e\u{301}e"], "^a(?:e\\u{301}){2}e$")
         )]
         fn succeeds_with_escape_option(test_cases: Vec<&str>, expected_output: &str) {
             let regexp = RegExpBuilder::from(&test_cases)
@@ -1663,22 +1683,3430 @@ mod word_non_word_conversion {
     }
 }
 
-fn test_if_regexp_is_correct(regexp: String, expected_output: &str, test_cases: &[&str]) {
-    assert_eq!(
-        regexp, expected_output,
-        "\n\ninput: {:?}\nexpected: {}\nactual: {}\n\n",
-        test_cases, expected_output, regexp
-    );
+#[cfg(feature = "coloring")]
+mod syntax_highlighting {
+    use super::*;
+
+    #[rstest(test_cases, expected_output,
+        case(vec!["a", "ab"], "^ab?$"),
+        case(
+            vec!["a", "b", "c", "xyz", "xyzxyz?", "1.5"],
+            "^(?:\\d\\.\\d|xyz|(?:xyz){2}\\?|[a-c])$"
+        )
+    )]
+    fn succeeds_without_syntax_highlighting(test_cases: Vec<&str>, expected_output: &str) {
+        colored::control::set_override(true);
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_conversion_of(&[Feature::Repetition, Feature::Digit])
+            .build();
+        test_if_regexp_is_correct(regexp, expected_output, &test_cases);
+    }
+
+    #[rstest(test_cases, expected_output,
+        case(
+            vec!["a", "ab"],
+            "\u{1b}[1;33m^\u{1b}[0mab\u{1b}[1;35m?\u{1b}[0m\u{1b}[1;33m$\u{1b}[0m"
+        ),
+        case(
+            vec!["a", "b", "c", "xyz", "xyzxyz?", "1.5"],
+            "\u{1b}[1;33m^\u{1b}[0m\u{1b}[1;32m(?:\u{1b}[0m\u{1b}[103;30m\\d\u{1b}[0m\\.\u{1b}[103;30m\\d\u{1b}[0m\u{1b}[1;31m|\u{1b}[0mxyz\u{1b}[1;31m|\u{1b}[0m\u{1b}[1;32m(?:\u{1b}[0mxyz\u{1b}[1;32m)\u{1b}[0m\u{1b}[104;37m{\u{1b}[0m\u{1b}[104;37m2\u{1b}[0m\u{1b}[104;37m}\u{1b}[0m\\?\u{1b}[1;31m|\u{1b}[0m\u{1b}[1;36m[\u{1b}[0ma\u{1b}[1;36m-\u{1b}[0mc\u{1b}[1;36m]\u{1b}[0m\u{1b}[1;32m)\u{1b}[0m\u{1b}[1;33m$\u{1b}[0m"
+        )
+    )]
+    fn succeeds_with_syntax_highlighting(test_cases: Vec<&str>, expected_output: &str) {
+        colored::control::set_override(true);
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_conversion_of(&[Feature::Repetition, Feature::Digit])
+            .with_syntax_highlighting()
+            .build();
+        test_if_regexp_is_correct(regexp, expected_output, &test_cases);
+    }
+
+    #[test]
+    fn succeeds_with_syntax_highlighting_and_ignore_case_option() {
+        colored::control::set_override(true);
+        let regexp = RegExpBuilder::from(&["Ab", "ab"])
+            .with_conversion_of(&[Feature::CaseInsensitivity])
+            .with_syntax_highlighting()
+            .build();
+        assert_eq!(
+            regexp,
+            "\u{1b}[40;93m(?i)\u{1b}[0m\u{1b}[1;33m^\u{1b}[0mab\u{1b}[1;33m$\u{1b}[0m"
+        );
+    }
+
+    #[test]
+    fn succeeds_with_syntax_highlighting_and_multiline_option() {
+        colored::control::set_override(true);
+        let regexp = RegExpBuilder::from(&["Ab", "ab"])
+            .with_conversion_of(&[Feature::CaseInsensitivity])
+            .with_multiline_mode()
+            .with_syntax_highlighting()
+            .build();
+        assert_eq!(
+            regexp,
+            "\u{1b}[40;93m(?i)\u{1b}[0m\u{1b}[40;93m(?m)\u{1b}[0m\u{1b}[1;33m^\u{1b}[0mab\u{1b}[1;33m$\u{1b}[0m"
+        );
+    }
+
+    #[test]
+    fn succeeds_with_syntax_highlighting_and_custom_color_theme() {
+        colored::control::set_override(true);
+        let regexp = RegExpBuilder::from(&["abc", "xyz"])
+            .with_syntax_highlighting()
+            .with_color_theme(
+                ColorTheme::new()
+                    .with_anchor_color(Color::BrightGreen)
+                    .with_parenthesis_color(Color::Magenta)
+                    .with_pipe_color(Color::Blue),
+            )
+            .build();
+        assert_eq!(
+            regexp,
+            "\u{1b}[1;92m^\u{1b}[0m\u{1b}[1;35m(?:\u{1b}[0mabc\u{1b}[1;34m|\u{1b}[0mxyz\u{1b}[1;35m)\u{1b}[0m\u{1b}[1;92m$\u{1b}[0m"
+        );
+    }
+
+    #[test]
+    fn succeeds_with_syntax_highlighting_and_custom_pipe_color_in_nested_alternation() {
+        colored::control::set_override(true);
+        let regexp = RegExpBuilder::from(&["xab", "xcd"])
+            .with_syntax_highlighting()
+            .with_color_theme(ColorTheme::new().with_pipe_color(Color::Blue))
+            .build_regexp();
+        assert_eq!(regexp.to_regex_string(), "^x(?:ab|cd)$");
+        assert_eq!(
+            regexp.to_string(),
+            "\u{1b}[1;33m^\u{1b}[0mx\u{1b}[1;32m(?:\u{1b}[0mab\u{1b}[1;34m|\u{1b}[0mcd\u{1b}[1;32m)\u{1b}[0m\u{1b}[1;33m$\u{1b}[0m"
+        );
+    }
+
+    #[rstest(theme, expected_output,
+        case(
+            ColorTheme::new(),
+            "\u{1b}[1;33m^\u{1b}[0m\u{1b}[1;36m[\u{1b}[0ma\u{1b}[1;36m-\u{1b}[0mz\u{1b}[1;36m]\u{1b}[0m\u{1b}[1;33m$\u{1b}[0m"
+        ),
+        case(
+            ColorTheme::new()
+                .with_anchor_color(Color::Red)
+                .with_range_hyphen_color(Color::Green)
+                .clone(),
+            "\u{1b}[1;31m^\u{1b}[0m\u{1b}[1;36m[\u{1b}[0ma\u{1b}[1;32m-\u{1b}[0mz\u{1b}[1;36m]\u{1b}[0m\u{1b}[1;31m$\u{1b}[0m"
+        )
+    )]
+    fn succeeds_with_syntax_highlighting_of_range(theme: ColorTheme, expected_output: &str) {
+        colored::control::set_override(true);
+        let test_cases = ('a'..='z').map(|it| it.to_string()).collect::<Vec<_>>();
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_syntax_highlighting()
+            .with_color_theme(&theme)
+            .build_regexp();
+        assert_eq!(regexp.to_regex_string(), "^[a-z]$");
+        assert_eq!(regexp.to_string(), expected_output);
+    }
+
+    #[test]
+    fn succeeds_without_ansi_codes_in_regex_string() {
+        colored::control::set_override(true);
+        let regexp = RegExpBuilder::from(&["abc", "xyz"])
+            .with_syntax_highlighting()
+            .build_regexp();
+        assert_eq!(regexp.to_regex_string(), "^(?:abc|xyz)$");
+        assert_eq!(
+            regexp.to_string(),
+            "\u{1b}[1;33m^\u{1b}[0m\u{1b}[1;32m(?:\u{1b}[0mabc\u{1b}[1;31m|\u{1b}[0mxyz\u{1b}[1;32m)\u{1b}[0m\u{1b}[1;33m$\u{1b}[0m"
+        );
+    }
+
+    #[test]
+    fn succeeds_with_syntax_highlighting_and_disabled_truecolor() {
+        colored::control::set_override(true);
+        let regexp = RegExpBuilder::from(&["a", "b"])
+            .with_syntax_highlighting()
+            .with_color_theme(ColorTheme::new().with_bracket_color(Color::TrueColor {
+                r: 255,
+                g: 100,
+                b: 0,
+            }))
+            .with_truecolor_disabled()
+            .build();
+        assert_eq!(
+            regexp,
+            "\u{1b}[1;33m^\u{1b}[0m\u{1b}[1;91m[\u{1b}[0mab\u{1b}[1;91m]\u{1b}[0m\u{1b}[1;33m$\u{1b}[0m"
+        );
+    }
 }
 
-fn test_if_regexp_matches_test_cases(expected_output: &str, test_cases: Vec<&str>) {
-    let re = Regex::new(expected_output).unwrap();
-    for test_case in test_cases {
-        assert!(
-            re.is_match(test_case),
-            "\n\n\"{}\" does not match regex {}\n\n",
-            test_case,
-            expected_output
+#[cfg(not(feature = "coloring"))]
+mod syntax_highlighting {
+    use super::*;
+
+    #[rstest(test_cases, expected_output,
+        case(vec!["my ♥ and 💩 is yours."], "(?i)^my ♥ and 💩 is yours\\.$"),
+        case(vec!["Ab", "ab"], "(?i)^ab$")
+    )]
+    fn succeeds_with_plain_output_without_coloring_feature(
+        test_cases: Vec<&str>,
+        expected_output: &str,
+    ) {
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_conversion_of(&[Feature::CaseInsensitivity])
+            .with_syntax_highlighting()
+            .build();
+        test_if_regexp_is_correct(regexp, expected_output, &test_cases);
+        test_if_regexp_matches_test_cases(expected_output, test_cases);
+    }
+}
+
+mod match_count {
+    use super::*;
+
+    #[rstest(test_cases, expected_match_count,
+        case(vec![""], Some(1)),
+        case(vec!["", "a", "b"], Some(3)),
+        case(vec!["abc"], Some(1)),
+        case(vec!["ac", "ad", "bc", "bd"], Some(4)),
+        case(vec!["a", "aa", "aaa"], Some(3)),
+        case(vec!["abc", "abxyc", "def"], Some(3))
+    )]
+    fn succeeds(test_cases: Vec<&str>, expected_match_count: Option<u128>) {
+        let regexp = RegExpBuilder::from(&test_cases).build_regexp();
+        assert_eq!(regexp.match_count(), expected_match_count);
+    }
+
+    #[rstest(test_cases, expected_match_count,
+        case(vec!["a", "aa", "aaaa"], Some(3)),
+        case(vec!["abab", "ababab"], Some(2))
+    )]
+    fn succeeds_with_repetition_feature(test_cases: Vec<&str>, expected_match_count: Option<u128>) {
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_conversion_of(&[Feature::Repetition])
+            .build_regexp();
+        assert_eq!(regexp.match_count(), expected_match_count);
+    }
+
+    #[test]
+    fn succeeds_with_digit_feature() {
+        let regexp = RegExpBuilder::from(&["1", "22"])
+            .with_conversion_of(&[Feature::Digit])
+            .build_regexp();
+        assert_eq!(regexp.to_string(), "^\\d(?:\\d)?$");
+        assert_eq!(regexp.match_count(), Some(650 + 650 * 650));
+    }
+}
+
+mod optional_surrounding_brackets {
+    use super::*;
+
+    #[rstest(test_cases, bracket_pairs, expected_output,
+        case(vec!["foo"], vec![('[', ']'), ('{', '}')], "^[\\[{]?foo[\\]}]?$"),
+        case(vec!["foo"], vec![('(', ')')], "^\\(?foo\\)?$"),
+        case(vec!["abc", "xyz"], vec![('<', '>')], "^<?(?:abc|xyz)>?$")
+    )]
+    fn succeeds_with_loose_brackets(
+        test_cases: Vec<&str>,
+        bracket_pairs: Vec<(char, char)>,
+        expected_output: &str,
+    ) {
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_optional_surrounding_brackets(&bracket_pairs, false)
+            .build();
+        test_if_regexp_is_correct(regexp, expected_output, &test_cases);
+        test_if_regexp_matches_test_cases(expected_output, test_cases);
+    }
+
+    #[rstest(test_cases, bracket_pairs, expected_output,
+        case(vec!["foo"], vec![('[', ']'), ('{', '}')], "^(?:\\{foo\\}|\\[foo\\]|foo)$"),
+        case(vec!["abc", "xyz"], vec![('<', '>')], "^(?:<(?:abc|xyz)>|abc|xyz)$")
+    )]
+    fn succeeds_with_balanced_brackets(
+        test_cases: Vec<&str>,
+        bracket_pairs: Vec<(char, char)>,
+        expected_output: &str,
+    ) {
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_optional_surrounding_brackets(&bracket_pairs, true)
+            .build();
+        test_if_regexp_is_correct(regexp, expected_output, &test_cases);
+        test_if_regexp_matches_test_cases(expected_output, test_cases.clone());
+
+        let re = Regex::new(expected_output).unwrap();
+        for &(opening_bracket, closing_bracket) in bracket_pairs.iter() {
+            assert!(re.is_match(&format!(
+                "{}{}{}",
+                opening_bracket, test_cases[0], closing_bracket
+            )));
+            assert!(!re.is_match(&format!("{}{}", opening_bracket, test_cases[0])));
+        }
+    }
+}
+
+mod char_class_escaping {
+    use super::*;
+
+    #[rstest(test_cases, expected_output,
+        case(vec!["^", "a"], "^[\\^a]$"),
+        case(vec!["a", "^", "~"], "^[\\^a~]$"),
+        case(vec!["A", "^", "a"], "^[A^a]$"),
+        case(vec!["A", "^"], "^[A^]$"),
+        case(vec!["-", "a"], "^[-a]$"),
+        case(vec!["!", "-", "a"], "^[!\\-a]$"),
+        case(vec!["!", "-"], "^[!-]$"),
+        case(vec!["+", ",", "-", ".", "/"], "^[+-/]$"),
+        case(vec!["-", ".", "/"], "^[\\--/]$")
+    )]
+    fn succeeds(test_cases: Vec<&str>, expected_output: &str) {
+        let regexp = RegExpBuilder::from(&test_cases).build();
+        test_if_regexp_is_correct(regexp, expected_output, &test_cases);
+        test_if_regexp_matches_test_cases(expected_output, test_cases);
+    }
+}
+
+mod range_compression {
+    use super::*;
+
+    #[rstest(test_cases, expected_output,
+        case(vec!["a", "b", "c", "d", "e", "f"], "^[abcdef]$"),
+        case(vec!["a", "b", "c", "x"], "^[abcx]$"),
+        case(vec!["a", "-", "b", "c", "]"], "^[-\\]abc]$"),
+        case(vec!["1a", "2a", "3a"], "^[123]a$")
+    )]
+    fn succeeds_with_disabled_compression(test_cases: Vec<&str>, expected_output: &str) {
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_range_compression_disabled()
+            .build();
+        test_if_regexp_is_correct(regexp, expected_output, &test_cases);
+        test_if_regexp_matches_test_cases(expected_output, test_cases);
+    }
+
+    #[rstest(
+        minimum_range_length,
+        expected_output,
+        case(2, "^[A-Da-cx-y]$"),
+        case(3, "^[A-Da-cxy]$"),
+        case(4, "^[A-Dabcxy]$"),
+        case(5, "^[ABCDabcxy]$")
+    )]
+    fn succeeds_with_minimum_range_length(minimum_range_length: u32, expected_output: &str) {
+        let test_cases = vec!["a", "b", "c", "x", "y", "A", "B", "C", "D"];
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_minimum_range_length(minimum_range_length)
+            .build();
+        test_if_regexp_is_correct(regexp, expected_output, &test_cases);
+        test_if_regexp_matches_test_cases(expected_output, test_cases);
+    }
+
+    #[test]
+    fn succeeds_with_enabled_compression() {
+        let test_cases = vec!["a", "b", "c", "d", "e", "f"];
+        let regexp = RegExpBuilder::from(&test_cases).build();
+        test_if_regexp_is_correct(regexp, "^[a-f]$", &test_cases);
+    }
+}
+
+mod escape_style {
+    use super::*;
+
+    #[rstest(
+        style,
+        expected_output,
+        case(EscapeStyle::UnicodeBraces, "^\\u{e9}\\t\\u{1f4a9}\\n$"),
+        case(EscapeStyle::FourDigitU, "^\\u00e9\\t\\u{1f4a9}\\n$"),
+        case(EscapeStyle::HexByte, "^\\xe9\\t\\x{1f4a9}\\n$"),
+        case(EscapeStyle::HexBraces, "^\\x{00e9}\\t\\x{1f4a9}\\n$")
+    )]
+    fn succeeds_without_surrogate_pairs(style: EscapeStyle, expected_output: &str) {
+        let test_cases = vec!["é\t💩\n"];
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_escaping_of_non_ascii_chars(false)
+            .with_escape_style(style)
+            .build();
+        test_if_regexp_is_correct(regexp, expected_output, &test_cases);
+        test_if_regexp_matches_test_cases(expected_output, test_cases);
+    }
+
+    #[rstest(
+        style,
+        expected_output,
+        case(EscapeStyle::UnicodeBraces, "^\\u{e9}\\t\\u{d83d}\\u{dca9}\\n$"),
+        case(EscapeStyle::FourDigitU, "^\\u00e9\\t\\ud83d\\udca9\\n$"),
+        case(EscapeStyle::HexByte, "^\\xe9\\t\\x{d83d}\\x{dca9}\\n$"),
+        case(EscapeStyle::HexBraces, "^\\x{00e9}\\t\\x{d83d}\\x{dca9}\\n$")
+    )]
+    fn succeeds_with_surrogate_pairs(style: EscapeStyle, expected_output: &str) {
+        let test_cases = vec!["é\t💩\n"];
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_escaping_of_non_ascii_chars(true)
+            .with_escape_style(style)
+            .build();
+        test_if_regexp_is_correct(regexp, expected_output, &test_cases);
+    }
+
+    #[rstest(
+        style,
+        expected_output,
+        case(EscapeStyle::UnicodeBraces, "^\\u{e9}{3}$"),
+        case(EscapeStyle::FourDigitU, "^\\u00e9{3}$"),
+        case(EscapeStyle::HexByte, "^\\xe9{3}$"),
+        case(EscapeStyle::HexBraces, "^\\x{00e9}{3}$")
+    )]
+    fn succeeds_with_repetitions(style: EscapeStyle, expected_output: &str) {
+        let test_cases = vec!["ééé"];
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_conversion_of(&[Feature::Repetition])
+            .with_escaping_of_non_ascii_chars(false)
+            .with_escape_style(style)
+            .build();
+        test_if_regexp_is_correct(regexp, expected_output, &test_cases);
+        test_if_regexp_matches_test_cases(expected_output, test_cases);
+    }
+
+    #[rstest(test_cases, expected_output,
+        case(vec!["\u{1}"], "^\\cA$"),
+        case(vec!["a\u{1a}b"], "^a\\cZb$"),
+        case(vec!["\u{3}\t\n\r"], "^\\cC\\t\\n\\r$"),
+        case(vec!["\u{1b}é💩"], "^\u{1b}\\u{e9}\\u{1f4a9}$"),
+        case(vec!["\u{2}\u{2}\u{2}"], "^\\cB{3}$")
+    )]
+    fn succeeds_with_control_char_style(test_cases: Vec<&str>, expected_output: &str) {
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_conversion_of(&[Feature::Repetition])
+            .with_escaping_of_non_ascii_chars(false)
+            .with_escape_style(EscapeStyle::ControlChar)
+            .build();
+        test_if_regexp_is_correct(regexp, expected_output, &test_cases);
+    }
+
+    #[rstest(test_cases, expected_output,
+        case(vec!["\u{1}x"], "^\\cAx$"),
+        case(vec!["\u{1}", "\u{2}"], "^[\\cA\\cB]$"),
+        case(vec!["\u{1}\t", "\u{2}\t"], "^[\\cA\\cB]\\t$"),
+        case(vec!["\u{1b}é"], "^\u{1b}é$")
+    )]
+    fn succeeds_with_control_char_style_and_unescaped_non_ascii_chars(
+        test_cases: Vec<&str>,
+        expected_output: &str,
+    ) {
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_escape_style(EscapeStyle::ControlChar)
+            .build();
+        test_if_regexp_is_correct(regexp, expected_output, &test_cases);
+    }
+
+    #[rstest(test_cases, expected_output,
+        case(vec!["a\u{1}\t\n"], "^a\u{1}\\t\\n$"),
+        case(vec!["\u{e9}", "\u{1f600}"], "^[\\x{00e9}\\x{1f600}]$"),
+        case(vec!["a", "\u{e9}", "\u{3b1}"], "^[a\\x{00e9}\\x{03b1}]$"),
+        case(vec!["\u{2603}"], "^\\x{2603}$")
+    )]
+    fn succeeds_with_hex_braces_style(test_cases: Vec<&str>, expected_output: &str) {
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_escaping_of_non_ascii_chars(false)
+            .with_escape_style(EscapeStyle::HexBraces)
+            .build();
+        test_if_regexp_is_correct(regexp, expected_output, &test_cases);
+        test_if_regexp_matches_test_cases(expected_output, test_cases);
+    }
+}
+
+mod slash_escaping {
+    use super::*;
+
+    #[rstest(test_cases, expected_output,
+        case(vec!["a/b"], "^a\\/b$"),
+        case(vec!["//"], "^\\/\\/$"),
+        case(vec!["a", "/"], "^[\\/a]$")
+    )]
+    fn succeeds_with_escaped_slashes(test_cases: Vec<&str>, expected_output: &str) {
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_escaping_of_slashes()
+            .build();
+        test_if_regexp_is_correct(regexp, expected_output, &test_cases);
+    }
+
+    #[rstest(test_cases, expected_output,
+        case(vec!["a/b"], "^a/b$"),
+        case(vec!["a", "/"], "^[/a]$")
+    )]
+    fn succeeds_without_escaped_slashes(test_cases: Vec<&str>, expected_output: &str) {
+        let regexp = RegExpBuilder::from(&test_cases).build();
+        test_if_regexp_is_correct(regexp, expected_output, &test_cases);
+        test_if_regexp_matches_test_cases(expected_output, test_cases);
+    }
+}
+
+mod exact_repetition_style {
+    use super::*;
+
+    #[rstest(test_cases, style, expected_output,
+        case(vec!["aaa"], ExactRepetitionStyle::Braces, "^a{3}$"),
+        case(vec!["aaa"], ExactRepetitionStyle::Expanded, "^aaa$"),
+        case(vec!["aaa", "bcbc"], ExactRepetitionStyle::Braces, "^(?:a{3}|(?:bc){2})$"),
+        case(vec!["aaa", "bcbc"], ExactRepetitionStyle::Expanded, "^(?:aaa|bcbc)$"),
+        case(vec!["zaaaz"], ExactRepetitionStyle::Expanded, "^zaaaz$"),
+        case(vec!["aa", "aaa"], ExactRepetitionStyle::Braces, "^a{2,3}$"),
+        case(vec!["aa", "aaa"], ExactRepetitionStyle::Expanded, "^a{2,3}$")
+    )]
+    fn succeeds(test_cases: Vec<&str>, style: ExactRepetitionStyle, expected_output: &str) {
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_conversion_of(&[Feature::Repetition])
+            .with_exact_repetition_style(style)
+            .build();
+        test_if_regexp_is_correct(regexp, expected_output, &test_cases);
+        test_if_regexp_matches_test_cases(expected_output, test_cases);
+    }
+
+    #[rstest(test_cases, style, expected_output,
+        case(vec!["aa", "aaa"], ExactRepetitionStyle::Braces, "^(?:aa|a{3})$"),
+        case(vec!["aa", "aaa"], ExactRepetitionStyle::Expanded, "^(?:aa|aaa)$"),
+        case(vec!["1111"], ExactRepetitionStyle::Braces, "^\\d{4}$"),
+        case(vec!["1111"], ExactRepetitionStyle::Expanded, "^\\d\\d\\d\\d$")
+    )]
+    fn succeeds_with_minimum_repetitions(
+        test_cases: Vec<&str>,
+        style: ExactRepetitionStyle,
+        expected_output: &str,
+    ) {
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_conversion_of(&[Feature::Repetition, Feature::Digit])
+            .with_minimum_repetitions(2)
+            .with_exact_repetition_style(style)
+            .build();
+        test_if_regexp_is_correct(regexp, expected_output, &test_cases);
+        test_if_regexp_matches_test_cases(expected_output, test_cases);
+    }
+
+    #[rstest(
+        style,
+        expected_output,
+        case(ExactRepetitionStyle::Braces, "(?:ab|c){2}"),
+        case(ExactRepetitionStyle::Expanded, "(?:ab|c)(?:ab|c)")
+    )]
+    fn succeeds_with_repeated_alternation(style: ExactRepetitionStyle, expected_output: &str) {
+        use grex::ast::{Expression, Quantifier};
+        use std::sync::Arc;
+
+        let config = Arc::new(
+            RegExpBuilder::from(&["a"])
+                .with_exact_repetition_style(style)
+                .config()
+                .clone(),
+        );
+        let expr = Expression::repetition(
+            Expression::alternation(
+                vec![
+                    Expression::literal("ab", &config),
+                    Expression::literal("c", &config),
+                ],
+                &config,
+            ),
+            Quantifier::Range(2, 2),
+            &config,
+        );
+        assert_eq!(expr.to_string(), expected_output);
+    }
+
+    #[rstest(
+        style,
+        expected_output,
+        case(ExactRepetitionStyle::Braces, "(ab|c){2}"),
+        case(ExactRepetitionStyle::Expanded, "(ab|c){2}")
+    )]
+    fn succeeds_with_repeated_capturing_group(style: ExactRepetitionStyle, expected_output: &str) {
+        use grex::ast::{Expression, Quantifier};
+        use std::sync::Arc;
+
+        let config = Arc::new(
+            RegExpBuilder::from(&["a"])
+                .with_conversion_of(&[Feature::CapturingGroup])
+                .with_exact_repetition_style(style)
+                .config()
+                .clone(),
+        );
+        let expr = Expression::repetition(
+            Expression::alternation(
+                vec![
+                    Expression::literal("ab", &config),
+                    Expression::literal("c", &config),
+                ],
+                &config,
+            ),
+            Quantifier::Range(2, 2),
+            &config,
+        );
+        assert_eq!(expr.to_string(), expected_output);
+    }
+}
+
+mod verification {
+    use super::*;
+    use grex::RegExp;
+
+    #[rstest(test_cases, other_test_cases, expected_result,
+        case(vec!["abc", "abd"], vec!["abc", "abd"], true),
+        case(vec!["abc", "abd"], vec!["abc", "abe"], false),
+        case(vec!["abc", "abd"], vec!["ab"], false),
+        case(vec!["a", "aa", "aaa"], vec!["aa"], true),
+        case(vec!["a", "aa", "aaa"], vec!["aaaa"], false),
+        case(vec!["", "ab"], vec![""], true),
+        case(vec!["I ♥ cake"], vec!["I ♥ cake"], true)
+    )]
+    fn succeeds(test_cases: Vec<&str>, other_test_cases: Vec<&str>, expected_result: bool) {
+        let regexp = RegExp::new(&test_cases, &RegExpConfig::new());
+        assert!(regexp.verify(&test_cases));
+        assert_eq!(regexp.verify(&other_test_cases), expected_result);
+    }
+
+    #[rstest(test_cases, other_test_cases, expected_result,
+        case(vec!["12", "345"], vec!["90"], true),
+        case(vec!["12", "345"], vec!["9"], false),
+        case(vec!["a b", "c d"], vec!["x\ty"], true),
+        case(vec!["a b", "c d"], vec!["x_y"], false)
+    )]
+    fn succeeds_with_conversion(
+        test_cases: Vec<&str>,
+        other_test_cases: Vec<&str>,
+        expected_result: bool,
+    ) {
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_conversion_of(&[Feature::Digit, Feature::Space, Feature::Word])
+            .build_regexp();
+        assert!(regexp.verify(&test_cases));
+        assert_eq!(regexp.verify(&other_test_cases), expected_result);
+    }
+
+    #[rstest(test_cases, is_dotall_enabled, other_test_cases, expected_result,
+        case(vec!["a1b", "a2b", "a3b"], false, vec!["axb"], true),
+        case(vec!["a1b", "a2b", "a3b"], false, vec!["a\nb"], false),
+        case(vec!["a1b", "a2b", "a3b"], true, vec!["a\nb"], true),
+        case(vec!["a1b", "a2b", "a\nb"], false, vec!["axb", "a\nb"], true)
+    )]
+    fn succeeds_with_wildcard(
+        test_cases: Vec<&str>,
+        is_dotall_enabled: bool,
+        other_test_cases: Vec<&str>,
+        expected_result: bool,
+    ) {
+        let mut builder = RegExpBuilder::from(&test_cases);
+        builder.with_wildcard_threshold(2);
+        if is_dotall_enabled {
+            builder.with_dotall_mode();
+        }
+        let regexp = builder.build_regexp();
+        assert!(regexp.verify(&test_cases));
+        assert_eq!(regexp.verify(&other_test_cases), expected_result);
+        let re = Regex::new(&regexp.to_string()).unwrap();
+        assert_eq!(
+            other_test_cases.iter().all(|it| re.is_match(it)),
+            expected_result
+        );
+    }
+
+    #[test]
+    fn succeeds_with_case_insensitive_matching() {
+        let test_cases = vec!["abc", "ABD"];
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_conversion_of(&[Feature::CaseInsensitivity])
+            .build_regexp();
+        assert!(regexp.verify(&test_cases));
+        assert!(regexp.verify(&["ABC", "abd"]));
+        assert!(!regexp.verify(&["abe"]));
+    }
+
+    #[test]
+    fn succeeds_with_enclosing_chars() {
+        let test_cases = vec!["'a'", "'b'"];
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_enclosing_char_detection()
+            .with_flavor(RegExpFlavor::Pcre)
+            .build_regexp();
+        assert!(regexp.verify(&test_cases));
+        assert!(!regexp.verify(&["\"a\""]));
+        assert!(!regexp.verify(&["'a"]));
+    }
+
+    #[test]
+    fn succeeds_with_display() {
+        let regexp = RegExp::new(&["a", "b"], &RegExpConfig::new());
+        assert_eq!(regexp.to_string(), "^[ab]$");
+        assert_eq!(regexp.to_regex_string(), "^[ab]$");
+        assert_eq!(regexp.capturing_group_count(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "No test cases have been provided for regular expression generation")]
+    fn fails_without_test_cases() {
+        RegExp::new(&Vec::<String>::new(), &RegExpConfig::new());
+    }
+}
+
+mod strict_escaping {
+    use super::*;
+
+    #[rstest(test_cases, expected_output,
+        case(vec!["a b#c"], "^a\\ b\\#c$"),
+        case(vec!["say \"hi\""], "^say\\ \\\"hi\\\"$"),
+        case(vec!["a/b_c"], "^a\\/b\\_c$"),
+        case(vec!["a+b.c"], "^a\\+b\\.c$"),
+        case(vec!["a\\b"], "^a\\\\b$"),
+        case(vec!["<a>"], "^<a>$"),
+        case(vec!["a", "#", " "], "^[\\ \\#a]$"),
+        case(vec!["1 2", "3 4"], "^\\d\\ \\d$")
+    )]
+    fn succeeds_with_strict_escaping(test_cases: Vec<&str>, expected_output: &str) {
+        let mut builder = RegExpBuilder::from(&test_cases);
+        if test_cases.iter().all(|it| it.contains(' ')) {
+            builder.with_conversion_of(&[Feature::Digit]);
+        }
+        let regexp = builder.with_strict_escaping().build();
+        test_if_regexp_is_correct(regexp, expected_output, &test_cases);
+    }
+
+    #[test]
+    fn succeeds_with_default_escaping() {
+        let test_cases = vec!["a b#c"];
+        let default = RegExpBuilder::from(&test_cases).build();
+        let strict = RegExpBuilder::from(&test_cases)
+            .with_strict_escaping()
+            .build();
+        assert_eq!(default, "^a b#c$");
+        assert_eq!(strict, "^a\\ b\\#c$");
+
+        let extended = Regex::new(&format!("(?x){}", strict)).unwrap();
+        assert!(extended.is_match("a b#c"));
+    }
+}
+
+mod literal_delimiter {
+    use super::*;
+
+    #[rstest(test_cases, expected_output,
+        case(vec!["a"], "/^a$/"),
+        case(vec!["a/b"], "/^a\\/b$/"),
+        case(vec!["a", "b", "bc"], "/^(?:bc?|a)$/")
+    )]
+    fn succeeds_with_slash_delimiter(test_cases: Vec<&str>, expected_output: &str) {
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_literal_delimiter('/')
+            .build();
+        test_if_regexp_is_correct(regexp, expected_output, &test_cases);
+    }
+
+    #[rstest(test_cases, expected_output,
+        case(vec!["big", "BIGGER"], "/^big(?:ger)?$/i"),
+        case(vec!["ABC", "abc"], "/^abc$/i")
+    )]
+    fn succeeds_with_case_insensitive_flag(test_cases: Vec<&str>, expected_output: &str) {
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_conversion_of(&[Feature::CaseInsensitivity])
+            .with_literal_delimiter('/')
+            .build();
+        test_if_regexp_is_correct(regexp, expected_output, &test_cases);
+    }
+
+    #[test]
+    fn succeeds_with_custom_delimiter() {
+        let test_cases = vec!["a/b", "A/B"];
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_conversion_of(&[Feature::CaseInsensitivity])
+            .with_literal_delimiter('#')
+            .build();
+        test_if_regexp_is_correct(regexp, "#^a/b$#i", &test_cases);
+    }
+
+    #[rstest(test_cases, delimiter, expected_output,
+        case(vec!["a#b"], '#', "#^a\\#b$#"),
+        case(vec!["a", "#", "b"], '#', "#^[\\#ab]$#"),
+        case(vec!["a|b"], '|', "|^a\\|b$|"),
+        case(vec!["a~b", "c"], '~', "~^(?:a\\~b|c)$~")
+    )]
+    fn succeeds_with_escaped_custom_delimiter(
+        test_cases: Vec<&str>,
+        delimiter: char,
+        expected_output: &str,
+    ) {
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_literal_delimiter(delimiter)
+            .build();
+        test_if_regexp_is_correct(regexp, expected_output, &test_cases);
+    }
+
+    #[test]
+    fn succeeds_with_escaped_custom_delimiter_and_strict_escaping() {
+        let test_cases = vec!["a#b"];
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_literal_delimiter('#')
+            .with_strict_escaping()
+            .build();
+        test_if_regexp_is_correct(regexp, "#^a\\#b$#", &test_cases);
+    }
+
+    #[test]
+    fn succeeds_with_unicode_disabled_flag() {
+        let regexp = RegExpBuilder::from_bytes(&[b"a#\xff".to_vec()])
+            .with_literal_delimiter('#')
+            .build();
+        assert_eq!(regexp, "#(?-u)^a\\#\\xff$#");
+    }
+}
+
+mod pattern_and_flags {
+    use super::*;
+
+    #[test]
+    fn succeeds_with_case_insensitive_flag() {
+        let test_cases = vec!["big", "BIGGER"];
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_conversion_of(&[Feature::CaseInsensitivity])
+            .build_regexp();
+        let (pattern, flags) = regexp.to_pattern_and_flags();
+        assert_eq!(pattern, "^big(?:ger)?$");
+        assert!(flags.contains(RegexFlags::CASE_INSENSITIVE));
+
+        let compiled = regex::RegexBuilder::new(&pattern)
+            .case_insensitive(flags.contains(RegexFlags::CASE_INSENSITIVE))
+            .build()
+            .unwrap();
+        for test_case in test_cases {
+            assert!(compiled.is_match(test_case));
+        }
+    }
+
+    #[test]
+    fn succeeds_without_flags() {
+        let regexp = RegExpBuilder::from(&["a", "b"])
+            .with_syntax_highlighting()
+            .with_literal_delimiter('/')
+            .build_regexp();
+        let (pattern, flags) = regexp.to_pattern_and_flags();
+        assert_eq!(pattern, "^[ab]$");
+        assert!(flags.is_empty());
+    }
+}
+
+mod byte_mode {
+    use super::*;
+
+    #[rstest(test_cases, expected_output,
+        case(vec![b"\xc3\x28".as_ref()], "(?-u)^\\xc3\\($"),
+        case(vec![b"\x00\xff".as_ref(), b"a\xe9"], "(?-u)^(?:\\x00\\xff|a\\xe9)$"),
+        case(vec![b"\x80".as_ref(), b"\x81", b"\x82", b"\x83"], "(?-u)^[\\x80-\\x83]$"),
+        case(vec![b"\r\n".as_ref()], "(?-u)^\\r\\n$")
+    )]
+    fn succeeds(test_cases: Vec<&[u8]>, expected_output: &str) {
+        let regexp = RegExpBuilder::from_bytes(&test_cases).build();
+        assert_eq!(regexp, expected_output);
+
+        let compiled = regex::bytes::Regex::new(&regexp).unwrap();
+        for test_case in test_cases {
+            assert!(compiled.is_match(test_case));
+        }
+    }
+
+    #[test]
+    fn succeeds_without_matching_utf8_encoding() {
+        let regexp = RegExpBuilder::from_bytes(&[b"\xe9"]).build();
+        let compiled = regex::bytes::Regex::new(&regexp).unwrap();
+        assert!(!compiled.is_match("é".as_bytes()));
+    }
+
+    #[rstest(features, expected_output,
+        case(vec![Feature::CaseInsensitivity], "(?-u)(?i)^ab\\xe9$"),
+        case(vec![Feature::Digit, Feature::Word], "(?-u)^\\w\\w\\xe9$")
+    )]
+    fn succeeds_with_ascii_only_conversion(features: Vec<Feature>, expected_output: &str) {
+        let test_cases = vec![b"AB\xe9".as_ref(), b"ab\xe9"];
+        let regexp = RegExpBuilder::from_bytes(&test_cases)
+            .with_conversion_of(&features)
+            .build();
+        assert_eq!(regexp, expected_output);
+    }
+
+    #[test]
+    fn succeeds_with_separate_flags() {
+        let regexp = RegExpBuilder::from_bytes(&[b"\xe9"]).build_regexp();
+        let (pattern, flags) = regexp.to_pattern_and_flags();
+        assert_eq!(pattern, "^\\xe9$");
+        assert!(flags.contains(RegexFlags::UNICODE_DISABLED));
+    }
+}
+
+mod ascii_only_output {
+    use super::*;
+
+    #[rstest(test_cases, expected_output,
+        case(vec!["é"], "^\\u{e9}$"),
+        case(vec!["a", "💩"], "^[a\\u{1f4a9}]$"),
+        case(vec!["abc"], "^abc$")
+    )]
+    fn succeeds_with_forced_escaping(test_cases: Vec<&str>, expected_output: &str) {
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_ascii_only_output()
+            .try_build();
+        assert_eq!(regexp, Ok(expected_output.to_string()));
+        test_if_regexp_matches_test_cases(expected_output, test_cases);
+    }
+
+    #[test]
+    fn succeeds_with_surrogate_pairs() {
+        let regexp = RegExpBuilder::from(&["💩"])
+            .with_escaping_of_non_ascii_chars(true)
+            .with_ascii_only_output()
+            .try_build();
+        assert_eq!(regexp, Ok("^\\u{d83d}\\u{dca9}$".to_string()));
+    }
+
+    #[test]
+    fn fails_with_non_ascii_literal_delimiter() {
+        let regexp = RegExpBuilder::from(&["a"])
+            .with_ascii_only_output()
+            .with_literal_delimiter('§')
+            .try_build();
+        assert_eq!(regexp, Err(RegExpError::NonAsciiChar('§')));
+    }
+
+    #[test]
+    #[should_panic(expected = "Ascii-only output is violated by the non-ascii character '§'")]
+    fn build_panics_with_non_ascii_literal_delimiter() {
+        RegExpBuilder::from(&["a"])
+            .with_ascii_only_output()
+            .with_literal_delimiter('§')
+            .build();
+    }
+}
+
+mod explanation {
+    use super::*;
+
+    #[rstest(test_cases, expected_explanation,
+        case(vec!["abc", "abd"], "matches 'ab' followed by one of [c, d]"),
+        case(vec!["a", "aa", "aaa"], "matches 'a' followed by optionally ('a' followed by optionally 'a')"),
+        case(vec!["ab", "cd", "x"], "matches one of ('ab', 'cd', 'x')"),
+        case(vec!["", "ab"], "matches optionally 'ab'"),
+        case(vec!["a", "b", "c", "d", "e", "f", "g", "h", "i", "j", "k", "m"], "matches one of 12 characters between 'a' and 'm'")
+    )]
+    fn succeeds(test_cases: Vec<&str>, expected_explanation: &str) {
+        let regexp = RegExpBuilder::from(&test_cases).build_regexp();
+        assert_eq!(regexp.explain(), expected_explanation);
+    }
+
+    #[rstest(test_cases, features, expected_explanation,
+        case(vec!["aaa", "bcbc"], vec![Feature::Repetition], "matches one of ('a' repeated 3 times, 'bc' repeated 2 times)"),
+        case(vec!["12", "a"], vec![Feature::Digit], "matches one of (a digit followed by a digit, 'a')"),
+        case(vec!["big", "BIGGER"], vec![Feature::CaseInsensitivity], "matches 'big' followed by optionally 'ger', ignoring case")
+    )]
+    fn succeeds_with_conversion_features(
+        test_cases: Vec<&str>,
+        features: Vec<Feature>,
+        expected_explanation: &str,
+    ) {
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_conversion_of(&features)
+            .build_regexp();
+        assert_eq!(regexp.explain(), expected_explanation);
+    }
+}
+
+mod pretty_string {
+    use super::*;
+
+    #[test]
+    fn succeeds_with_nested_repetitions() {
+        let regexp = RegExpBuilder::from(&["a", "aa", "aaa"]).build_regexp();
+        assert_eq!(
+            regexp.to_pretty_string(),
+            indoc(
+                "
+                Concatenation
+                  Literal a
+                  Repetition
+                    Concatenation
+                      Literal a
+                      Repetition
+                        Literal a
+                        Quantifier ?
+                    Quantifier ?
+                "
+            )
+        );
+    }
+
+    #[test]
+    fn succeeds_with_alternation() {
+        let regexp = RegExpBuilder::from(&["aaa", "bcbc", "x", "y"])
+            .with_conversion_of(&[Feature::Repetition])
+            .with_syntax_highlighting()
+            .build_regexp();
+        assert_eq!(
+            regexp.to_pretty_string(),
+            indoc(
+                "
+                Alternation
+                  CharacterClass [xy]
+                  Literal a{3}
+                  Literal (?:bc){2}
+                "
+            )
+        );
+    }
+
+    #[test]
+    fn succeeds_with_case_insensitive_flag() {
+        let regexp = RegExpBuilder::from(&["big", "BIGGER"])
+            .with_conversion_of(&[Feature::CaseInsensitivity])
+            .build_regexp();
+        assert_eq!(
+            regexp.to_pretty_string(),
+            indoc(
+                "
+                Flag (?i)
+                Concatenation
+                  Literal big
+                  Repetition
+                    Literal ger
+                    Quantifier ?
+                "
+            )
+        );
+    }
+
+    fn indoc(text: &str) -> String {
+        let lines = text.lines().skip(1).collect::<Vec<_>>();
+        let indentation = lines[0].len() - lines[0].trim_start().len();
+        lines
+            .iter()
+            .filter(|it| !it.trim().is_empty())
+            .map(|it| &it[indentation..])
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+mod capturing_group_count {
+    use super::*;
+
+    #[rstest(test_cases, features, expected_count,
+        case(vec!["abc", "abd"], vec![Feature::CapturingGroup], 0),
+        case(vec!["a", "aa", "aaa"], vec![Feature::CapturingGroup], 1),
+        case(vec!["a", "aa", "aaa"], vec![], 0),
+        case(vec!["aaa", "bcbc", "x"], vec![Feature::Repetition, Feature::CapturingGroup], 2),
+        case(vec!["(a)", "(b", "[c]"], vec![Feature::CapturingGroup], 2),
+        case(vec!["big", "BIGGER"], vec![Feature::CaseInsensitivity, Feature::CapturingGroup], 1)
+    )]
+    fn succeeds(test_cases: Vec<&str>, features: Vec<Feature>, expected_count: usize) {
+        let mut builder = RegExpBuilder::from(&test_cases);
+        if !features.is_empty() {
+            builder.with_conversion_of(&features);
+        }
+        let regexp = builder.build_regexp();
+        let compiled = Regex::new(&regexp.to_regex_string()).unwrap();
+
+        assert_eq!(regexp.capturing_group_count(), expected_count);
+        assert_eq!(compiled.captures_len() - 1, expected_count);
+    }
+
+    #[test]
+    fn succeeds_with_enclosing_chars() {
+        let regexp = RegExpBuilder::from(&["'abc'", "\"abd\""])
+            .with_enclosing_char_detection()
+            .with_flavor(RegExpFlavor::Pcre)
+            .with_conversion_of(&[Feature::CapturingGroup])
+            .build_regexp();
+
+        assert_eq!(regexp.to_regex_string(), "^([\"'])ab[cd]\\1$");
+        assert_eq!(regexp.capturing_group_count(), 1);
+    }
+
+    #[rstest(test_cases, expected_output, expected_count,
+        case(vec!["a(b)c", "x"], "^(\\Qa(b)c\\E|x)$", 1),
+        case(vec!["a[b]c(d"], "^\\Qa[b]c(d\\E$", 0),
+        case(vec!["a\\E(b", "x"], "^(\\Qa\\E\\\\E\\Q(b\\E|x)$", 1)
+    )]
+    fn succeeds_with_literal_quoting(
+        test_cases: Vec<&str>,
+        expected_output: &str,
+        expected_count: usize,
+    ) {
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_conversion_of(&[Feature::CapturingGroup])
+            .with_literal_quoting()
+            .with_flavor(RegExpFlavor::Pcre)
+            .build_regexp();
+
+        assert_eq!(regexp.to_regex_string(), expected_output);
+        assert_eq!(regexp.capturing_group_count(), expected_count);
+    }
+}
+
+mod group_policy {
+    use super::*;
+
+    #[rstest(test_cases, policy, expected_output, expected_count,
+        case(
+            vec!["aaa", "bcbc", "x"],
+            GroupPolicy::new().with_capturing_alternations().clone(),
+            "^(x|a{3}|(?:bc){2})$",
+            1
+        ),
+        case(
+            vec!["aaa", "bcbc", "x"],
+            GroupPolicy::new().with_capturing_repetitions().clone(),
+            "^(?:x|a{3}|(bc){2})$",
+            1
+        ),
+        case(
+            vec!["xab", "xcd", "xabab"],
+            GroupPolicy::new().with_capturing_concatenations().clone(),
+            "^x(ab|cd|(?:ab){2})$",
+            1
+        ),
+        case(
+            vec!["xab", "xcd", "xabab"],
+            GroupPolicy::new().with_capturing_concatenations().with_capturing_repetitions().clone(),
+            "^x(ab|cd|(ab){2})$",
+            2
+        ),
+        case(
+            vec!["aaa", "bcbc", "x"],
+            GroupPolicy::new(),
+            "^(?:x|a{3}|(?:bc){2})$",
+            0
+        )
+    )]
+    fn succeeds_with_mixed_policy(
+        test_cases: Vec<&str>,
+        policy: GroupPolicy,
+        expected_output: &str,
+        expected_count: usize,
+    ) {
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_conversion_of(&[Feature::Repetition, Feature::CapturingGroup])
+            .with_group_policy(&policy)
+            .build_regexp();
+
+        assert_eq!(regexp.to_regex_string(), expected_output);
+        assert_eq!(regexp.capturing_group_count(), expected_count);
+
+        let compiled = Regex::new(expected_output).unwrap();
+        for test_case in test_cases {
+            assert!(compiled.is_match(test_case));
+        }
+    }
+
+    #[test]
+    fn succeeds_without_capturing_group_feature() {
+        let regexp = RegExpBuilder::from(&["aaa", "bcbc", "x"])
+            .with_conversion_of(&[Feature::Repetition])
+            .with_group_policy(GroupPolicy::new().with_capturing_repetitions())
+            .build_regexp();
+
+        assert_eq!(regexp.to_regex_string(), "^(?:x|a{3}|(bc){2})$");
+    }
+}
+
+mod atomic_groups {
+    use super::*;
+    use grex::ast::{Expression, Quantifier};
+    use std::sync::Arc;
+
+    #[rstest(test_cases, flavor, expected_output,
+        case(vec!["aaa", "bcbc"], RegExpFlavor::Pcre, "^(?>a{3}|(?>bc){2})$"),
+        case(vec!["ab", "abc", "x"], RegExpFlavor::Pcre, "^(?>abc?|x)$"),
+        case(vec!["aaa", "bcbc"], RegExpFlavor::Standard, "^(?:a{3}|(?:bc){2})$"),
+        case(vec!["ab", "abc", "x"], RegExpFlavor::Standard, "^(?:abc?|x)$"),
+        case(vec!["aaa", "bcbc"], RegExpFlavor::RustBytes, "^(?:a{3}|(?:bc){2})$")
+    )]
+    fn succeeds(test_cases: Vec<&str>, flavor: RegExpFlavor, expected_output: &str) {
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_conversion_of(&[Feature::Repetition])
+            .with_atomic_groups()
+            .with_flavor(flavor)
+            .build();
+        assert_eq!(regexp, expected_output);
+    }
+
+    #[rstest(
+        flavor,
+        expected_output,
+        case(RegExpFlavor::Pcre, "(?>ab|cd)+"),
+        case(RegExpFlavor::Standard, "(?:ab|cd)+"),
+        case(RegExpFlavor::RustBytes, "(?:ab|cd)+")
+    )]
+    fn succeeds_with_repeated_alternation(flavor: RegExpFlavor, expected_output: &str) {
+        let config = Arc::new(
+            RegExpBuilder::from(&["a"])
+                .with_atomic_groups()
+                .with_flavor(flavor)
+                .config()
+                .clone(),
+        );
+        let expr = Expression::repetition(
+            Expression::alternation(
+                vec![
+                    Expression::literal("ab", &config),
+                    Expression::literal("cd", &config),
+                ],
+                &config,
+            ),
+            Quantifier::Range(1, u32::MAX),
+            &config,
+        );
+        assert_eq!(expr.to_string(), expected_output);
+    }
+
+    #[test]
+    fn succeeds_with_capturing_concatenations() {
+        let regexp = RegExpBuilder::from(&["xab", "xcd", "xabab"])
+            .with_conversion_of(&[Feature::Repetition])
+            .with_group_policy(GroupPolicy::new().with_capturing_concatenations())
+            .with_atomic_groups()
+            .with_flavor(RegExpFlavor::Pcre)
+            .try_build();
+        assert_eq!(regexp, Ok("^x(ab|cd|(?>ab){2})$".to_string()));
+    }
+
+    #[test]
+    fn fails_with_capturing_groups() {
+        let result = RegExpBuilder::from(&["aaa", "bcbc"])
+            .with_conversion_of(&[Feature::Repetition, Feature::CapturingGroup])
+            .with_atomic_groups()
+            .with_flavor(RegExpFlavor::Pcre)
+            .try_build();
+        assert_eq!(result, Err(RegExpError::AtomicCapturingGroup));
+    }
+
+    #[test]
+    fn fails_with_capturing_repetitions() {
+        let mut config = RegExpBuilder::from(&["a"])
+            .with_group_policy(GroupPolicy::new().with_capturing_repetitions())
+            .with_atomic_groups()
+            .config()
+            .clone();
+        assert_eq!(
+            grex::try_generate(&["aaa", "bcbc"], &config),
+            Err(RegExpError::AtomicCapturingGroup)
+        );
+        config = RegExpBuilder::from(&["a"])
+            .with_atomic_groups()
+            .config()
+            .clone();
+        assert!(grex::try_generate(&["aaa", "bcbc"], &config).is_ok());
+    }
+
+    #[test]
+    #[should_panic(expected = "Atomic groups cannot be combined with capturing groups")]
+    fn panics_with_capturing_groups() {
+        RegExpBuilder::from(&["aaa", "bcbc"])
+            .with_conversion_of(&[Feature::CapturingGroup])
+            .with_atomic_groups()
+            .build();
+    }
+}
+
+mod syntax_tree {
+    use super::*;
+    use grex::ast::{Expression, Quantifier};
+    use std::ops::RangeInclusive;
+    use std::sync::Arc;
+
+    #[test]
+    fn succeeds_with_modified_tree() {
+        let regexp = RegExpBuilder::from(&["abc", "abd"]).build_regexp();
+        assert_eq!(regexp.ast().to_string(), "ab[cd]");
+
+        let config = Arc::new(RegExpConfig::new());
+        let expr = match regexp.ast().clone() {
+            Expression::Concatenation(first, second, _) => Expression::concatenation(
+                *first,
+                Expression::repetition(*second, Quantifier::KleeneStar, &config),
+                &config,
+            ),
+            _ => unreachable!(),
+        };
+        assert_eq!(expr.to_string(), "ab[cd]*");
+    }
+
+    #[test]
+    fn succeeds_with_escaped_literal() {
+        let config = Arc::new(RegExpConfig::new());
+        let expr = Expression::alternation(
+            vec![
+                Expression::literal("a.b", &config),
+                Expression::character_class(vec!['-', 'a'], &config),
+            ],
+            &config,
         );
+        assert_eq!(expr.to_string(), "a\\.b|[-a]");
+    }
+
+    #[test]
+    #[should_panic(expected = "No options have been provided for the alternation")]
+    fn fails_with_empty_alternation() {
+        Expression::alternation(vec![], &Arc::new(RegExpConfig::new()));
+    }
+
+    #[rstest(
+        range,
+        expected_output,
+        case('a'..='f', "[a-f]"),
+        case('a'..='b', "[ab]"),
+        case('x'..='x', "x"),
+        case('.'..='.', "\\."),
+        case('\u{4e00}'..='\u{9fff}', "[\u{4e00}-\u{9fff}]"),
+        case('\u{d7fe}'..='\u{e001}', "[\u{d7fe}-\u{e001}]"),
+        case('\u{100}'..='\u{10ffff}', "[\u{100}-\u{10ffff}]")
+    )]
+    fn succeeds_with_character_class_from_range(
+        range: RangeInclusive<char>,
+        expected_output: &str,
+    ) {
+        let config = Arc::new(RegExpConfig::new());
+        let expr = Expression::character_class_from_range(range, &config);
+        assert_eq!(expr.to_string(), expected_output);
+    }
+
+    #[test]
+    fn succeeds_with_character_class_from_range_in_concatenation() {
+        let config = Arc::new(RegExpConfig::new());
+        let expr = Expression::concatenation(
+            Expression::literal("id", &config),
+            Expression::repetition(
+                Expression::character_class_from_range('0'..='9', &config),
+                Quantifier::Range(1, 3),
+                &config,
+            ),
+            &config,
+        );
+        assert_eq!(expr.to_string(), "id[0-9]{1,3}");
+    }
+
+    #[test]
+    #[should_panic(expected = "No characters have been provided for the character class")]
+    fn fails_with_empty_character_class() {
+        Expression::character_class(vec![], &Arc::new(RegExpConfig::new()));
+    }
+
+    #[test]
+    #[should_panic(expected = "No characters have been provided for the character class")]
+    fn fails_with_empty_character_class_range() {
+        Expression::character_class_from_range('b'..='a', &Arc::new(RegExpConfig::new()));
+    }
+}
+
+#[cfg(feature = "serde")]
+mod json_input {
+    use super::*;
+    use grex::RegExp;
+
+    #[test]
+    fn succeeds_with_array_of_strings() {
+        let config = RegExpConfig::new();
+        let regexp = RegExp::from_json(r#"["abc","abd"]"#, &config).unwrap();
+        assert_eq!(regexp.to_string(), generate(&["abc", "abd"], &config));
+        assert_eq!(regexp.to_string(), "^ab[cd]$");
+    }
+
+    #[rstest(
+        json,
+        expected_error,
+        case(
+            r#"["abc",1]"#,
+            "invalid type: integer `1`, expected a string at line 1 column 8"
+        ),
+        case(
+            r#"{"abc":"abd"}"#,
+            "invalid type: map, expected a sequence at line 1 column 0"
+        ),
+        case(
+            "[]",
+            "No test cases have been provided for regular expression generation"
+        )
+    )]
+    fn fails_with_invalid_json(json: &str, expected_error: &str) {
+        let error = RegExp::from_json(json, &RegExpConfig::new()).err().unwrap();
+        assert_eq!(error.to_string(), expected_error);
+    }
+}
+
+mod file_input {
+    use super::*;
+    use grex::RegExp;
+    use std::io::ErrorKind;
+
+    #[test]
+    #[allow(unused_must_use)]
+    fn succeeds_with_mixed_line_endings() {
+        let mut file = NamedTempFile::new().unwrap();
+        write!(file, "a\r\nb\nc\r\nxyz\n");
+
+        let expected_output = "^(?:xyz|[a-c])$";
+        let test_cases = vec!["a", "b", "c", "xyz"];
+
+        let regexp = RegExp::from_file(file.path(), &RegExpConfig::new()).unwrap();
+        test_if_regexp_is_correct(regexp.to_string(), expected_output, &test_cases);
+        test_if_regexp_matches_test_cases(expected_output, test_cases);
+    }
+
+    #[test]
+    #[allow(unused_must_use)]
+    fn succeeds_with_skipping_of_blank_lines() {
+        let mut file = NamedTempFile::new().unwrap();
+        writeln!(file, "a\n\n  \r\nb");
+
+        let regexp = RegExp::from_file(file.path(), &RegExpConfig::new()).unwrap();
+        assert_eq!(regexp.to_string(), "^(?:  |[ab])?$");
+
+        let config = RegExpBuilder::from(&["_"])
+            .with_skipping_of_blank_lines()
+            .config()
+            .clone();
+        let regexp = RegExp::from_file(file.path(), &config).unwrap();
+        assert_eq!(regexp.to_string(), "^[ab]$");
+    }
+
+    #[test]
+    #[allow(unused_must_use)]
+    fn fails_without_test_cases() {
+        let mut file = NamedTempFile::new().unwrap();
+        writeln!(file, "\n ");
+
+        let config = RegExpBuilder::from(&["_"])
+            .with_skipping_of_blank_lines()
+            .config()
+            .clone();
+        let error = RegExp::from_file(file.path(), &config).err().unwrap();
+        assert_eq!(error.kind(), ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn fails_with_non_existing_file() {
+        let result = RegExp::from_file("/path/to/non-existing/file", &RegExpConfig::new());
+        assert_eq!(result.err().unwrap().kind(), ErrorKind::NotFound);
+    }
+}
+
+mod alternation_to_class {
+    use super::*;
+
+    #[rstest(test_cases, expected_output,
+        case(vec!["a", "é", "ü"], "^[a\\u{e9}\\u{fc}]$"),
+        case(vec!["x♥y", "x💩y"], "^x[\\u{2665}\\u{1f4a9}]y$")
+    )]
+    fn succeeds_with_escape_option(test_cases: Vec<&str>, expected_output: &str) {
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_escaping_of_non_ascii_chars(false)
+            .build();
+        test_if_regexp_is_correct(regexp, expected_output, &test_cases);
+        test_if_regexp_matches_test_cases(expected_output, test_cases);
+    }
+
+    #[rstest(test_cases, expected_output,
+        case(vec!["a", "é", "ü"], "^(?:a|\\u{e9}|\\u{fc})$")
+    )]
+    fn succeeds_with_disabled_conversion(test_cases: Vec<&str>, expected_output: &str) {
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_escaping_of_non_ascii_chars(false)
+            .with_alternation_to_class_disabled()
+            .build();
+        test_if_regexp_is_correct(regexp, expected_output, &test_cases);
+        test_if_regexp_matches_test_cases(expected_output, test_cases);
+    }
+
+    #[rstest(test_cases, expected_output,
+        case(vec!["a", "é"], "^(?:a|\\u{e9})$"),
+        case(vec!["a", "é", "ü"], "^[a\\u{e9}\\u{fc}]$")
+    )]
+    fn succeeds_with_threshold(test_cases: Vec<&str>, expected_output: &str) {
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_escaping_of_non_ascii_chars(false)
+            .with_alternation_to_class_threshold(3)
+            .build();
+        test_if_regexp_is_correct(regexp, expected_output, &test_cases);
+        test_if_regexp_matches_test_cases(expected_output, test_cases);
+    }
+}
+
+mod length_quantification {
+    use super::*;
+
+    #[rstest(test_cases, expected_output,
+        case(vec!["ab", "abc", "abcd"], "^[a-d]{2,4}$"),
+        case(vec!["aa", "aaa"], "^a{2,3}$"),
+        case(vec!["1", "22", "333"], "^[1-3]{1,3}$"),
+        case(vec!["-.", "--."], "^[-.]{2,3}$"),
+        case(vec!["ab", "cd"], "^(?:ab|cd)$"),
+        case(vec!["cat", "horse"], "^(?:horse|cat)$")
+    )]
+    fn succeeds(test_cases: Vec<&str>, expected_output: &str) {
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_length_quantification()
+            .build();
+        test_if_regexp_is_correct(regexp, expected_output, &test_cases);
+        test_if_regexp_matches_test_cases(expected_output, test_cases);
+    }
+
+    #[test]
+    fn succeeds_with_match_count() {
+        let regexp = RegExpBuilder::from(&["ab", "abc"])
+            .with_length_quantification()
+            .build_regexp();
+        assert_eq!(regexp.match_count(), Some(9 + 27));
+    }
+}
+
+mod hex_generalization {
+    use super::*;
+
+    #[rstest(test_cases, expected_output,
+        case(vec!["cafe", "deadbeef"], "^[0-9a-f]{4,8}$"),
+        case(vec!["c0ffee", "facade"], "^[0-9a-f]{6}$"),
+        case(vec!["Cafe", "BEEF"], "^[0-9A-Fa-f]{4}$"),
+        case(vec!["DEADBEEF", "cafe01"], "^[0-9A-Fa-f]{6,8}$"),
+        case(vec!["ABC1"], "^[0-9A-F]{4}$"),
+        case(vec!["a"], "^[0-9a-f]$"),
+        case(vec!["cafe", "coffee"], "^c(?:offe|af)e$"),
+        case(vec!["123", "456"], "^(?:123|456)$")
+    )]
+    fn succeeds(test_cases: Vec<&str>, expected_output: &str) {
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_hex_generalization()
+            .build();
+        test_if_regexp_is_correct(regexp, expected_output, &test_cases);
+        test_if_regexp_matches_test_cases(expected_output, test_cases);
+    }
+
+    #[test]
+    fn succeeds_with_length_quantification() {
+        let test_cases = vec!["ab", "abc"];
+        let expected_output = "^[0-9a-f]{2,3}$";
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_hex_generalization()
+            .with_length_quantification()
+            .build();
+        test_if_regexp_is_correct(regexp, expected_output, &test_cases);
+        test_if_regexp_matches_test_cases(expected_output, test_cases);
+    }
+}
+
+mod decimal_number_detection {
+    use super::*;
+
+    #[rstest(test_cases, expected_output,
+        case(vec!["1.5", "23.75"], "^\\d{1,2}\\.\\d{1,2}$"),
+        case(vec!["1.5"], "^\\d\\.\\d$"),
+        case(vec!["10.25", "3.14159"], "^\\d{1,2}\\.\\d{2,5}$"),
+        case(vec!["1.5", "a"], "^(?:1\\.5|a)$"),
+        case(vec!["1.5.3"], "^1\\.5\\.3$"),
+        case(vec!["15"], "^15$")
+    )]
+    fn succeeds(test_cases: Vec<&str>, expected_output: &str) {
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_decimal_number_detection()
+            .build();
+        test_if_regexp_is_correct(regexp, expected_output, &test_cases);
+        test_if_regexp_matches_test_cases(expected_output, test_cases);
+    }
+}
+
+mod numeric_detection {
+    use super::*;
+
+    #[rstest(test_cases, expected_output,
+        case(vec!["123", "456"], "^\\d{3}$"),
+        case(vec!["007", "123"], "^\\d{3}$"),
+        case(vec!["5"], "^\\d$"),
+        case(vec!["12", "3456", "789"], "^\\d{2,4}$"),
+        case(vec!["0", "00", "000"], "^\\d{1,3}$"),
+        case(vec!["-12", "34"], "^(?:\\-12|34)$"),
+        case(vec!["12", "ab"], "^(?:12|ab)$"),
+        case(vec!["1.5"], "^1\\.5$")
+    )]
+    fn succeeds(test_cases: Vec<&str>, expected_output: &str) {
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_numeric_detection()
+            .build();
+        test_if_regexp_is_correct(regexp, expected_output, &test_cases);
+        test_if_regexp_matches_test_cases(expected_output, test_cases);
+    }
+
+    #[rstest(test_cases, expected_output,
+        case(vec!["1", "22", "333"], "^\\d+$"),
+        case(vec!["12", "3456"], "^\\d{2,}$")
+    )]
+    fn succeeds_with_unbounded_repetitions(test_cases: Vec<&str>, expected_output: &str) {
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_numeric_detection()
+            .with_unbounded_repetitions()
+            .build();
+        test_if_regexp_is_correct(regexp, expected_output, &test_cases);
+        test_if_regexp_matches_test_cases(expected_output, test_cases);
+    }
+
+    #[test]
+    fn succeeds_with_optional_sign_detection() {
+        let test_cases = vec!["-12", "34", "+567"];
+        let expected_output = "^[+-]?\\d{2,3}$";
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_numeric_detection()
+            .with_optional_sign_detection()
+            .build();
+        test_if_regexp_is_correct(regexp, expected_output, &test_cases);
+        test_if_regexp_matches_test_cases(expected_output, test_cases);
+    }
+}
+
+mod alternation_order {
+    use super::*;
+
+    #[rstest(test_cases, order, expected_output,
+        case(vec!["b", "xyz", "ab"], AlternationOrder::ByLength, "^(?:xyz|a?b)$"),
+        case(vec!["b", "xyz", "ab"], AlternationOrder::Input, "^(?:a?b|xyz)$"),
+        case(vec!["b", "xyz", "ab"], AlternationOrder::Lexicographic, "^(?:a?b|xyz)$"),
+        case(vec!["xy", "abc", "q"], AlternationOrder::ByLength, "^(?:abc|xy|q)$"),
+        case(vec!["xy", "abc", "q"], AlternationOrder::Input, "^(?:xy|abc|q)$"),
+        case(vec!["xy", "abc", "q"], AlternationOrder::Lexicographic, "^(?:abc|q|xy)$"),
+        case(vec!["b1", "abcd", "ab"], AlternationOrder::ByLength, "^(?:ab(?:cd)?|b1)$"),
+        case(vec!["b1", "abcd", "ab"], AlternationOrder::Input, "^(?:b1|ab(?:cd)?)$"),
+        case(vec!["x", "abc", "ab"], AlternationOrder::Input, "^(?:x|abc?)$"),
+        case(vec!["a", "bc", "ac", "x"], AlternationOrder::Input, "^(?:a|bc|ac|x)$")
+    )]
+    fn succeeds(test_cases: Vec<&str>, order: AlternationOrder, expected_output: &str) {
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_alternation_order(order)
+            .build();
+        test_if_regexp_is_correct(regexp, expected_output, &test_cases);
+        test_if_regexp_matches_test_cases(expected_output, test_cases);
+    }
+}
+
+mod frequency_ordering {
+    use super::*;
+
+    #[rstest(test_cases, expected_output,
+        case(vec!["a", "a", "bc"], "^(?:a|bc)$"),
+        case(vec!["bc", "a", "a"], "^(?:a|bc)$"),
+        case(vec!["b", "b", "aa"], "^(?:b|aa)$"),
+        case(vec!["x1", "y2", "y2", "y3", "z", "z", "z", "z"], "^(?:z|y[23]|x1)$"),
+        case(vec!["xyz", "ab", "ab", "ab", "b"], "^(?:a?b|xyz)$"),
+        case(vec!["abc", "abc", "abc", "b", "b"], "^(?:abc|b)$"),
+        case(vec!["abc", "xyz"], "^(?:abc|xyz)$")
+    )]
+    fn succeeds(test_cases: Vec<&str>, expected_output: &str) {
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_frequency_ordering()
+            .build();
+        test_if_regexp_is_correct(regexp, expected_output, &test_cases);
+        test_if_regexp_matches_test_cases(expected_output, test_cases);
+    }
+
+    #[test]
+    fn succeeds_with_lexicographic_order_of_equal_frequencies() {
+        let test_cases = vec!["zz", "zz", "ab", "cd"];
+        let expected_output = "^(?:zz|ab|cd)$";
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_frequency_ordering()
+            .with_alternation_order(AlternationOrder::Lexicographic)
+            .build();
+        test_if_regexp_is_correct(regexp, expected_output, &test_cases);
+        test_if_regexp_matches_test_cases(expected_output, test_cases);
+    }
+
+    #[test]
+    fn succeeds_with_dedup_report() {
+        let regexp = RegExpBuilder::from(&["a", "a", "bc"])
+            .with_frequency_ordering()
+            .build_regexp();
+        assert_eq!(regexp.dedup_report().removed(), &["a".to_string()]);
+    }
+}
+
+mod preserved_input_order {
+    use super::*;
+
+    #[rstest(test_cases, expected_output,
+        case(vec!["a", "bc"], "^(?:a|bc)$"),
+        case(vec!["x1", "z", "y2"], "^(?:x1|z|y2)$"),
+        case(vec!["cd", "ab", "ef", "ab"], "^(?:cd|ab|ef)$"),
+        case(vec!["b", "a", "xyz"], "^(?:[ab]|xyz)$"),
+        case(vec!["xyz", "abc", "ab"], "^(?:xyz|abc?)$"),
+        case(vec!["xyz", "y", "x"], "^(?:xyz|y|x)$")
+    )]
+    fn succeeds(test_cases: Vec<&str>, expected_output: &str) {
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_preserved_input_order()
+            .build();
+        test_if_regexp_is_correct(regexp, expected_output, &test_cases);
+        test_if_regexp_matches_test_cases(expected_output, test_cases);
+    }
+
+    #[test]
+    fn succeeds_with_permutations() {
+        let first_permutation = vec!["x1", "y2", "z"];
+        let second_permutation = vec!["z", "y2", "x1"];
+        let third_permutation = vec!["xyz", "y", "x"];
+
+        let build = |test_cases: &[&str], is_input_order_preserved: bool| {
+            let mut builder = RegExpBuilder::from(test_cases);
+            if is_input_order_preserved {
+                builder.with_preserved_input_order();
+            }
+            builder.build()
+        };
+
+        assert_eq!(build(&first_permutation, true), "^(?:x1|y2|z)$");
+        assert_eq!(build(&second_permutation, true), "^(?:z|y2|x1)$");
+        assert_eq!(build(&first_permutation, false), "^(?:x1|y2|z)$");
+        assert_eq!(build(&second_permutation, false), "^(?:x1|y2|z)$");
+        assert_eq!(build(&third_permutation, true), "^(?:xyz|y|x)$");
+    }
+
+    #[test]
+    fn succeeds_with_frequency_ordering() {
+        let test_cases = vec!["cd", "ab", "ef", "ef"];
+        let expected_output = "^(?:ef|cd|ab)$";
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_preserved_input_order()
+            .with_frequency_ordering()
+            .build();
+        test_if_regexp_is_correct(regexp, expected_output, &test_cases);
+        test_if_regexp_matches_test_cases(expected_output, test_cases);
+    }
+}
+
+mod longest_branches_first {
+    use super::*;
+    use grex::ast::Expression;
+    use std::sync::Arc;
+
+    #[rstest(test_cases, expected_output,
+        case(vec!["a", "ab", "1"], "^(?:ab|a|1)$"),
+        case(vec!["a", "ab", "b"], "^(?:a?b|a)$"),
+        case(vec!["x1", "y2", "z"], "^(?:x1|y2|z)$")
+    )]
+    fn succeeds(test_cases: Vec<&str>, expected_output: &str) {
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_preserved_input_order()
+            .with_longest_branches_first()
+            .build();
+        test_if_regexp_is_correct(regexp, expected_output, &test_cases);
+        test_if_regexp_matches_test_cases(expected_output, test_cases);
+    }
+
+    #[rstest(branches, expected_output,
+        case(vec!["ab", "abc", "x"], "abc|ab|x"),
+        case(vec!["a", "ab", "abc"], "abc|ab|a"),
+        case(vec!["x", "ab", "y", "abc"], "x|y|abc|ab"),
+        case(vec!["abc", "ab", "x"], "abc|ab|x"),
+        case(vec!["ab", "cd", "ef"], "ab|cd|ef")
+    )]
+    fn succeeds_with_prefix_related_branches(branches: Vec<&str>, expected_output: &str) {
+        let config = Arc::new(
+            RegExpBuilder::from(&["a"])
+                .with_longest_branches_first()
+                .config()
+                .clone(),
+        );
+        let alternation = Expression::alternation(
+            branches
+                .iter()
+                .map(|it| Expression::literal(it, &config))
+                .collect(),
+            &config,
+        );
+        assert_eq!(alternation.to_string(), expected_output);
+    }
+
+    #[test]
+    fn succeeds_with_prefix_of_character_class() {
+        let config = Arc::new(
+            RegExpBuilder::from(&["a"])
+                .with_longest_branches_first()
+                .config()
+                .clone(),
+        );
+        let alternation = Expression::alternation(
+            vec![
+                Expression::literal("a", &config),
+                Expression::concatenation(
+                    Expression::character_class(vec!['a', 'b'], &config),
+                    Expression::literal("c", &config),
+                    &config,
+                ),
+                Expression::literal("bd", &config),
+            ],
+            &config,
+        );
+        assert_eq!(alternation.to_string(), "[ab]c|a|bd");
+    }
+
+    #[test]
+    fn succeeds_without_reordering_by_default() {
+        let config = Arc::new(RegExpConfig::new());
+        let alternation = Expression::alternation(
+            vec![
+                Expression::literal("ab", &config),
+                Expression::literal("abc", &config),
+            ],
+            &config,
+        );
+        assert_eq!(alternation.to_string(), "ab|abc");
+    }
+}
+
+mod suffix_sharing {
+    use super::*;
+
+    #[rstest(test_cases, expected_output,
+        case(vec!["action", "auction"], "^au?ction$"),
+        case(vec!["tap", "taps", "top", "tops"], "^t[ao]ps?$"),
+        case(vec!["action", "auction", "caution", "fraction"], "^(?:frac|cau|ac|auc)tion$")
+    )]
+    fn succeeds_with_smaller_regexp_than_flat_alternation(
+        test_cases: Vec<&str>,
+        expected_output: &str,
+    ) {
+        let regexp = RegExpBuilder::from(&test_cases).build();
+        let flat_alternation = format!("^(?:{})$", test_cases.join("|"));
+        assert!(regexp.len() < flat_alternation.len());
+        test_if_regexp_is_correct(regexp, expected_output, &test_cases);
+        test_if_regexp_matches_test_cases(expected_output, test_cases);
+    }
+}
+
+mod prefix_factoring {
+    use super::*;
+
+    #[rstest(test_cases, expected_output,
+        case(vec!["foobar", "foobaz", "fooqux"], "^foo(?:ba[rz]|qux)$"),
+        case(vec!["abc", "def"], "^(?:abc|def)$"),
+        case(vec!["💩a", "💩b"], "^💩[ab]$")
+    )]
+    fn succeeds(test_cases: Vec<&str>, expected_output: &str) {
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_prefix_factoring()
+            .build();
+        test_if_regexp_is_correct(regexp, expected_output, &test_cases);
+        test_if_regexp_matches_test_cases(expected_output, test_cases);
+    }
+
+    #[rstest(test_cases, expected_output,
+        case(
+            vec!["ab", "abaaab", "baab", "aabaabab"],
+            "^(?:a(?:ba{3}|(?:aba){2})?|ba{2})b$"
+        )
+    )]
+    fn succeeds_with_repetition_feature(test_cases: Vec<&str>, expected_output: &str) {
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_conversion_of(&[Feature::Repetition])
+            .with_prefix_factoring()
+            .build();
+        test_if_regexp_is_correct(regexp, expected_output, &test_cases);
+        test_if_regexp_matches_test_cases(expected_output, test_cases);
+    }
+}
+
+mod optional_char_detection {
+    use super::*;
+
+    #[rstest(test_cases, expected_output,
+        case(vec!["color", "colour"], "^colou?r$"),
+        case(vec!["ab", "xab", "cd"], "^(?:x?ab|cd)$"),
+        case(vec!["abc", "abxc", "ayc"], "^a(?:bx?|y)c$"),
+        case(vec!["ab", "axb", "ayb"], "^a[xy]?b$"),
+        case(vec!["ab", "cd"], "^(?:ab|cd)$")
+    )]
+    fn succeeds(test_cases: Vec<&str>, expected_output: &str) {
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_optional_char_detection()
+            .build();
+        test_if_regexp_is_correct(regexp, expected_output, &test_cases);
+        test_if_regexp_matches_test_cases(expected_output, test_cases);
+    }
+
+    #[test]
+    fn succeeds_with_match_count() {
+        let regexp = RegExpBuilder::from(&["ab", "xab", "cd"])
+            .with_optional_char_detection()
+            .build_regexp();
+        assert_eq!(regexp.match_count(), Some(3));
+    }
+}
+
+mod suffix_factoring {
+    use super::*;
+
+    #[rstest(test_cases, expected_output,
+        case(vec!["running", "jumping"], "^(?:jump|runn)ing$"),
+        case(vec!["abc", "def"], "^(?:abc|def)$")
+    )]
+    fn succeeds(test_cases: Vec<&str>, expected_output: &str) {
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_suffix_factoring()
+            .build();
+        test_if_regexp_is_correct(regexp, expected_output, &test_cases);
+        test_if_regexp_matches_test_cases(expected_output, test_cases);
+    }
+
+    #[test]
+    fn succeeds_with_prefix_factoring() {
+        let test_cases = vec!["abxyz", "abz", "abxxz"];
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_prefix_factoring()
+            .with_suffix_factoring()
+            .build();
+        assert_eq!(regexp, "^ab(?:x[xy])?z$");
+        test_if_regexp_matches_test_cases(&regexp, test_cases);
+    }
+
+    #[rstest(test_cases, expected_output,
+        case(vec!["ab", "bab", "aab", "baaaab"], "^(?:b(?:a|a{4})|a{1,2})b$"),
+        case(vec!["bbaaab", "baaaab", "bab", "ab"], "^(?:b{1,2}(?:a|a{4}|a{3})|a)b$")
+    )]
+    fn succeeds_with_prefix_factoring_and_repetition_feature(
+        test_cases: Vec<&str>,
+        expected_output: &str,
+    ) {
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_conversion_of(&[Feature::Repetition])
+            .with_prefix_factoring()
+            .with_suffix_factoring()
+            .build();
+        test_if_regexp_is_correct(regexp, expected_output, &test_cases);
+        test_if_regexp_matches_test_cases(expected_output, test_cases);
+    }
+}
+
+mod optional_sign_detection {
+    use super::*;
+
+    #[rstest(test_cases, expected_output,
+        case(vec!["5", "-5", "+5"], "^[+-]?5$"),
+        case(vec!["-5"], "^[+-]?5$"),
+        case(vec!["-1", "22"], "^[+-]?(?:22|1)$"),
+        case(vec!["-1.5", "2.5"], "^[+-]?[12]\\.5$"),
+        case(vec!["1", "22"], "^(?:22|1)$"),
+        case(vec!["-a", "5"], "^(?:\\-a|5)$"),
+        case(vec!["-", "5"], "^[-5]$"),
+        case(vec!["-.5"], "^\\-\\.5$")
+    )]
+    fn succeeds(test_cases: Vec<&str>, expected_output: &str) {
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_optional_sign_detection()
+            .build();
+        test_if_regexp_is_correct(regexp, expected_output, &test_cases);
+        test_if_regexp_matches_test_cases(expected_output, test_cases);
+    }
+
+    #[test]
+    fn succeeds_with_decimal_number_detection() {
+        let test_cases = vec!["-1.5", "23.75"];
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_optional_sign_detection()
+            .with_decimal_number_detection()
+            .build();
+        assert_eq!(regexp, "^[+-]?\\d{1,2}\\.\\d{1,2}$");
+        test_if_regexp_matches_test_cases(&regexp, test_cases);
+    }
+}
+
+mod wildcard_threshold {
+    use super::*;
+
+    fn distinct_chars(count: u32) -> Vec<String> {
+        (0x100..0x100 + count)
+            .map(|it| std::char::from_u32(it).unwrap().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn succeeds_with_large_char_class() {
+        let test_cases = distinct_chars(200);
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_wildcard_threshold(100)
+            .build();
+        assert_eq!(regexp, "^.$");
+    }
+
+    #[test]
+    fn succeeds_with_large_char_class_containing_newline() {
+        let mut test_cases = distinct_chars(200);
+        test_cases.push("\n".to_string());
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_wildcard_threshold(100)
+            .build();
+        assert_eq!(regexp, "^[\\s\\S]$");
+        test_if_regexp_matches_test_cases(
+            &regexp,
+            test_cases.iter().map(|it| it.as_str()).collect(),
+        );
+    }
+
+    #[test]
+    fn succeeds_with_large_char_class_containing_newline_in_dotall_mode() {
+        let mut test_cases = distinct_chars(200);
+        test_cases.push("\n".to_string());
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_wildcard_threshold(100)
+            .with_dotall_mode()
+            .build_regexp();
+        assert_eq!(regexp.to_string(), "(?s)^.$");
+        assert_eq!(
+            regexp.to_pattern_and_flags(),
+            ("^.$".to_string(), RegexFlags::DOT_MATCHES_NEW_LINE)
+        );
+        test_if_regexp_matches_test_cases(
+            &regexp.to_string(),
+            test_cases.iter().map(|it| it.as_str()).collect(),
+        );
+    }
+
+    #[rstest(test_cases, expected_output,
+        case(vec!["a", "b", "c"], "(?s)^.$"),
+        case(vec!["a", "b"], "^[ab]$")
+    )]
+    fn succeeds_with_dotall_flag_only_for_wildcards(test_cases: Vec<&str>, expected_output: &str) {
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_wildcard_threshold(2)
+            .with_dotall_mode()
+            .build();
+        test_if_regexp_is_correct(regexp, expected_output, &test_cases);
+        test_if_regexp_matches_test_cases(expected_output, test_cases);
+    }
+
+    #[rstest(test_cases, expected_output,
+        case(vec!["\n", "a"], "^[\\na]$"),
+        case(vec!["\r", "\t", "\n"], "^[\\t\\n\\r]$")
+    )]
+    fn succeeds_with_escaped_newline_in_char_class(test_cases: Vec<&str>, expected_output: &str) {
+        let regexp = RegExpBuilder::from(&test_cases).build();
+        test_if_regexp_is_correct(regexp, expected_output, &test_cases);
+        test_if_regexp_matches_test_cases(expected_output, test_cases);
+    }
+
+    #[test]
+    fn succeeds_with_small_char_class() {
+        let test_cases = distinct_chars(5);
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_wildcard_threshold(100)
+            .build();
+        assert_eq!(regexp, "^[Ā-Ą]$");
+    }
+
+    #[rstest(test_cases, expected_output,
+        case(vec!["a", "b", "c"], "^.$"),
+        case(vec!["xa", "xb", "xc", "y"], "^(?:x.|y)$"),
+        case(vec!["a", "b", "c", "ab"], "^(?:ab|a|[bc])$"),
+        case(vec!["a", "b"], "^[ab]$")
+    )]
+    fn succeeds_with_low_threshold(test_cases: Vec<&str>, expected_output: &str) {
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_wildcard_threshold(2)
+            .build();
+        test_if_regexp_is_correct(regexp, expected_output, &test_cases);
+        test_if_regexp_matches_test_cases(expected_output, test_cases);
+    }
+
+    #[test]
+    #[cfg(feature = "coloring")]
+    fn succeeds_with_syntax_highlighting() {
+        colored::control::set_override(true);
+        let regexp = RegExpBuilder::from(&["a", "b", "c"])
+            .with_wildcard_threshold(2)
+            .with_syntax_highlighting()
+            .build();
+        assert_eq!(
+            regexp,
+            "\u{1b}[1;33m^\u{1b}[0m\u{1b}[103;30m.\u{1b}[0m\u{1b}[1;33m$\u{1b}[0m"
+        );
+    }
+}
+
+mod flags_string {
+    use super::*;
+
+    #[rstest(flags, test_cases, expected_output,
+        case("", vec!["a", "1"], "^[1a]$"),
+        case("dg", vec!["a", "1"], "^(\\d|a)$"),
+        case("ddDD", vec!["a", "1"], "^(?:\\d|\\D)$"),
+        case("ri", vec!["AAA", "aa"], "(?i)^a{2,3}$"),
+        case("e", vec!["💩"], "^\\u{1f4a9}$"),
+        case("u", vec!["💩"], "^\\u{d83d}\\u{dca9}$"),
+        case("m", vec!["abc"], "(?m)^abc$")
+    )]
+    fn succeeds_with_valid_flags(flags: &str, test_cases: Vec<&str>, expected_output: &str) {
+        let config = flags.parse::<RegExpConfig>().unwrap();
+        let regexp = generate(&test_cases, &config);
+        test_if_regexp_is_correct(regexp, expected_output, &test_cases);
+    }
+
+    #[test]
+    fn succeeds_with_same_config_as_builder() {
+        let config = "wSr".parse::<RegExpConfig>().unwrap();
+        let builder_config = RegExpBuilder::from(&["a"])
+            .with_conversion_of(&[Feature::Word, Feature::NonSpace, Feature::Repetition])
+            .config()
+            .clone();
+        assert_eq!(config, builder_config);
+    }
+
+    #[rstest(
+        flags,
+        expected_error,
+        case("x", RegExpError::UnknownFlag('x')),
+        case("dgX", RegExpError::UnknownFlag('X')),
+        case("d g", RegExpError::UnknownFlag(' '))
+    )]
+    fn fails_with_unknown_flag(flags: &str, expected_error: RegExpError) {
+        assert_eq!(flags.parse::<RegExpConfig>(), Err(expected_error));
+    }
+}
+
+mod multiline {
+    use super::*;
+    use regex::Regex;
+
+    #[rstest(test_cases, expected_output,
+        case(vec!["abc", "abd"], "(?m)^ab[cd]$"),
+        case(vec!["a", "bc"], "(?m)^(?:bc|a)$")
+    )]
+    fn succeeds_with_multiline_option(test_cases: Vec<&str>, expected_output: &str) {
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_multiline_mode()
+            .build();
+        test_if_regexp_is_correct(regexp, expected_output, &test_cases);
+        test_if_regexp_matches_test_cases(expected_output, test_cases);
+    }
+
+    #[test]
+    fn succeeds_with_matching_of_single_lines() {
+        let regexp = RegExpBuilder::from(&["abc", "abd"])
+            .with_multiline_mode()
+            .build();
+        let matches = Regex::new(&regexp)
+            .unwrap()
+            .find_iter("abc\nxyz\nabd")
+            .map(|it| it.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(matches, vec!["abc", "abd"]);
+    }
+
+    #[test]
+    fn succeeds_with_separate_flags() {
+        let regexp = RegExpBuilder::from(&["Ab"])
+            .with_conversion_of(&[Feature::CaseInsensitivity])
+            .with_multiline_mode()
+            .build_regexp();
+        assert_eq!(
+            regexp.to_pattern_and_flags(),
+            (
+                "^ab$".to_string(),
+                RegexFlags::CASE_INSENSITIVE | RegexFlags::MULTI_LINE
+            )
+        );
+    }
+}
+
+mod repetition_merge_gap_tolerance {
+    use super::*;
+
+    #[rstest(test_cases, tolerance, expected_output,
+        case(vec!["aa", "aaaaa"], 0, "^(?:a{2}|a{5})$"),
+        case(vec!["aa", "aaaaa"], 1, "^(?:a{2}|a{5})$"),
+        case(vec!["aa", "aaaaa"], 2, "^a{2,5}$"),
+        case(vec!["aa", "aaaaa"], 3, "^a{2,5}$"),
+        case(vec!["xaa", "xaaaaa"], 3, "^xa{2,5}$"),
+        case(vec!["aa", "aaaaa", "aaaaaaaaaa"], 3, "^(?:a{2,5}|a{10})$")
+    )]
+    fn succeeds(test_cases: Vec<&str>, tolerance: u32, expected_output: &str) {
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_conversion_of(&[Feature::Repetition])
+            .with_repetition_merge_gap_tolerance(tolerance)
+            .build();
+        test_if_regexp_is_correct(regexp, expected_output, &test_cases);
+        test_if_regexp_matches_test_cases(expected_output, test_cases);
+    }
+}
+
+mod unbounded_repetitions {
+    use super::*;
+
+    #[rstest(test_cases, expected_output,
+        case(vec!["aa", "aaaa"], "^a{2,}$"),
+        case(vec!["abab", "ababab", "c"], "^(?:c|(?:ab){2,})$"),
+        case(vec!["xaaa", "xaa", "y"], "^(?:xa{2,}|y)$"),
+        case(vec!["aabcbc", "aaabcbcbc"], "^a{2,}(?:bc){2,}$"),
+        case(vec!["-aaa", "x-aa"], "^(?:x\\-a{2,}|\\-a{3,})$"),
+        case(vec!["ba", "bbaa"], "^b+a+$"),
+        case(vec!["aba", "abbaa"], "^ab+a+$"),
+        case(vec!["abc"], "^abc$")
+    )]
+    fn succeeds(test_cases: Vec<&str>, expected_output: &str) {
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_conversion_of(&[Feature::Repetition])
+            .with_unbounded_repetitions()
+            .build();
+        test_if_regexp_is_correct(regexp, expected_output, &test_cases);
+        test_if_regexp_matches_test_cases(expected_output, test_cases);
+    }
+
+    #[test]
+    fn succeeds_with_explicit_quantifiers() {
+        let regexp = RegExpBuilder::from(&["aa", "aaaa"])
+            .with_conversion_of(&[Feature::Repetition])
+            .with_unbounded_repetitions()
+            .with_explicit_quantifiers()
+            .build();
+        assert_eq!(regexp, "^a{2,}$");
+        test_if_regexp_matches_test_cases(&regexp, vec!["aa", "aaa", "aaaaaaaa"]);
+    }
+
+    #[test]
+    fn succeeds_without_match_count() {
+        let regexp = RegExpBuilder::from(&["aa", "aaaa"])
+            .with_conversion_of(&[Feature::Repetition])
+            .with_unbounded_repetitions()
+            .build_regexp();
+        assert_eq!(regexp.match_count(), None);
+    }
+}
+
+mod noise_chars {
+    use super::*;
+
+    #[rstest(test_cases, expected_output,
+        case(vec!["555-1234", "5551234"], "^555\\-?1234$"),
+        case(vec!["555-1234"], "^555\\-?1234$"),
+        case(vec!["555 1234", "555-1234"], "^555[ -]?1234$"),
+        case(vec!["a-b", "c"], "^(?:a\\-?b|c)$")
+    )]
+    fn succeeds(test_cases: Vec<&str>, expected_output: &str) {
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_noise_chars(&['-', ' '])
+            .build();
+        test_if_regexp_is_correct(regexp, expected_output, &test_cases);
+        test_if_regexp_matches_test_cases(expected_output, test_cases.clone());
+
+        for test_case in test_cases {
+            let denoised_test_case = test_case.replace(['-', ' '], "");
+            test_if_regexp_matches_test_cases(expected_output, vec![&denoised_test_case]);
+        }
+    }
+}
+
+mod top_level_group_suppression {
+    use super::*;
+
+    #[rstest(test_cases, features, expected_output,
+        case(vec!["abc", "def"], vec![], "^abc$|^def$"),
+        case(vec!["abc", "def", "defdef"], vec![Feature::Repetition], "^abc$|^def$|^(?:def){2}$"),
+        case(vec!["abc", "DEF"], vec![Feature::CaseInsensitivity], "(?i)^abc$|^def$"),
+        case(vec!["abc", "def"], vec![Feature::CapturingGroup], "^(abc|def)$"),
+        case(vec!["", "abc", "def"], vec![], "^(?:abc|def)?$"),
+        case(vec!["a", "b"], vec![], "^[ab]$"),
+        case(vec!["abc"], vec![], "^abc$")
+    )]
+    fn succeeds(test_cases: Vec<&str>, features: Vec<Feature>, expected_output: &str) {
+        let mut builder = RegExpBuilder::from(&test_cases);
+        if !features.is_empty() {
+            builder.with_conversion_of(&features);
+        }
+        let regexp = builder.with_top_level_group_suppressed().build();
+        test_if_regexp_is_correct(regexp, expected_output, &test_cases);
+        test_if_regexp_matches_test_cases(expected_output, test_cases);
+    }
+
+    #[test]
+    fn succeeds_without_changing_meaning_of_anchors() {
+        let regexp = RegExpBuilder::from(&["abc", "def"])
+            .with_top_level_group_suppressed()
+            .build();
+        let compiled_regexp = Regex::new(&regexp).unwrap();
+        for other_string in ["abcx", "xdef", "abcdef", "abc\ndef"] {
+            assert!(!compiled_regexp.is_match(other_string));
+        }
+    }
+
+    #[test]
+    fn succeeds_with_atomic_groups() {
+        let regexp = RegExpBuilder::from(&["abc", "def"])
+            .with_flavor(RegExpFlavor::Pcre)
+            .with_atomic_groups()
+            .with_top_level_group_suppressed()
+            .build();
+        assert_eq!(regexp, "^(?>abc|def)$");
+    }
+
+    #[test]
+    fn succeeds_with_literal_delimiter() {
+        let regexp = RegExpBuilder::from(&["abc", "DEF"])
+            .with_conversion_of(&[Feature::CaseInsensitivity])
+            .with_literal_delimiter('/')
+            .with_top_level_group_suppressed()
+            .build();
+        assert_eq!(regexp, "/^abc$|^def$/i");
+    }
+}
+
+mod pre_segmented_graphemes {
+    use super::*;
+    use grex::RegExp;
+
+    fn segment(test_cases: &[&str]) -> Vec<Vec<String>> {
+        test_cases
+            .iter()
+            .map(|it| it.chars().map(|c| c.to_string()).collect())
+            .collect()
+    }
+
+    #[rstest(test_cases, features,
+        case(vec!["abc", "abd"], vec![]),
+        case(vec!["", "a", "aa", "ba"], vec![]),
+        case(vec!["a", "b", "c", "xyz"], vec![]),
+        case(vec!["ababab", "cddd"], vec![Feature::Repetition]),
+        case(vec!["a1 b2", "c33 d"], vec![Feature::Digit, Feature::Space]),
+        case(vec!["ABc", "abd"], vec![Feature::CaseInsensitivity]),
+        case(vec!["xy", "xyxy", "z"], vec![Feature::Repetition, Feature::CapturingGroup])
+    )]
+    fn succeeds_with_default_segmentation_of_ascii(test_cases: Vec<&str>, features: Vec<Feature>) {
+        let mut builder = RegExpBuilder::from(&test_cases);
+        if !features.is_empty() {
+            builder.with_conversion_of(&features);
+        }
+        let config = builder.config().clone();
+        let expected_output = RegExp::new(&test_cases, &config).to_string();
+        let regexp = RegExp::from_graphemes(segment(&test_cases), &config);
+        assert_eq!(regexp.to_string(), expected_output);
+        assert!(regexp.verify(&test_cases));
+    }
+
+    #[test]
+    fn succeeds_with_custom_segmentation() {
+        let inputs = vec![
+            vec!["ch".to_string(), "a".to_string()],
+            vec!["c".to_string(), "a".to_string()],
+            vec!["ch".to_string(), "ch".to_string(), "a".to_string()],
+        ];
+        let config = RegExpBuilder::from(&["a"])
+            .with_conversion_of(&[Feature::Repetition])
+            .config()
+            .clone();
+        let regexp = RegExp::from_graphemes(inputs, &config);
+        assert_eq!(regexp.to_string(), "^(?:c|(?:ch){1,2})a$");
+        assert_eq!(
+            RegExp::new(&["cha", "ca", "chcha"], &config).to_string(),
+            "^(?:ch|(?:ch){2}|c)a$"
+        );
+    }
+
+    #[test]
+    fn succeeds_with_dedup_report() {
+        let inputs = vec![
+            vec!["a".to_string(), "b".to_string()],
+            vec!["ab".to_string()],
+            vec!["a".to_string(), "b".to_string()],
+        ];
+        let regexp = RegExp::from_graphemes(inputs, &RegExpConfig::new());
+        assert_eq!(regexp.dedup_report().removed(), ["ab"]);
+    }
+
+    #[test]
+    #[should_panic(expected = "No test cases have been provided for regular expression generation")]
+    fn fails_without_inputs() {
+        RegExp::from_graphemes(vec![], &RegExpConfig::new());
+    }
+
+    #[test]
+    #[should_panic(expected = "Empty graphemes are not allowed for regular expression generation")]
+    fn fails_with_empty_grapheme() {
+        RegExp::from_graphemes(
+            vec![vec!["a".to_string(), "".to_string()]],
+            &RegExpConfig::new(),
+        );
+    }
+}
+
+mod delimiter_detection {
+    use super::*;
+
+    #[rstest(test_cases, expected_output,
+        case(vec!["a,b,c", "x,y"], "^(?:[a-cxy],)*[a-cxy]$"),
+        case(vec!["a,b,c"], "^(?:[a-c],)*[a-c]$"),
+        case(vec!["foo,bar", "baz"], "^(?:(?:ba[rz]|foo),)*(?:ba[rz]|foo)$"),
+        case(vec!["a;b", "c;d;e"], "^(?:[a-e];)*[a-e]$"),
+        case(vec!["a|b", "c|d|e"], "^(?:[a-e]\\|)*[a-e]$"),
+        case(vec!["foo bar", "baz"], "^(?:(?:ba[rz]|foo) )*(?:ba[rz]|foo)$"),
+        case(vec!["a,b c", "d,e"], "^(?:(?:b c|[ade]),)*(?:b c|[ade])$"),
+        case(vec!["abc", "abd"], "^ab[cd]$"),
+        case(vec!["a,,b", "c"], "^(?:a,,b|c)$"),
+        case(vec![",a", "b"], "^(?:,a|b)$")
+    )]
+    fn succeeds(test_cases: Vec<&str>, expected_output: &str) {
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_delimiter_detection()
+            .build();
+        test_if_regexp_is_correct(regexp, expected_output, &test_cases);
+        test_if_regexp_matches_test_cases(expected_output, test_cases);
+    }
+
+    #[rstest(test_cases, expected_output,
+        case(vec!["1,22,333", "4"], "^(?:\\d{1,3},)*\\d{1,3}$"),
+        case(vec!["12,345"], "^(?:\\d{2,3},)*\\d{2,3}$")
+    )]
+    fn succeeds_with_numeric_detection(test_cases: Vec<&str>, expected_output: &str) {
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_delimiter_detection()
+            .with_numeric_detection()
+            .build();
+        test_if_regexp_is_correct(regexp, expected_output, &test_cases);
+        test_if_regexp_matches_test_cases(expected_output, test_cases);
+    }
+
+    #[test]
+    fn succeeds_with_delimiter_candidates() {
+        let test_cases = vec!["a-b,c", "d-e"];
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_delimiter_detection()
+            .with_delimiter_candidates(&['-'])
+            .build();
+        let expected_output = "^(?:(?:b,c|[ade])\\-)*(?:b,c|[ade])$";
+        test_if_regexp_is_correct(regexp, expected_output, &test_cases);
+        test_if_regexp_matches_test_cases(expected_output, test_cases);
+    }
+
+    #[test]
+    fn succeeds_with_generalization_of_field_count() {
+        let regexp = RegExpBuilder::from(&["a,b", "c,d,e"])
+            .with_delimiter_detection()
+            .build();
+        test_if_regexp_matches_test_cases(&regexp, vec!["a", "e,d,c,b,a"]);
+        let compiled_regexp = Regex::new(&regexp).unwrap();
+        assert!(!compiled_regexp.is_match("a,"));
+        assert!(!compiled_regexp.is_match("a,f"));
+    }
+
+    #[test]
+    fn succeeds_without_delimiter_detection() {
+        let regexp = RegExpBuilder::from(&["a,b", "c,d"]).build();
+        assert_eq!(regexp, "^(?:a,b|c,d)$");
+    }
+}
+
+mod explicit_quantifiers {
+    use super::*;
+
+    #[rstest(test_cases, expected_output,
+        case(vec!["a", "aa", "aaa"], "^a(?:aa{0,1}){0,1}$"),
+        case(vec!["ab", "abc"], "^abc{0,1}$"),
+        case(vec!["ab", "abcd"], "^ab(?:cd){0,1}$"),
+        case(vec!["abc"], "^abc$")
+    )]
+    fn succeeds(test_cases: Vec<&str>, expected_output: &str) {
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_explicit_quantifiers()
+            .build();
+        test_if_regexp_is_correct(regexp, expected_output, &test_cases);
+        test_if_regexp_matches_test_cases(expected_output, test_cases);
+    }
+
+    #[rstest(test_cases, expected_output,
+        case(vec!["a", "aa"], "^a{1,2}$"),
+        case(vec!["aaa", "bbbb"], "^(?:a{3}|b{4})$")
+    )]
+    fn succeeds_with_repetition_feature(test_cases: Vec<&str>, expected_output: &str) {
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_conversion_of(&[Feature::Repetition])
+            .with_explicit_quantifiers()
+            .build();
+        test_if_regexp_is_correct(regexp, expected_output, &test_cases);
+        test_if_regexp_matches_test_cases(expected_output, test_cases);
+    }
+}
+
+mod enclosing_char_detection {
+    use super::*;
+
+    #[rstest(test_cases, expected_output,
+        case(vec!["\"foo\"", "'foo'"], "^([\"'])foo\\1$"),
+        case(vec!["'a'", "\"bc\""], "^([\"'])(?:bc|a)\\1$"),
+        case(vec!["|x|", "/x/", "/y/"], "^([/|])[xy]\\1$"),
+        case(vec!["'foo'", "'bar'"], "^'(?:bar|foo)'$"),
+        case(vec!["'foo'", "\"foo'"], "^[\"']foo'$"),
+        case(vec!["'foo'", "a"], "^(?:'foo'|a)$")
+    )]
+    fn succeeds(test_cases: Vec<&str>, expected_output: &str) {
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_enclosing_char_detection()
+            .with_flavor(RegExpFlavor::Pcre)
+            .build();
+        test_if_regexp_is_correct(regexp, expected_output, &test_cases);
+    }
+
+    #[rstest(test_cases, expected_output,
+        case(vec!["\"foo\"", "'foo'"], "^(?:\"foo\"|'foo')$"),
+        case(vec!["|x|", "/x/", "/y/"], "^(?:/[xy]/|\\|x\\|)$")
+    )]
+    fn succeeds_without_backreferences_for_standard_flavor(
+        test_cases: Vec<&str>,
+        expected_output: &str,
+    ) {
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_enclosing_char_detection()
+            .with_flavor(RegExpFlavor::Standard)
+            .build();
+        test_if_regexp_is_correct(regexp, expected_output, &test_cases);
+        test_if_regexp_matches_test_cases(expected_output, test_cases);
+    }
+
+    #[test]
+    fn succeeds_with_match_count() {
+        let regexp = RegExpBuilder::from(&["\"a\"", "'b'"])
+            .with_enclosing_char_detection()
+            .with_flavor(RegExpFlavor::Pcre)
+            .build_regexp();
+        assert_eq!(regexp.to_string(), "^([\"'])[ab]\\1$");
+        assert_eq!(regexp.match_count(), Some(4));
+    }
+}
+
+mod dedup {
+    use super::*;
+
+    #[test]
+    fn succeeds_with_duplicates_removed() {
+        let regexp = RegExpBuilder::from(&["a", "a", "b"]).build_regexp();
+        assert_eq!(regexp.to_string(), RegExpBuilder::from(&["a", "b"]).build());
+        assert_eq!(regexp.dedup_report().removed(), &["a".to_string()]);
+    }
+
+    #[test]
+    fn succeeds_with_first_seen_order_of_duplicates() {
+        let regexp = RegExpBuilder::from(&["b", "a", "b", "a", "b"]).build_regexp();
+        assert_eq!(
+            regexp.dedup_report().removed(),
+            &["b".to_string(), "a".to_string(), "b".to_string()]
+        );
+    }
+
+    #[test]
+    fn succeeds_with_exact_comparison() {
+        let regexp = RegExpBuilder::from(&["a", "A"])
+            .with_conversion_of(&[Feature::CaseInsensitivity])
+            .build_regexp();
+        assert_eq!(regexp.to_string(), "(?i)^a$");
+        assert!(regexp.dedup_report().is_empty());
+    }
+
+    #[test]
+    fn succeeds_with_dedup_disabled() {
+        let regexp = RegExpBuilder::from(&["a", "a", "b"])
+            .with_dedup_disabled()
+            .build_regexp();
+        assert_eq!(regexp.to_string(), "^[ab]$");
+        assert!(regexp.dedup_report().is_empty());
+    }
+}
+
+mod emoji_zwj_sequences {
+    use super::*;
+
+    #[rstest(test_cases, expected_output,
+        case(vec!["👨‍👩‍👧", "👨‍👩‍👦"], "^(?:👨‍👩‍👦|👨‍👩‍👧)$"),
+        case(vec!["👨‍❤️‍👨", "👩‍❤️‍👩"], "^(?:👨‍❤️‍👨|👩‍❤️‍👩)$")
+    )]
+    fn succeeds(test_cases: Vec<&str>, expected_output: &str) {
+        let regexp = RegExpBuilder::from(&test_cases).build();
+        test_if_regexp_is_correct(regexp, expected_output, &test_cases);
+        test_if_regexp_matches_test_cases(expected_output, test_cases);
+    }
+
+    #[test]
+    fn succeeds_with_repetition_feature() {
+        let test_cases = vec!["👨‍❤️‍👨👨‍❤️‍👨"];
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_conversion_of(&[Feature::Repetition])
+            .build();
+        test_if_regexp_is_correct(regexp, "^(?:👨‍❤️‍👨){2}$", &test_cases);
+    }
+}
+
+mod skin_tone_modifiers {
+    use super::*;
+
+    #[rstest(test_cases, expected_output,
+        case(vec!["👍🏽", "👍🏿"], "^(?:👍🏽|👍🏿)$"),
+        case(vec!["👍🏽", "👍"], "^(?:👍|👍🏽)$")
+    )]
+    fn succeeds(test_cases: Vec<&str>, expected_output: &str) {
+        let regexp = RegExpBuilder::from(&test_cases).build();
+        test_if_regexp_is_correct(regexp, expected_output, &test_cases);
+        test_if_regexp_matches_test_cases(expected_output, test_cases);
+    }
+
+    #[test]
+    fn succeeds_with_repetition_feature() {
+        let test_cases = vec!["👍🏽👍🏽"];
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_conversion_of(&[Feature::Repetition])
+            .build();
+        test_if_regexp_is_correct(regexp, "^(?:👍🏽){2}$", &test_cases);
+    }
+}
+
+mod regional_indicator_flags {
+    use super::*;
+
+    #[rstest(test_cases, expected_output,
+        case(vec!["🇺🇸", "🇬🇧"], "^(?:🇬🇧|🇺🇸)$"),
+        case(vec!["🇺🇸", "🇺🇦"], "^(?:🇺🇦|🇺🇸)$")
+    )]
+    fn succeeds(test_cases: Vec<&str>, expected_output: &str) {
+        let regexp = RegExpBuilder::from(&test_cases).build();
+        test_if_regexp_is_correct(regexp, expected_output, &test_cases);
+        test_if_regexp_matches_test_cases(expected_output, test_cases);
+    }
+
+    #[rstest(test_cases, expected_output,
+        case(vec!["🇺🇸", "🇬🇧"], "^(?:\\u{1f1ec}\\u{1f1e7}|\\u{1f1fa}\\u{1f1f8})$"),
+        case(vec!["🇺🇸🇺🇸"], "^(?:\\u{1f1fa}\\u{1f1f8}){2}$")
+    )]
+    fn succeeds_with_escape_option(test_cases: Vec<&str>, expected_output: &str) {
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_conversion_of(&[Feature::Repetition])
+            .with_escaping_of_non_ascii_chars(false)
+            .build();
+        test_if_regexp_is_correct(regexp, expected_output, &test_cases);
+        test_if_regexp_matches_test_cases(expected_output, test_cases);
+    }
+
+    #[rstest(test_cases, expected_output,
+        case(vec!["🇺🇸", "🇺🇦"], "^(?:\\u{d83c}\\u{ddfa}\\u{d83c}\\u{dde6}|\\u{d83c}\\u{ddfa}\\u{d83c}\\u{ddf8})$"),
+        case(vec!["🇺🇸🇺🇸"], "^(?:\\u{d83c}\\u{ddfa}\\u{d83c}\\u{ddf8}){2}$")
+    )]
+    fn succeeds_with_escape_and_surrogate_option(test_cases: Vec<&str>, expected_output: &str) {
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_conversion_of(&[Feature::Repetition])
+            .with_escaping_of_non_ascii_chars(true)
+            .build();
+        test_if_regexp_is_correct(regexp, expected_output, &test_cases);
+    }
+}
+
+mod rust_bytes_flavor {
+    use super::*;
+
+    #[rstest(test_cases, expected_output,
+        case(vec!["abc"], "^abc$"),
+        case(vec!["é"], "^(?-u:\\xc3\\xa9)$"),
+        case(vec!["café", "cafe"], "^caf(?:e|(?-u:\\xc3\\xa9))$"),
+        case(vec!["é", "ö"], "^(?-u:\\xc3\\xa9|\\xc3\\xb6)$"),
+        case(vec!["a", "b", "c", "é"], "^(?:[a-c]|(?-u:\\xc3\\xa9))$"),
+        case(vec!["e\u{301}"], "^e(?-u:\\xcc\\x81)$"),
+        case(vec!["You smell like 💩."], "^You smell like (?-u:\\xf0\\x9f\\x92\\xa9)\\.$")
+    )]
+    fn succeeds(test_cases: Vec<&str>, expected_output: &str) {
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_flavor(RegExpFlavor::RustBytes)
+            .build();
+        test_if_regexp_is_correct(regexp, expected_output, &test_cases);
+        test_if_byte_regexp_matches_test_cases(expected_output, test_cases);
+    }
+
+    #[rstest(test_cases, expected_output,
+        case(vec!["éé", "ééé"], "^(?-u:\\xc3\\xa9){2,3}$"),
+        case(vec!["e\u{301}e\u{301}"], "^(?:e(?-u:\\xcc\\x81)){2}$")
+    )]
+    fn succeeds_with_repetition_feature(test_cases: Vec<&str>, expected_output: &str) {
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_conversion_of(&[Feature::Repetition])
+            .with_flavor(RegExpFlavor::RustBytes)
+            .build();
+        test_if_regexp_is_correct(regexp, expected_output, &test_cases);
+        test_if_byte_regexp_matches_test_cases(expected_output, test_cases);
+    }
+
+    #[test]
+    fn succeeds_with_ignored_escaping_of_non_ascii_chars() {
+        let test_cases = vec!["💩"];
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_escaping_of_non_ascii_chars(true)
+            .with_flavor(RegExpFlavor::RustBytes)
+            .build();
+        test_if_regexp_is_correct(regexp, "^(?-u:\\xf0\\x9f\\x92\\xa9)$", &test_cases);
+    }
+
+    fn test_if_byte_regexp_matches_test_cases(expected_output: &str, test_cases: Vec<&str>) {
+        let re = regex::bytes::Regex::new(expected_output).unwrap();
+        for test_case in test_cases {
+            assert!(
+                re.is_match(test_case.as_bytes()),
+                "\n\n\"{}\" does not match byte regex {}\n\n",
+                test_case,
+                expected_output
+            );
+        }
+    }
+}
+
+mod char_class_annotations {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn succeeds_with_indices_of_both_test_cases() {
+        let regexp = RegExpBuilder::from(&["xa", "xb"])
+            .with_char_class_annotations()
+            .build_regexp();
+        assert_eq!(regexp.to_string(), "^x[ab]$");
+        assert_eq!(
+            regexp.char_class_annotations(),
+            &[annotations(&[("a", vec![0]), ("b", vec![1])])]
+        );
+    }
+
+    #[test]
+    fn succeeds_with_member_shared_by_test_cases() {
+        let regexp = RegExpBuilder::from(&["a1", "b1", "a2"])
+            .with_char_class_annotations()
+            .build_regexp();
+        assert_eq!(regexp.to_string(), "^(?:a[12]|b1)$");
+        assert_eq!(
+            regexp.char_class_annotations(),
+            &[annotations(&[("1", vec![0]), ("2", vec![2])])]
+        );
+    }
+
+    #[test]
+    fn succeeds_with_separate_annotations_of_distinct_classes() {
+        let regexp = RegExpBuilder::from(&["ac", "bc", "ca", "cb"])
+            .with_char_class_annotations()
+            .build_regexp();
+        assert_eq!(regexp.to_string(), "^(?:[ab]c|c[ab])$");
+        assert_eq!(
+            regexp.char_class_annotations(),
+            &[
+                annotations(&[("a", vec![0]), ("b", vec![1])]),
+                annotations(&[("a", vec![2]), ("b", vec![3])])
+            ]
+        );
+    }
+
+    #[test]
+    fn succeeds_with_enclosing_chars() {
+        let regexp = RegExpBuilder::from(&["\"ab\"", "'ac'"])
+            .with_enclosing_char_detection()
+            .with_flavor(RegExpFlavor::Pcre)
+            .with_char_class_annotations()
+            .build_regexp();
+        assert_eq!(regexp.to_string(), "^([\"'])a[bc]\\1$");
+        assert_eq!(
+            regexp.char_class_annotations(),
+            &[
+                annotations(&[("\"", vec![0]), ("'", vec![1])]),
+                annotations(&[("b", vec![0]), ("c", vec![1])])
+            ]
+        );
+    }
+
+    #[test]
+    fn succeeds_without_annotations_by_default() {
+        let regexp = RegExpBuilder::from(&["a", "b"]).build_regexp();
+        assert!(regexp.char_class_annotations().is_empty());
+    }
+
+    fn annotations(entries: &[(&str, Vec<usize>)]) -> BTreeMap<String, Vec<usize>> {
+        entries
+            .iter()
+            .map(|(member, indices)| (member.to_string(), indices.clone()))
+            .collect()
+    }
+}
+
+mod generate_function {
+    use super::*;
+
+    #[rstest(test_cases, expected_output,
+        case(vec!["abc", "abd"], "^ab[cd]$"),
+        case(vec!["a", "aa", "aaa"], "^a(?:aa?)?$")
+    )]
+    fn succeeds_with_default_config(test_cases: Vec<&str>, expected_output: &str) {
+        let regexp = generate(&test_cases, &RegExpConfig::new());
+        test_if_regexp_is_correct(regexp, expected_output, &test_cases);
+    }
+
+    #[test]
+    fn succeeds_with_config_of_builder() {
+        let test_cases = vec!["aa", "bcbc", "defdefdef"];
+        let mut builder = RegExpBuilder::from(&test_cases);
+        builder.with_conversion_of(&[Feature::Repetition]);
+        let owned_test_cases = test_cases
+            .iter()
+            .map(|it| it.to_string())
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            generate(&owned_test_cases, builder.config()),
+            builder.build()
+        );
+    }
+}
+
+#[cfg(feature = "serde")]
+mod config_serialization {
+    use super::*;
+    use grex::try_generate;
+
+    #[test]
+    #[cfg(feature = "coloring")]
+    fn succeeds_with_round_trip_through_json() {
+        let mut builder = RegExpBuilder::from(&["abc"]);
+        builder
+            .with_conversion_of(&[Feature::Digit, Feature::CapturingGroup])
+            .with_escaping_of_non_ascii_chars(true)
+            .with_syntax_highlighting()
+            .with_color_theme(ColorTheme::new().with_pipe_color(Color::TrueColor {
+                r: 1,
+                g: 2,
+                b: 3,
+            }))
+            .with_alternation_order(AlternationOrder::Lexicographic)
+            .with_flavor(RegExpFlavor::RustBytes)
+            .with_noise_chars(&['-'])
+            .with_optional_surrounding_brackets(&[('(', ')')], true);
+        let config = builder.config();
+
+        let json = serde_json::to_string(config).unwrap();
+        let deserialized_config = serde_json::from_str::<RegExpConfig>(&json).unwrap();
+
+        assert_eq!(&deserialized_config, config);
+    }
+
+    #[test]
+    fn succeeds_with_snake_cased_field_names() {
+        let json = serde_json::to_value(RegExpConfig::new()).unwrap();
+
+        assert_eq!(json["is_output_colorized"], false);
+        assert_eq!(json["alternation_order"], "by_length");
+        assert_eq!(json["flavor"], "standard");
+        assert_eq!(json["color_theme"]["anchors"], "yellow");
+    }
+
+    #[test]
+    fn succeeds_with_defaults_for_missing_fields() {
+        let config = serde_json::from_str::<RegExpConfig>(
+            r#"{"conversion_features": ["digit"], "is_non_ascii_char_escaped": true}"#,
+        )
+        .unwrap();
+
+        assert_eq!(generate(&["1", "é"], &config), "^(?:\\d|\\u{e9})$");
+    }
+
+    #[test]
+    fn fails_with_ascii_only_output_and_disabled_escaping() {
+        let config = serde_json::from_str::<RegExpConfig>(
+            r#"{"is_ascii_only": true, "is_non_ascii_char_escaped": false}"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            try_generate(&["é"], &config),
+            Err(RegExpError::NonAsciiCharsNotEscaped)
+        );
+        assert_eq!(try_generate(&["e"], &config), Ok("^e$".to_string()));
+    }
+}
+
+fn test_if_regexp_is_correct(regexp: String, expected_output: &str, test_cases: &[&str]) {
+    assert_eq!(
+        regexp, expected_output,
+        "\n\ninput: {:?}\nexpected: {}\nactual: {}\n\n",
+        test_cases, expected_output, regexp
+    );
+}
+
+fn test_if_regexp_matches_test_cases(expected_output: &str, test_cases: Vec<&str>) {
+    let re = Regex::new(expected_output).unwrap();
+    for test_case in test_cases {
+        assert!(
+            re.is_match(test_case),
+            "\n\n\"{}\" does not match regex {}\n\n",
+            test_case,
+            expected_output
+        );
+    }
+}
+
+mod literal_quoting {
+    use super::*;
+
+    #[rstest(test_cases, expected_output,
+        case(vec!["a.b*c"], "^\\Qa.b*c\\E$"),
+        case(vec!["(a+b)"], "^\\Q(a+b)\\E$"),
+        case(vec!["a.b"], "^a\\.b$"),
+        case(vec!["abc"], "^abc$"),
+        case(vec!["a\\Eb.c"], "^\\Qa\\E\\\\E\\Qb.c\\E$"),
+        case(vec!["$.\\"], "^\\Q$.\\\\E$"),
+        case(vec!["a.b\nc.d"], "^a\\.b\\nc\\.d$"),
+        case(vec!["[a].\nb"], "^\\Q[a].\\E\\nb$"),
+        case(vec!["a.b*c", "a.b*d"], "^\\Qa.b*\\E[cd]$")
+    )]
+    fn succeeds(test_cases: Vec<&str>, expected_output: &str) {
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_flavor(RegExpFlavor::Pcre)
+            .with_literal_quoting()
+            .build();
+        assert_eq!(regexp, expected_output);
+    }
+
+    #[test]
+    fn succeeds_with_escaping_of_non_ascii_chars() {
+        let regexp = RegExpBuilder::from(&["ä.b*c"])
+            .with_flavor(RegExpFlavor::Pcre)
+            .with_escaping_of_non_ascii_chars(false)
+            .with_literal_quoting()
+            .build();
+        assert_eq!(regexp, "^\\u{e4}\\Q.b*c\\E$");
+    }
+
+    #[test]
+    fn succeeds_with_escaping_of_slashes() {
+        let regexp = RegExpBuilder::from(&["a.b/c*d"])
+            .with_flavor(RegExpFlavor::Pcre)
+            .with_escaping_of_slashes()
+            .with_literal_quoting()
+            .build();
+        assert_eq!(regexp, "^a\\.b\\/c\\*d$");
+    }
+
+    #[rstest(test_cases, expected_output,
+        case(vec!["+++..."], "^\\+{3}\\.{3}$"),
+        case(vec!["((a))"], "^\\({2}a\\){2}$"),
+        case(vec!["a.b*c"], "^\\Qa.b*c\\E$")
+    )]
+    fn succeeds_with_conversion_of_repetitions(test_cases: Vec<&str>, expected_output: &str) {
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_conversion_of(&[Feature::Repetition])
+            .with_flavor(RegExpFlavor::Pcre)
+            .with_literal_quoting()
+            .build();
+        assert_eq!(regexp, expected_output);
+    }
+
+    #[test]
+    fn succeeds_without_quoting_for_unsupported_flavors() {
+        let test_cases = vec!["a.b*c"];
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_literal_quoting()
+            .build();
+        assert_eq!(regexp, "^a\\.b\\*c$");
+        test_if_regexp_matches_test_cases(&regexp, test_cases);
+    }
+}
+
+mod max_output_length {
+    use super::*;
+
+    #[test]
+    fn succeeds_within_limit() {
+        let regexp = RegExpBuilder::from(&["abc", "def"])
+            .with_max_output_length(13)
+            .try_build();
+        assert_eq!(regexp, Ok("^(?:abc|def)$".to_string()));
+    }
+
+    #[test]
+    fn succeeds_with_colorized_output_within_limit() {
+        let regexp = RegExpBuilder::from(&["abc", "def"])
+            .with_syntax_highlighting()
+            .with_max_output_length(13)
+            .try_build();
+        assert!(regexp.is_ok());
+    }
+
+    #[test]
+    fn fails_beyond_limit() {
+        let test_cases = (0..100)
+            .map(|it| format!("{}x{}", it * 7, it))
+            .collect::<Vec<_>>();
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_max_output_length(50)
+            .try_build();
+        assert_eq!(regexp, Err(RegExpError::OutputTooLong(50)));
+    }
+
+    #[test]
+    fn fails_with_generate() {
+        let config = RegExpBuilder::from(&["a"])
+            .with_max_output_length(12)
+            .config()
+            .clone();
+        assert_eq!(
+            grex::try_generate(&["abc", "def"], &config),
+            Err(RegExpError::OutputTooLong(12))
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "The regular expression exceeds the maximum length of 5 characters")]
+    fn panics_with_build() {
+        RegExpBuilder::from(&["abc", "def"])
+            .with_max_output_length(5)
+            .build();
+    }
+}
+
+mod normalization {
+    use super::*;
+
+    #[rstest(test_cases, normalization, expected_output,
+        case(vec!["caf\u{e9}", "cafe\u{301}"], Normalization::Nfc, "^caf\u{e9}$"),
+        case(vec!["caf\u{e9}", "cafe\u{301}"], Normalization::Nfd, "^cafe\u{301}$"),
+        case(vec!["caf\u{e9}", "cafe\u{301}"], Normalization::None, "^caf(?:e\u{301}|\u{e9})$"),
+        case(vec!["\u{e9}", "e\u{301}", "a"], Normalization::Nfc, "^[a\u{e9}]$")
+    )]
+    fn succeeds(test_cases: Vec<&str>, normalization: Normalization, expected_output: &str) {
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_normalization(normalization)
+            .build();
+        test_if_regexp_is_correct(regexp, expected_output, &test_cases);
+    }
+
+    #[test]
+    fn succeeds_with_verification_of_raw_input() {
+        let regexp = RegExpBuilder::from(&["caf\u{e9}"])
+            .with_normalization(Normalization::Nfc)
+            .build_regexp();
+        assert!(regexp.verify(&["caf\u{e9}"]));
+        assert!(!regexp.verify(&["cafe\u{301}"]));
+    }
+
+    #[test]
+    fn succeeds_with_normalized_duplicates_in_dedup_report() {
+        let regexp = RegExpBuilder::from(&["caf\u{e9}", "cafe\u{301}"])
+            .with_normalization(Normalization::Nfc)
+            .build_regexp();
+        assert_eq!(regexp.dedup_report().removed(), &["caf\u{e9}".to_string()]);
+    }
+}
+
+mod stats {
+    use super::*;
+
+    #[rstest(test_cases, features, distinct_grapheme_count, alternation_branch_count, has_repetitions,
+        case(vec!["abc"], vec![], 3, 0, false),
+        case(vec!["abc", "abd"], vec![], 2, 0, false),
+        case(vec!["a", "bc", "bcd"], vec![], 4, 2, true),
+        case(vec!["abab", "cd"], vec![Feature::Repetition], 4, 2, true),
+        case(vec!["a1", "b22"], vec![Feature::Digit, Feature::Repetition], 3, 2, true)
+    )]
+    fn succeeds(
+        test_cases: Vec<&str>,
+        features: Vec<Feature>,
+        distinct_grapheme_count: usize,
+        alternation_branch_count: usize,
+        has_repetitions: bool,
+    ) {
+        let mut builder = RegExpBuilder::from(&test_cases);
+        if !features.is_empty() {
+            builder.with_conversion_of(&features);
+        }
+        let regexp = builder.build_regexp();
+        let stats = regexp.stats();
+        assert_eq!(stats.distinct_grapheme_count(), distinct_grapheme_count);
+        assert_eq!(stats.alternation_branch_count(), alternation_branch_count);
+        assert_eq!(stats.has_repetitions(), has_repetitions);
+        assert_eq!(stats.pattern_length(), regexp.to_regex_string().len());
+    }
+
+    #[test]
+    fn succeeds_with_enclosing_chars() {
+        let regexp = RegExpBuilder::from(&["'abc'", "\"abd\""])
+            .with_enclosing_char_detection()
+            .with_flavor(RegExpFlavor::Pcre)
+            .build_regexp();
+        let stats = regexp.stats();
+        assert_eq!(stats.distinct_grapheme_count(), 2);
+        assert_eq!(stats.alternation_branch_count(), 0);
+    }
+
+    #[test]
+    fn succeeds_without_ansi_escape_sequences() {
+        let regexp = RegExpBuilder::from(&["abc", "def"])
+            .with_syntax_highlighting()
+            .build_regexp();
+        assert_eq!(regexp.stats().pattern_length(), "^(?:abc|def)$".len());
+    }
+}
+
+mod custom_escapes {
+    use super::*;
+
+    #[rstest(test_cases, c, sequence, expected_output,
+        case(vec!["a\tb"], '\t', "\\x09", "^a\\x09b$"),
+        case(vec!["a\t", "b\t"], '\t', "\\x09", "^[ab]\\x09$"),
+        case(vec!["\t", "a"], '\t', "\\x09", "^[\\x09a]$"),
+        case(vec!["a.b"], '.', "\\x2e", "^a\\x2eb$"),
+        case(vec!["a b"], ' ', "\\x{20}", "^a\\x{20}b$"),
+        case(vec!["a\nb\tc"], '\t', "\\x09", "^a\\nb\\x09c$")
+    )]
+    fn succeeds(test_cases: Vec<&str>, c: char, sequence: &str, expected_output: &str) {
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_custom_escape(c, sequence)
+            .build();
+        test_if_regexp_is_correct(regexp, expected_output, &test_cases);
+        test_if_regexp_matches_test_cases(expected_output, test_cases);
+    }
+
+    #[test]
+    fn succeeds_with_shorthand_classes() {
+        let test_cases = vec!["1\t", "2\t"];
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_conversion_of(&[Feature::Digit])
+            .with_custom_escape('\\', "\\x5c")
+            .with_custom_escape('\t', "\\x09")
+            .build();
+        assert_eq!(regexp, "^\\d\\x09$");
+        test_if_regexp_matches_test_cases(&regexp, test_cases);
+    }
+
+    #[test]
+    fn succeeds_with_literal_quoting() {
+        let regexp = RegExpBuilder::from(&["a.b*c\td"])
+            .with_flavor(RegExpFlavor::Pcre)
+            .with_literal_quoting()
+            .with_custom_escape('\t', "\\x09")
+            .build();
+        assert_eq!(regexp, "^\\Qa.b*c\\E\\x09d$");
+    }
+
+    #[rstest(
+        c,
+        sequence,
+        case('\t', ""),
+        case('\t', "."),
+        case('\t', "\\"),
+        case('\t', "[\\t]"),
+        case('\t', "\\x{9"),
+        case('x', "y"),
+        case('x', "\\Q"),
+        case('b', "\\b"),
+        case('1', "\\1"),
+        case('1', "\\d")
+    )]
+    #[should_panic(expected = "is not a safe escape sequence")]
+    fn panics_with_unsafe_sequence(c: char, sequence: &str) {
+        RegExpBuilder::from(&["ab", "xb-"]).with_custom_escape(c, sequence);
+    }
+}
+
+mod prefix_matching {
+    use super::*;
+
+    #[rstest(test_cases, expected_output,
+        case(vec!["abc"], "^a(?:bc?)?$"),
+        case(vec!["abc", "abd"], "^a(?:b[cd]?)?$"),
+        case(vec!["ab", "cd"], "^(?:ab?|cd?)$"),
+        case(vec!["", "ab"], "^(?:ab?)?$"),
+        case(vec!["e\u{301}x"], "^e\u{301}x?$")
+    )]
+    fn succeeds(test_cases: Vec<&str>, expected_output: &str) {
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_prefix_matching()
+            .build();
+        test_if_regexp_is_correct(regexp, expected_output, &test_cases);
+        test_if_regexp_matches_test_cases(expected_output, test_cases);
+    }
+
+    #[test]
+    fn succeeds_with_matching_of_prefixes() {
+        let regexp = RegExpBuilder::from(&["abc"]).with_prefix_matching().build();
+        let compiled_regexp = Regex::new(&regexp).unwrap();
+        for prefix in ["a", "ab", "abc"] {
+            assert!(compiled_regexp.is_match(prefix), "{}", prefix);
+        }
+        for other_string in ["", "b", "bc", "abcd"] {
+            assert!(!compiled_regexp.is_match(other_string), "{}", other_string);
+        }
+    }
+
+    #[test]
+    fn succeeds_with_pre_segmented_graphemes() {
+        let config = RegExpBuilder::from(&["a"])
+            .with_prefix_matching()
+            .config()
+            .clone();
+        let regexp =
+            grex::RegExp::from_graphemes(vec![vec!["ch".to_string(), "a".to_string()]], &config);
+        assert_eq!(regexp.to_string(), "^cha?$");
+    }
+
+    #[test]
+    fn succeeds_without_prefixes_in_dedup_report() {
+        let regexp = RegExpBuilder::from(&["ab", "a"])
+            .with_prefix_matching()
+            .build_regexp();
+        assert!(regexp.dedup_report().is_empty());
+    }
+}
+
+mod branches {
+    use super::*;
+
+    #[rstest(test_cases, features, expected_branches,
+        case(vec!["abc", "de", "f"], vec![], vec!["^abc$", "^de$", "^f$"]),
+        case(vec!["abc", "abd"], vec![], vec!["^ab[cd]$"]),
+        case(vec!["", "abc", "de"], vec![], vec!["^(?:abc|de)?$"]),
+        case(vec!["a1", "bbb", "c."], vec![Feature::Digit, Feature::Repetition], vec!["^a\\d$", "^c\\.$", "^b{3}$"]),
+        case(vec!["ABC", "def", "x"], vec![Feature::CaseInsensitivity], vec!["(?i)^abc$", "(?i)^def$", "(?i)^x$"])
+    )]
+    fn succeeds(test_cases: Vec<&str>, features: Vec<Feature>, expected_branches: Vec<&str>) {
+        let mut builder = RegExpBuilder::from(&test_cases);
+        if !features.is_empty() {
+            builder.with_conversion_of(&features);
+        }
+        let branches = builder.build_regexp().branches();
+        assert_eq!(branches, expected_branches);
+        for branch in branches {
+            let compiled_regexp = Regex::new(&branch).unwrap();
+            assert!(test_cases.iter().any(|it| compiled_regexp.is_match(it)));
+        }
+    }
+
+    #[test]
+    fn succeeds_with_enclosing_chars() {
+        let regexp = RegExpBuilder::from(&["'abc'", "\"de\"", "'f'"])
+            .with_enclosing_char_detection()
+            .with_flavor(RegExpFlavor::Pcre)
+            .build_regexp();
+        assert_eq!(
+            regexp.branches(),
+            vec!["^([\"'])abc\\1$", "^([\"'])de\\1$", "^([\"'])f\\1$"]
+        );
+    }
+
+    #[test]
+    fn succeeds_with_literal_delimiter() {
+        let regexp = RegExpBuilder::from(&["abc", "DE"])
+            .with_conversion_of(&[Feature::CaseInsensitivity])
+            .with_literal_delimiter('/')
+            .build_regexp();
+        assert_eq!(regexp.branches(), vec!["/^abc$/i", "/^de$/i"]);
+    }
+}
+
+mod ruby_flavor {
+    use super::*;
+    use grex::ast::Expression;
+    use std::sync::Arc;
+
+    #[rstest(test_cases, expected_output,
+        case(vec!["1a2B", "ff00"], "\\A\\h{4}\\z"),
+        case(vec!["1a2b", "ff00"], "\\A[0-9a-f]{4}\\z"),
+        case(vec!["1A2B", "FF0"], "\\A[0-9A-F]{3,4}\\z")
+    )]
+    fn succeeds_with_hex_generalization(test_cases: Vec<&str>, expected_output: &str) {
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_hex_generalization()
+            .with_flavor(RegExpFlavor::Ruby)
+            .build();
+        test_if_regexp_is_correct(regexp, expected_output, &test_cases);
+    }
+
+    #[test]
+    fn succeeds_with_hex_digit_class() {
+        let test_cases = "0123456789abcdefABCDEF"
+            .chars()
+            .map(|it| it.to_string())
+            .collect::<Vec<_>>();
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_flavor(RegExpFlavor::Ruby)
+            .build();
+        assert_eq!(regexp, "\\A\\h\\z");
+
+        let regexp = RegExpBuilder::from(&test_cases).build();
+        assert_eq!(regexp, "^[0-9A-Fa-f]$");
+    }
+
+    #[test]
+    fn succeeds_with_atomic_groups() {
+        let regexp = RegExpBuilder::from(&["aaa", "bcbc"])
+            .with_conversion_of(&[Feature::Repetition])
+            .with_atomic_groups()
+            .with_flavor(RegExpFlavor::Ruby)
+            .build();
+        assert_eq!(regexp, "\\A(?>a{3}|(?>bc){2})\\z");
+    }
+
+    #[test]
+    fn succeeds_with_unicode_escapes() {
+        let regexp = RegExpBuilder::from(&["\u{e9}"])
+            .with_escaping_of_non_ascii_chars(false)
+            .with_flavor(RegExpFlavor::Ruby)
+            .build();
+        assert_eq!(regexp, "\\A\\u{e9}\\z");
+    }
+
+    #[test]
+    fn succeeds_with_non_hex_digit_class() {
+        let config = RegExpBuilder::from(&["a"])
+            .with_flavor(RegExpFlavor::Ruby)
+            .config()
+            .clone();
+        let expr = Expression::character_class(
+            ('\u{0}'..=char::MAX).filter(|it| !it.is_ascii_hexdigit()),
+            &Arc::new(config),
+        );
+        assert_eq!(expr.to_string(), "\\H");
+    }
+
+    #[test]
+    fn succeeds_with_multiline_mode() {
+        let regexp = RegExpBuilder::from(&["a", "b"])
+            .with_multiline_mode()
+            .with_flavor(RegExpFlavor::Ruby)
+            .build();
+        assert_eq!(regexp, "^[ab]$");
+    }
+
+    #[rstest(
+        literal_delimiter,
+        expected_output,
+        case(None, "(?m)\\Aa.b\\z"),
+        case(Some('/'), "/\\Aa.b\\z/m")
+    )]
+    fn succeeds_with_dotall_flag(literal_delimiter: Option<char>, expected_output: &str) {
+        let mut builder = RegExpBuilder::from(&["a\nb", "a1b", "axb"]);
+        builder
+            .with_wildcard_threshold(2)
+            .with_dotall_mode()
+            .with_flavor(RegExpFlavor::Ruby);
+        if let Some(delimiter) = literal_delimiter {
+            builder.with_literal_delimiter(delimiter);
+        }
+        assert_eq!(builder.build(), expected_output);
+    }
+}
+
+mod run_detection {
+    use super::*;
+
+    #[rstest(test_cases, expected_output,
+        case(vec!["aaa", "aaaa", "aaaaa"], "^a{3,5}$"),
+        case(vec!["aaa", "aaaaa"], "^a{3,5}$"),
+        case(vec!["a", "aa"], "^a{1,2}$"),
+        case(vec!["aaa"], "^a{3}$"),
+        case(vec!["...", "."], "^\\.{1,3}$"),
+        case(vec!["\u{e9}\u{e9}", "\u{e9}\u{e9}\u{e9}"], "^\u{e9}{2,3}$"),
+        case(vec!["\u{1f468}\u{200d}\u{1f469}", "\u{1f468}\u{200d}\u{1f469}\u{1f468}\u{200d}\u{1f469}"], "^(?:\u{1f468}\u{200d}\u{1f469}){1,2}$"),
+        case(vec!["aaa", "bbb"], "^(?:aaa|bbb)$"),
+        case(vec!["aaa", "aab"], "^aa[ab]$"),
+        case(vec!["", "aa"], "^(?:aa)?$")
+    )]
+    fn succeeds(test_cases: Vec<&str>, expected_output: &str) {
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_run_detection()
+            .build();
+        test_if_regexp_is_correct(regexp, expected_output, &test_cases);
+        test_if_regexp_matches_test_cases(expected_output, test_cases);
+    }
+
+    #[test]
+    fn succeeds_with_case_insensitivity() {
+        let test_cases = vec!["AAA", "aaaa"];
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_conversion_of(&[Feature::CaseInsensitivity])
+            .with_run_detection()
+            .build();
+        assert_eq!(regexp, "(?i)^a{3,4}$");
+        test_if_regexp_matches_test_cases(&regexp, test_cases);
+    }
+
+    #[rstest(test_cases, features, expected_output,
+        case(vec!["111", "11"], vec![Feature::Digit], "^\\d{2,3}$"),
+        case(vec!["123", "45"], vec![Feature::Digit], "^\\d{2,3}$"),
+        case(vec!["a1", "1"], vec![Feature::Digit], "^a?\\d$"),
+        case(vec!["aaa", "aaaaa"], vec![Feature::Repetition], "^a{3,5}$"),
+        case(vec!["111", "11"], vec![Feature::Digit, Feature::Repetition], "^\\d{2,3}$")
+    )]
+    fn succeeds_with_conversion_features(
+        test_cases: Vec<&str>,
+        features: Vec<Feature>,
+        expected_output: &str,
+    ) {
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_conversion_of(&features)
+            .with_run_detection()
+            .build();
+        test_if_regexp_is_correct(regexp, expected_output, &test_cases);
+        test_if_regexp_matches_test_cases(expected_output, test_cases);
+    }
+
+    #[test]
+    fn succeeds_with_verification() {
+        let test_cases = ["aaa", "aaaa", "aaaaa"];
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_run_detection()
+            .build_regexp();
+        assert!(regexp.verify(&test_cases));
+        assert!(!regexp.verify(&["aa"]));
+        assert!(!regexp.verify(&["aaaaaa"]));
+    }
+}
+
+mod input_trim {
+    use super::*;
+
+    #[rstest(test_cases, trim, expected_output,
+        case(vec!["a ", "a", "b\t"], InputTrim::Trailing, "^[ab]$"),
+        case(vec!["a ", "a", "b\t"], InputTrim::None, "^(?:a ?|b\\t)$"),
+        case(vec![" a", "a "], InputTrim::Trailing, "^ ?a$"),
+        case(vec![" a", "a "], InputTrim::Both, "^a$"),
+        case(vec!["a b ", "a b"], InputTrim::Both, "^a b$"),
+        case(vec!["   ", "a"], InputTrim::Both, "^a?$")
+    )]
+    fn succeeds(test_cases: Vec<&str>, trim: InputTrim, expected_output: &str) {
+        let regexp = RegExpBuilder::from(&test_cases)
+            .with_input_trim(trim)
+            .build();
+        test_if_regexp_is_correct(regexp, expected_output, &test_cases);
+    }
+
+    #[test]
+    fn succeeds_with_fewer_branches() {
+        let test_cases = ["abc ", "abc", "def  ", "def", "ghi"];
+        let untrimmed_regexp = RegExpBuilder::from(&test_cases).build_regexp();
+        let trimmed_regexp = RegExpBuilder::from(&test_cases)
+            .with_input_trim(InputTrim::Trailing)
+            .build_regexp();
+        assert_eq!(trimmed_regexp.to_string(), "^(?:abc|def|ghi)$");
+        assert_eq!(trimmed_regexp.branches().len(), 3);
+        assert!(
+            trimmed_regexp.stats().pattern_length() < untrimmed_regexp.stats().pattern_length()
+        );
+        assert!(trimmed_regexp.verify(&["abc", "def", "ghi"]));
+        assert!(!trimmed_regexp.verify(&["abc "]));
+    }
+
+    #[test]
+    fn succeeds_with_pre_segmented_graphemes() {
+        let config = RegExpBuilder::from(&["a"])
+            .with_input_trim(InputTrim::Both)
+            .config()
+            .clone();
+        let to_graphemes = |graphemes: &[&str]| graphemes.iter().map(|it| it.to_string()).collect();
+        let regexp = grex::RegExp::from_graphemes(
+            vec![
+                to_graphemes(&[" ", "ch", "a", " "]),
+                to_graphemes(&["ch", "a"]),
+            ],
+            &config,
+        );
+        assert_eq!(regexp.to_string(), "^cha$");
+    }
+
+    #[test]
+    fn succeeds_with_bytes() {
+        let regexp = RegExpBuilder::from_bytes(&[b"a\xa0".to_vec(), b"a ".to_vec()])
+            .with_input_trim(InputTrim::Trailing)
+            .build();
+        assert_eq!(regexp, "(?-u)^a\\xa0?$");
     }
 }
@@ -14,7 +14,7 @@
  * limitations under the License.
  */
 
-use grex::{Feature, RegExpBuilder};
+use grex::{ExactRepetitionStyle, Feature, RegExpBuilder};
 use proptest::prelude::*;
 use regex::{Error, Regex, RegexBuilder};
 
@@ -86,7 +86,7 @@ proptest! {
         let test_cases_vec = test_cases.iter().cloned().collect::<Vec<_>>();
         let regexp = RegExpBuilder::from(&test_cases_vec).build();
         if let Ok(compiled_regexp) = compile_regexp(&regexp) {
-            prop_assert!(test_cases.iter().all(|test_case| compiled_regexp.is_match(&test_case)));
+            prop_assert!(test_cases.iter().all(|test_case| compiled_regexp.is_match(test_case)));
         }
     }
 
@@ -100,7 +100,7 @@ proptest! {
             .with_escaping_of_non_ascii_chars(false)
             .build();
         if let Ok(compiled_regexp) = compile_regexp(&regexp) {
-            prop_assert!(test_cases.iter().all(|test_case| compiled_regexp.is_match(&test_case)));
+            prop_assert!(test_cases.iter().all(|test_case| compiled_regexp.is_match(test_case)));
         }
     }
 
@@ -119,7 +119,7 @@ proptest! {
             .with_minimum_substring_length(minimum_substring_length)
             .build();
         if let Ok(compiled_regexp) = compile_regexp(&regexp) {
-            prop_assert!(test_cases.iter().all(|test_case| compiled_regexp.is_match(&test_case)));
+            prop_assert!(test_cases.iter().all(|test_case| compiled_regexp.is_match(test_case)));
         }
     }
 
@@ -139,7 +139,7 @@ proptest! {
             .with_escaping_of_non_ascii_chars(false)
             .build();
         if let Ok(compiled_regexp) = compile_regexp(&regexp) {
-            prop_assert!(test_cases.iter().all(|test_case| compiled_regexp.is_match(&test_case)));
+            prop_assert!(test_cases.iter().all(|test_case| compiled_regexp.is_match(test_case)));
         }
     }
 
@@ -153,7 +153,7 @@ proptest! {
             let test_cases_vec = test_cases.iter().cloned().collect::<Vec<_>>();
             let regexp = RegExpBuilder::from(&test_cases_vec).build();
             if let Ok(compiled_regexp) = compile_regexp(&regexp) {
-                prop_assert!(other_strings.iter().all(|other_string| !compiled_regexp.is_match(&other_string)));
+                prop_assert!(other_strings.iter().all(|other_string| !compiled_regexp.is_match(other_string)));
             }
         }
     }
@@ -170,10 +170,173 @@ proptest! {
                 .with_escaping_of_non_ascii_chars(false)
                 .build();
             if let Ok(compiled_regexp) = compile_regexp(&regexp) {
-                prop_assert!(other_strings.iter().all(|other_string| !compiled_regexp.is_match(&other_string)));
+                prop_assert!(other_strings.iter().all(|other_string| !compiled_regexp.is_match(other_string)));
             }
         }
     }
+
+    #[test]
+    #[ignore]
+    fn matching_regexes_with_random_settings(
+        test_cases in prop::collection::hash_set(".{1,20}", 1..=10),
+        conversion_features in prop::collection::hash_set(conversion_feature_strategy(), 0..=9),
+        settings in prop::collection::hash_set(setting_strategy(), 0..=10)
+    ) {
+        let test_cases_vec = test_cases.iter().cloned().collect::<Vec<_>>();
+        let mut builder = RegExpBuilder::from(&test_cases_vec);
+        if !conversion_features.is_empty() {
+            builder.with_conversion_of(&conversion_features.into_iter().collect::<Vec<_>>());
+        }
+        for setting in settings {
+            setting.apply(&mut builder);
+        }
+        let regexp = builder.build();
+        let compiled_regexp = compile_regexp(&regexp);
+        prop_assert!(compiled_regexp.is_ok(), "invalid regex {:?}", regexp);
+        let compiled_regexp = compiled_regexp.unwrap();
+        for test_case in test_cases.iter() {
+            prop_assert!(
+                compiled_regexp.is_match(test_case),
+                "regex {:?} does not match test case {:?}", regexp, test_case
+            );
+        }
+    }
+
+    #[test]
+    #[ignore]
+    fn regexes_not_matching_other_strings_with_random_settings(
+        test_cases in prop::collection::hash_set(".{1,20}", 1..=10),
+        other_strings in prop::collection::hash_set(".{1,20}", 1..=10),
+        conversion_features in prop::collection::hash_set(
+            prop_oneof![Just(Feature::Repetition), Just(Feature::CapturingGroup)], 0..=2
+        ),
+        settings in prop::collection::hash_set(exact_setting_strategy(), 0..=10)
+    ) {
+        if test_cases.is_disjoint(&other_strings) {
+            let test_cases_vec = test_cases.iter().cloned().collect::<Vec<_>>();
+            let mut builder = RegExpBuilder::from(&test_cases_vec);
+            if !conversion_features.is_empty() {
+                builder.with_conversion_of(&conversion_features.into_iter().collect::<Vec<_>>());
+            }
+            for setting in settings {
+                setting.apply(&mut builder);
+            }
+            let regexp = builder.build();
+            if let Ok(compiled_regexp) = compile_regexp(&regexp) {
+                for other_string in other_strings.iter() {
+                    prop_assert!(
+                        !compiled_regexp.is_match(other_string),
+                        "regex {:?} matches other string {:?}", regexp, other_string
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// A setting of `RegExpBuilder` which is applied in addition to the conversion features.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+enum Setting {
+    AlternationToClassDisabled,
+    AlternationToClassThreshold(usize),
+    DecimalNumberDetection,
+    DedupDisabled,
+    DotallMode,
+    EnclosingCharDetection,
+    EscapingOfNonAsciiChars,
+    ExpandedExactRepetitions,
+    ExplicitQuantifiers,
+    FrequencyOrdering,
+    HexGeneralization,
+    LengthQuantification,
+    MinimumRangeLength(u32),
+    MinimumRepetitions(u32),
+    MinimumSubstringLength(u32),
+    MultilineMode,
+    NumericDetection,
+    OptionalCharDetection,
+    OptionalSignDetection,
+    PrefixFactoring,
+    PreservedInputOrder,
+    RangeCompressionDisabled,
+    SuffixFactoring,
+    UnboundedRepetitions,
+    WildcardThreshold(usize),
+}
+
+impl Setting {
+    fn apply(self, builder: &mut RegExpBuilder) {
+        match self {
+            Setting::AlternationToClassDisabled => builder.with_alternation_to_class_disabled(),
+            Setting::AlternationToClassThreshold(threshold) => {
+                builder.with_alternation_to_class_threshold(threshold)
+            }
+            Setting::DecimalNumberDetection => builder.with_decimal_number_detection(),
+            Setting::DedupDisabled => builder.with_dedup_disabled(),
+            Setting::DotallMode => builder.with_dotall_mode(),
+            Setting::EnclosingCharDetection => builder.with_enclosing_char_detection(),
+            Setting::EscapingOfNonAsciiChars => builder.with_escaping_of_non_ascii_chars(false),
+            Setting::ExpandedExactRepetitions => {
+                builder.with_exact_repetition_style(ExactRepetitionStyle::Expanded)
+            }
+            Setting::ExplicitQuantifiers => builder.with_explicit_quantifiers(),
+            Setting::FrequencyOrdering => builder.with_frequency_ordering(),
+            Setting::HexGeneralization => builder.with_hex_generalization(),
+            Setting::LengthQuantification => builder.with_length_quantification(),
+            Setting::MinimumRangeLength(length) => builder.with_minimum_range_length(length),
+            Setting::MinimumRepetitions(quantity) => builder.with_minimum_repetitions(quantity),
+            Setting::MinimumSubstringLength(length) => {
+                builder.with_minimum_substring_length(length)
+            }
+            Setting::MultilineMode => builder.with_multiline_mode(),
+            Setting::NumericDetection => builder.with_numeric_detection(),
+            Setting::OptionalCharDetection => builder.with_optional_char_detection(),
+            Setting::OptionalSignDetection => builder.with_optional_sign_detection(),
+            Setting::PrefixFactoring => builder.with_prefix_factoring(),
+            Setting::PreservedInputOrder => builder.with_preserved_input_order(),
+            Setting::RangeCompressionDisabled => builder.with_range_compression_disabled(),
+            Setting::SuffixFactoring => builder.with_suffix_factoring(),
+            Setting::UnboundedRepetitions => builder.with_unbounded_repetitions(),
+            Setting::WildcardThreshold(threshold) => builder.with_wildcard_threshold(threshold),
+        };
+    }
+}
+
+fn setting_strategy() -> impl Strategy<Value = Setting> {
+    prop_oneof![
+        exact_setting_strategy(),
+        Just(Setting::DecimalNumberDetection),
+        Just(Setting::EnclosingCharDetection),
+        Just(Setting::HexGeneralization),
+        Just(Setting::LengthQuantification),
+        Just(Setting::NumericDetection),
+        Just(Setting::OptionalSignDetection),
+        Just(Setting::UnboundedRepetitions),
+        (1..10usize).prop_map(Setting::WildcardThreshold)
+    ]
+}
+
+/// Settings which never make the resulting expression match more strings than the test cases.
+fn exact_setting_strategy() -> impl Strategy<Value = Setting> {
+    prop_oneof![
+        Just(Setting::AlternationToClassDisabled),
+        (2..5usize).prop_map(Setting::AlternationToClassThreshold),
+        Just(Setting::DedupDisabled),
+        Just(Setting::DotallMode),
+        Just(Setting::EscapingOfNonAsciiChars),
+        Just(Setting::ExpandedExactRepetitions),
+        Just(Setting::ExplicitQuantifiers),
+        Just(Setting::FrequencyOrdering),
+        (2..5u32).prop_map(Setting::MinimumRangeLength),
+        (1..5u32).prop_map(Setting::MinimumRepetitions),
+        (1..5u32).prop_map(Setting::MinimumSubstringLength),
+        Just(Setting::MultilineMode),
+        Just(Setting::OptionalCharDetection),
+        Just(Setting::PrefixFactoring),
+        Just(Setting::PreservedInputOrder),
+        Just(Setting::RangeCompressionDisabled),
+        Just(Setting::SuffixFactoring)
+    ]
 }
 
 fn conversion_feature_strategy() -> impl Strategy<Value = Feature> {
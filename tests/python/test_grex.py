@@ -0,0 +1,63 @@
+#
+# Copyright © 2019-2020 Peter M. Stahl pemistahl@gmail.com
+#
+# Licensed under the Apache License, Version 2.0 (the "License");
+# you may not use this file except in compliance with the License.
+# You may obtain a copy of the License at
+#
+# http://www.apache.org/licenses/LICENSE-2.0
+#
+# Unless required by applicable law or agreed to in writing, software
+# distributed under the License is distributed on an "AS IS" BASIS,
+# WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either expressed or implied.
+# See the License for the specific language governing permissions and
+# limitations under the License.
+
+import re
+
+import pytest
+
+from grex import generate
+
+
+@pytest.mark.parametrize(
+    "inputs, options, expected_output",
+    [
+        (["a", "aa", "aaa"], {}, "^a(?:aa?)?$"),
+        (["1", "23"], {"digits": True}, "^\\d(?:\\d)?$"),
+        (["big", "BIGGER"], {"ignore_case": True}, "(?i)^big(?:ger)?$"),
+        (["big", "BIGGER"], {"ignore_case": True, "capture_groups": True}, "(?i)^big(ger)?$"),
+        (["aa", "bcbc"], {"repetitions": True, "min_substring_length": 2}, "^(?:aa|(?:bc){2})$"),
+        (["You smell like 💩."], {"escape": True}, "^You smell like \\u{1f4a9}\\.$"),
+        (
+            ["You smell like 💩."],
+            {"escape": True, "with_surrogates": True},
+            "^You smell like \\u{d83d}\\u{dca9}\\.$",
+        ),
+    ],
+)
+def test_generate(inputs, options, expected_output):
+    regexp = generate(inputs, **options)
+    assert regexp == expected_output
+    if "escape" not in options:
+        for test_case in inputs:
+            assert re.search(regexp, test_case)
+
+
+@pytest.mark.parametrize(
+    "inputs, options",
+    [
+        ([], {}),
+        (["a"], {"with_surrogates": True}),
+        (["a"], {"repetitions": True, "min_repetitions": 0}),
+        (["a"], {"repetitions": True, "min_substring_length": 0}),
+    ],
+)
+def test_generate_raises_value_error(inputs, options):
+    with pytest.raises(ValueError):
+        generate(inputs, **options)
+
+
+def test_generate_rejects_unknown_options():
+    with pytest.raises(TypeError):
+        generate(["a"], colorize=True)